@@ -0,0 +1,55 @@
+use criterion::{criterion_group, criterion_main, Bencher, Criterion};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use triangulation::{Delaunay, Point};
+
+fn uniform(count: usize) -> Vec<Point> {
+    let mut rng = StdRng::seed_from_u64(1337);
+
+    (0..count)
+        .map(|_| Point::new(rng.gen_range(0.0, 10000.0), rng.gen_range(0.0, 10000.0)))
+        .collect()
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    // the first half is triangulated up front, the second one inserted
+    let single = |b: &mut Bencher, &&count: &&usize| {
+        let points = uniform(2 * count);
+
+        b.iter_with_setup(
+            || Delaunay::new(&points[..count]).unwrap(),
+            |mut t| {
+                for &p in &points[count..] {
+                    t.insert(p).unwrap();
+                }
+                t
+            },
+        )
+    };
+
+    let bulk = |b: &mut Bencher, &&count: &&usize| {
+        let points = uniform(2 * count);
+
+        b.iter_with_setup(
+            || Delaunay::new(&points[..count]).unwrap(),
+            |mut t| {
+                t.insert_all(&points[count..]).unwrap();
+                t
+            },
+        )
+    };
+
+    let counts = &[1000, 10_000];
+    c.bench_function_over_inputs("insert", single, counts);
+    c.bench_function_over_inputs("insert_all", bulk, counts);
+
+    let counts = &[100_000];
+    let mut large = Criterion::default().configure_from_args().sample_size(10);
+    large.bench_function_over_inputs("insert", single, counts);
+    large.bench_function_over_inputs("insert_all", bulk, counts);
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
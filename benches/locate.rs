@@ -0,0 +1,56 @@
+use criterion::{criterion_group, criterion_main, Bencher, Criterion};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use triangulation::{Delaunay, Point, Triangulation};
+
+fn uniform(count: usize) -> Vec<Point> {
+    let mut rng = StdRng::seed_from_u64(1337);
+
+    (0..count)
+        .map(|_| Point::new(rng.gen_range(0.0, 10000.0), rng.gen_range(0.0, 10000.0)))
+        .collect()
+}
+
+/// A scanline through the middle of the points, sampled at small steps
+fn scanline() -> Vec<Point> {
+    (0..1000)
+        .map(|i| Point::new(i as f32 * 10.0, 5000.0))
+        .collect()
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let counts = &[1000, 10_000, 100_000];
+
+    let plain = |b: &mut Bencher, &&count: &&usize| {
+        let points = uniform(count);
+        let t = Delaunay::new(&points).unwrap();
+        let path = scanline();
+
+        b.iter(|| path.iter().filter_map(|&p| t.locate(p)).count())
+    };
+
+    let hinted = |b: &mut Bencher, &&count: &&usize| {
+        let points = uniform(count);
+        let t = Delaunay::new(&points).unwrap();
+        let path = scanline();
+
+        b.iter(|| {
+            let mut hint = 0.into();
+            path.iter()
+                .filter_map(|&p| {
+                    let e = t.locate_with_hint(p, hint)?;
+                    hint = e;
+                    Some(e)
+                })
+                .count()
+        })
+    };
+
+    c.bench_function_over_inputs("locate", plain, counts);
+    c.bench_function_over_inputs("locate_with_hint", hinted, counts);
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -5,7 +5,7 @@ use rand::{Rng, SeedableRng};
 
 use triangulation::{Delaunay, Point};
 
-fn bench_uniform(count: usize) -> Delaunay {
+fn uniform(count: usize) -> Vec<Point> {
     let mut rng = StdRng::seed_from_u64(1337);
     let mut points = Vec::with_capacity(count);
 
@@ -15,20 +15,37 @@ fn bench_uniform(count: usize) -> Delaunay {
         points.push(Point::new(x, y));
     }
 
-    Delaunay::new(&points).unwrap()
+    points
 }
 
 fn criterion_benchmark(c: &mut Criterion) {
-    let bench = |b: &mut Bencher, &&count: &&usize| b.iter(|| bench_uniform(count));
+    let bench = |b: &mut Bencher, &&count: &&usize| {
+        let points = uniform(count);
+        b.iter(|| Delaunay::new(&points).unwrap())
+    };
+
+    let divconq = |b: &mut Bencher, &&count: &&usize| {
+        let points = uniform(count);
+        b.iter(|| Delaunay::new_divconq(&points).unwrap())
+    };
+
+    let tiled = |b: &mut Bencher, &&count: &&usize| {
+        let points = uniform(count);
+        b.iter(|| Delaunay::new_tiled(&points, 64).unwrap())
+    };
 
     let counts = &[100, 1000, 10_000];
     c.bench_function_over_inputs("uniform", bench, counts);
+    c.bench_function_over_inputs("uniform_divconq", divconq, counts);
+    c.bench_function_over_inputs("uniform_tiled", tiled, counts);
 
     let counts = &[100_000, 200_000, 500_000, 1_000_000];
-    Criterion::default()
-        .configure_from_args()
-        .sample_size(10)
-        .bench_function_over_inputs("uniform", bench, counts);
+    let mut large = Criterion::default().configure_from_args().sample_size(10);
+    large.bench_function_over_inputs("uniform", bench, counts);
+    large.bench_function_over_inputs("uniform_tiled", tiled, counts);
+
+    // divide and conquer is a reference implementation, not a fast path
+    large.bench_function_over_inputs("uniform_divconq", divconq, &[100_000, 200_000]);
 }
 
 criterion_group!(benches, criterion_benchmark);
@@ -0,0 +1,77 @@
+use std::path::PathBuf;
+
+use rand::Rng;
+use structopt::StructOpt;
+
+use triangulation::{Delaunay, Interpolator, Point};
+
+/// Samples a hilly height field at random points, interpolates it over the
+/// triangulation and saves the result as a grayscale PNG image
+#[derive(StructOpt, Debug)]
+#[structopt(name = "terrain")]
+struct Opt {
+    /// Number of height samples
+    #[structopt(short = "c", long = "count", default_value = "2000")]
+    count: usize,
+
+    /// Output image width
+    #[structopt(short = "w", long = "width", default_value = "1000")]
+    width: u32,
+
+    /// Output image height
+    #[structopt(short = "h", long = "height", default_value = "1000")]
+    height: u32,
+
+    /// Output file
+    #[structopt(short = "o", long = "output", parse(from_os_str))]
+    output: PathBuf,
+}
+
+/// Height in `0..=1` of a few overlapping waves
+fn height(p: Point, size: f32) -> f32 {
+    let (x, y) = (p.x / size * 10.0, p.y / size * 10.0);
+    let h = (x.sin() + (0.7 * y).cos() + (0.3 * (x + y)).sin()) / 3.0;
+    (h + 1.0) / 2.0
+}
+
+fn main() {
+    let opt = Opt::from_args();
+    let size = opt.width.max(opt.height) as f32;
+
+    let mut rng = rand::thread_rng();
+    let mut points = vec![
+        Point::new(0.0, 0.0),
+        Point::new(opt.width as f32, 0.0),
+        Point::new(opt.width as f32, opt.height as f32),
+        Point::new(0.0, opt.height as f32),
+    ];
+
+    for _ in 0..opt.count {
+        let x = rng.gen_range(0.0, opt.width as f32);
+        let y = rng.gen_range(0.0, opt.height as f32);
+        points.push(Point::new(x, y));
+    }
+
+    let heights = points.iter().map(|&p| height(p, size)).collect::<Vec<_>>();
+
+    let t = std::time::Instant::now();
+    let triangulation = Delaunay::new(&points).unwrap();
+    let interpolator = Interpolator::new(&triangulation, &heights);
+    println!(
+        "Created {} triangles in {:?}",
+        triangulation.dcel.num_triangles(),
+        t.elapsed()
+    );
+
+    let t = std::time::Instant::now();
+    let im = image::GrayImage::from_fn(opt.width, opt.height, |x, y| {
+        let p = Point::new(x as f32 + 0.5, y as f32 + 0.5);
+        let h = interpolator.interpolate(p).unwrap_or(0.0);
+        image::Luma([(h * 255.0) as u8])
+    });
+
+    println!("Interpolating took {:?}", t.elapsed());
+
+    im.save(&opt.output).unwrap();
+    println!("Saved as {}", opt.output.display());
+}
@@ -73,7 +73,7 @@ fn main() {
                 image::Rgb([r as u8, g as u8, b as u8])
             },
         );
-    };
+    }
 
     for t in triangulation.dcel.triangles(&points) {
         draw_line(im, t.0, t.1);
@@ -0,0 +1,431 @@
+use crate::geom::{Normalization, Point, Rect, Scalar};
+use crate::{preprocess, Delaunay, DelaunayError, PointIndex};
+
+/// Builder for [`Delaunay`] triangulations with non-default settings.
+///
+/// Distances are given as `f64` whatever the coordinate type, as both
+/// coordinate types convert to it exactly.
+///
+/// # Examples
+/// ```
+/// # use triangulation::{DelaunayBuilder, Point};
+/// let points = &[
+///     Point::new(500010.0, 4100010.0),
+///     Point::new(500100.0, 4100020.0),
+///     Point::new(500060.0, 4100120.0),
+/// ];
+///
+/// let t = DelaunayBuilder::new().normalize(true).build(points).unwrap();
+/// assert_eq!(t.dcel.num_triangles(), 1);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct DelaunayBuilder {
+    normalize: Option<bool>,
+    thin: Option<f64>,
+    tiles: Option<usize>,
+    clamp_weights: bool,
+    frame: Option<f64>,
+    tolerance: Option<f64>,
+}
+
+impl DelaunayBuilder {
+    /// Creates a builder with the default settings
+    pub fn new() -> DelaunayBuilder {
+        DelaunayBuilder::default()
+    }
+
+    /// Forces mapping the input points into the unit square before
+    /// triangulating them on or off.
+    ///
+    /// By default this is done only when the bounding box is far away from
+    /// the origin or its size is very large or very small, where the
+    /// predicates lose too much precision. The limits scale with the
+    /// precision of the coordinates, so `f64` points are rarely normalized.
+    pub fn normalize(mut self, normalize: bool) -> DelaunayBuilder {
+        self.normalize = Some(normalize);
+        self
+    }
+
+    /// Drops points closer than `spacing` to an already kept one before
+    /// triangulating, see [`preprocess::thin`].
+    ///
+    /// Point indices in the result still refer to the original slice, the
+    /// dropped points are just not part of the triangulation.
+    pub fn thin(mut self, spacing: f64) -> DelaunayBuilder {
+        self.thin = Some(spacing);
+        self
+    }
+
+    /// Triangulates the points in a grid of about `count` tiles and stitches
+    /// them together, see [`Delaunay::new_tiled`].
+    pub fn tiles(mut self, count: usize) -> DelaunayBuilder {
+        self.tiles = Some(count);
+        self
+    }
+
+    /// Makes [`Delaunay::cotan_weights`] clamp negative weights to zero.
+    ///
+    /// Negative weights appear opposite to obtuse angles on the hull and
+    /// break the maximum principle of the discrete Laplacian.
+    pub fn clamp_negative_weights(mut self, clamp: bool) -> DelaunayBuilder {
+        self.clamp_weights = clamp;
+        self
+    }
+
+    /// Surrounds the input with 4 synthetic corner points placed `margin`
+    /// times the bounding box diagonal outside of it.
+    ///
+    /// Every input point then becomes an interior vertex with a closed
+    /// Voronoi cell. The corners are available from
+    /// [`Delaunay::frame_points`] and [`Delaunay::frame_positions`], and can
+    /// be removed again with [`Delaunay::strip_frame`].
+    pub fn bounding_frame(mut self, margin: f64) -> DelaunayBuilder {
+        self.frame = Some(margin);
+        self
+    }
+
+    /// Drops points whose coordinates both differ by at most `epsilon` from
+    /// an already kept one before triangulating, see [`preprocess::dedup`].
+    ///
+    /// Without it only points equal up to the `EPSILON` of the coordinate
+    /// type are skipped. Like with [`thin`](DelaunayBuilder::thin), point
+    /// indices in the result still refer to the original slice.
+    pub fn tolerance(mut self, epsilon: f64) -> DelaunayBuilder {
+        self.tolerance = Some(epsilon);
+        self
+    }
+
+    /// Triangulates a set of given points, if it is possible.
+    pub fn build<T: Scalar>(&self, points: &[Point<T>]) -> Result<Delaunay<T>, DelaunayError> {
+        if points.iter().any(|p| !p.x.is_finite() || !p.y.is_finite()) {
+            return Err(DelaunayError::InvalidCoordinate);
+        }
+
+        let framed;
+        let input = match self.frame {
+            Some(margin) => {
+                framed = with_frame(points, margin).ok_or(DelaunayError::TooFewPoints)?;
+                &framed[..]
+            }
+            None => points,
+        };
+
+        let mut delaunay = self.build_input(input)?;
+
+        if self.frame.is_some() {
+            delaunay.frame = (points.len()..input.len()).map(PointIndex::from).collect();
+            delaunay.frame_positions = input[points.len()..].to_vec();
+        }
+
+        delaunay.set_points(input.to_vec());
+        Ok(delaunay)
+    }
+
+    fn build_input<T: Scalar>(&self, points: &[Point<T>]) -> Result<Delaunay<T>, DelaunayError> {
+        let mut kept: Option<(Vec<Point<T>>, Vec<usize>)> = None;
+
+        if let Some(spacing) = self.thin {
+            kept = Some(preprocess::thin(points, T::from_f64(spacing)));
+        }
+
+        if let Some(epsilon) = self.tolerance {
+            let input = kept.as_ref().map_or(points, |(kept, _)| &kept[..]);
+            let (deduped, mut indices) = preprocess::dedup(input, T::from_f64(epsilon));
+
+            if let Some((_, thinned)) = &kept {
+                indices.iter_mut().for_each(|i| *i = thinned[*i]);
+            }
+
+            kept = Some((deduped, indices));
+        }
+
+        let mut delaunay = match kept {
+            Some((kept, indices)) => {
+                let mut delaunay = self.build_all(&kept)?;
+                delaunay.remap_points(&indices, points.len());
+                delaunay
+            }
+            None => self.build_all(points)?,
+        };
+
+        delaunay.clamp_weights = self.clamp_weights;
+        Ok(delaunay)
+    }
+
+    fn build_all<T: Scalar>(&self, points: &[Point<T>]) -> Result<Delaunay<T>, DelaunayError> {
+        let rect = Rect::from_points(points).ok_or(DelaunayError::TooFewPoints)?;
+
+        let normalize = self.normalize.unwrap_or_else(|| needs_normalization(rect));
+
+        if !normalize {
+            return self.triangulate(points);
+        }
+
+        let normalization = Normalization::from_rect(rect);
+        let normalized = points
+            .iter()
+            .map(|&p| normalization.apply(p))
+            .collect::<Vec<_>>();
+
+        let mut delaunay = self.triangulate(&normalized)?;
+        delaunay.normalization = Some(normalization);
+        Ok(delaunay)
+    }
+
+    fn triangulate<T: Scalar>(&self, points: &[Point<T>]) -> Result<Delaunay<T>, DelaunayError> {
+        match self.tiles {
+            Some(count) => Delaunay::triangulate_tiled(points, count),
+            None => Delaunay::triangulate(points),
+        }
+    }
+}
+
+/// Returns the points followed by the corners of a frame around them
+fn with_frame<T: Scalar>(points: &[Point<T>], margin: f64) -> Option<Vec<Point<T>>> {
+    let rect = Rect::from_points(points)?;
+
+    let diagonal = rect.width().hypot(rect.height());
+    let offset = T::from_f64(margin) * if diagonal > T::ZERO { diagonal } else { T::ONE };
+    let (min, max) = (rect.min, rect.max);
+
+    let mut framed = points.to_vec();
+    framed.extend_from_slice(&[
+        Point::new(min.x - offset, min.y - offset),
+        Point::new(max.x + offset, min.y - offset),
+        Point::new(max.x + offset, max.y + offset),
+        Point::new(min.x - offset, max.y + offset),
+    ]);
+
+    Some(framed)
+}
+
+/// Returns true if the predicates are likely to be imprecise for points in
+/// `rect`, with limits tuned for `f32` and relaxed for more precise types
+fn needs_normalization<T: Scalar>(rect: Rect<T>) -> bool {
+    let extent = rect.width().max(rect.height()).to_f64();
+
+    let magnitude = [rect.min.x, rect.min.y, rect.max.x, rect.max.y]
+        .iter()
+        .fold(0.0f64, |m, v| m.max(v.to_f64().abs()));
+
+    // 1 for f32, smaller for more precise types
+    let precision = T::EPSILON.to_f64() / f64::from(f32::EPSILON);
+
+    magnitude * precision > 16.0 * extent || !(1e-3 * precision..=1e6 / precision).contains(&extent)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+    use crate::testing::{assert_delaunay, edge_set, random_points};
+    use crate::Triangulation;
+
+    #[test]
+    fn far_from_origin() {
+        let offset = 1_000_000.0;
+        let far = random_points(500, 7)
+            .into_iter()
+            .map(|p| Point::new(offset + p.x * 10.0, offset + p.y * 10.0))
+            .collect::<Vec<_>>();
+
+        // subtracting the offset back is exact
+        let centered = far
+            .iter()
+            .map(|p| Point::new(p.x - offset - 500.0, p.y - offset - 500.0))
+            .collect::<Vec<_>>();
+
+        let far = Delaunay::new(&far).unwrap();
+        let centered = Delaunay::new(&centered).unwrap();
+
+        assert!(far.normalization().is_some());
+        assert!(centered.normalization().is_none());
+        assert_eq!(edge_set(&far), edge_set(&centered));
+    }
+
+    #[test]
+    fn strip_frame() {
+        let points = random_points(300, 5)
+            .into_iter()
+            .map(|p| Point::new(p.x, p.y * 0.3))
+            .collect::<Vec<_>>();
+
+        let plain = Delaunay::new(&points).unwrap();
+
+        let mut framed = DelaunayBuilder::new()
+            .bounding_frame(0.1)
+            .build(&points)
+            .unwrap();
+        assert_eq!(framed.points().len(), 304);
+        assert_eq!(framed.frame_points().len(), 4);
+        assert_eq!(framed.hull().len(), 4);
+
+        framed.strip_frame();
+        assert_eq!(framed.points(), &points[..]);
+        assert!(framed.frame_points().is_empty());
+        assert_eq!(edge_set(&framed), edge_set(&plain));
+        assert_eq!(framed.hull().len(), plain.hull().len());
+    }
+
+    #[test]
+    fn strip_frame_after_insert() {
+        let mut points = random_points(100, 8);
+
+        let mut framed = DelaunayBuilder::new()
+            .bounding_frame(0.5)
+            .build(&points)
+            .unwrap();
+
+        let extra = random_points(20, 9);
+        framed.insert(extra[0]).unwrap();
+        framed.insert_all(&extra[1..]).unwrap();
+        assert_eq!(framed.points().len(), 124);
+
+        framed.strip_frame();
+        points.extend_from_slice(&extra);
+        assert_eq!(framed.points(), &points[..]);
+        assert!(framed.frame_points().is_empty());
+
+        let plain = Delaunay::new(&points).unwrap();
+        assert_delaunay(&framed, framed.points());
+        assert_eq!(edge_set(&framed), edge_set(&plain));
+    }
+
+    #[test]
+    fn thinned() {
+        let spacing = 5.0f32;
+        let points = random_points(2000, 3);
+
+        let t = DelaunayBuilder::new()
+            .thin(f64::from(spacing))
+            .build(&points)
+            .unwrap();
+        assert!(t.is_connected());
+
+        for (a, b, _) in t.dcel.wireframe_edges() {
+            assert!(points[a].distance_sq(points[b]) >= spacing * spacing);
+        }
+    }
+
+    #[test]
+    fn tolerance() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(6);
+        let epsilon = 0.5f32;
+
+        let mut points = random_points(500, 5);
+        let jittered = points
+            .iter()
+            .map(|p| {
+                Point::new(
+                    p.x + rng.gen_range(-0.4, 0.4),
+                    p.y + rng.gen_range(-0.4, 0.4),
+                )
+            })
+            .collect::<Vec<_>>();
+        points.extend(jittered);
+
+        for builder in &[
+            DelaunayBuilder::new().tolerance(f64::from(epsilon)),
+            DelaunayBuilder::new()
+                .thin(1.0)
+                .tolerance(f64::from(epsilon)),
+        ] {
+            let t = builder.build(&points).unwrap();
+            assert!(t.is_connected());
+
+            for (a, b, _) in t.dcel.wireframe_edges() {
+                assert!(!points[a].approx_eq_eps(points[b], epsilon));
+            }
+        }
+    }
+
+    #[test]
+    fn tolerance_f64() {
+        // just outside of 0.1, but inside of 0.1 rounded to `f32`
+        let offset = 0.100_000_000_5;
+        assert!(offset < f64::from(0.1f32));
+
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(0.0, 10.0),
+            Point::new(offset, offset),
+        ];
+
+        let t = DelaunayBuilder::new()
+            .tolerance(0.1)
+            .build(&points)
+            .unwrap();
+        assert_eq!(t.dcel.num_triangles(), 3);
+
+        let t = Delaunay::new_with_tolerance(&points, 0.1).unwrap();
+        assert_eq!(t.dcel.num_triangles(), 3);
+
+        let t = Delaunay::new_with_tolerance(&points, 0.2).unwrap();
+        assert_eq!(t.dcel.num_triangles(), 1);
+    }
+
+    #[test]
+    fn zero_tolerance() {
+        let mut points = random_points(200, 11);
+        points.extend_from_within(..50);
+
+        for &epsilon in &[0.0, -1.0, 1e-20] {
+            let t = Delaunay::new_with_tolerance(&points, epsilon).unwrap();
+            assert_eq!(edge_set(&t), edge_set(&Delaunay::new(&points).unwrap()));
+
+            let (kept, indices) = preprocess::dedup(&points, epsilon);
+            assert_eq!(kept.len(), if epsilon > 0.0 { 200 } else { 250 });
+            assert_eq!(indices.len(), kept.len());
+        }
+    }
+
+    #[test]
+    fn zero_spacing() {
+        let points = random_points(200, 12);
+
+        for &spacing in &[0.0, -1.0, 1e-20] {
+            let t = DelaunayBuilder::new().thin(spacing).build(&points).unwrap();
+            assert_eq!(edge_set(&t), edge_set(&Delaunay::new(&points).unwrap()));
+
+            let (kept, _) = preprocess::thin(&points, spacing as f32);
+            assert_eq!(kept, points);
+        }
+    }
+
+    #[test]
+    fn thin_nan_scores() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(0.5, 0.0),
+            Point::new(0.0, 0.5),
+            Point::new(5.0, 5.0),
+        ];
+        let scores = [f32::NAN, 0.1, -f32::NAN, f32::NAN];
+
+        let (_, indices) = preprocess::thin_by_score(&points, 1.0, &scores);
+        assert_eq!(indices, vec![1, 3]);
+    }
+
+    #[test]
+    fn thin_f64() {
+        // the fourth point is just farther than 0.1 from the first one, but
+        // closer than 0.1 rounded to `f32`
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(0.0, 10.0),
+            Point::new(0.100_000_000_5, 0.0),
+        ];
+
+        let t = DelaunayBuilder::new().thin(0.1).build(&points).unwrap();
+        assert_eq!(t.dcel.num_triangles(), 2);
+
+        let t = DelaunayBuilder::new()
+            .thin(f64::from(0.1f32))
+            .build(&points)
+            .unwrap();
+        assert_eq!(t.dcel.num_triangles(), 1);
+    }
+}
@@ -0,0 +1,753 @@
+//! Clipping of the triangulation to a polygon
+
+use std::collections::{HashMap, HashSet};
+
+use crate::contour::ring_contains;
+use crate::dcel::{EdgeIndex, EdgeLookup, PointIndex, TrianglesAroundPoint, TrianglesDCEL};
+use crate::geom::{Point, Scalar, Triangle};
+use crate::remove::clip_ears;
+use crate::{locate_from, Delaunay};
+
+impl<T: Scalar> Delaunay<T> {
+    /// Returns the part of the triangulation inside of the `boundary`
+    /// polygon, with the triangles crossing the boundary cut along it.
+    ///
+    /// The pieces of every triangle inside of the polygon are triangulated
+    /// again and linked into a new DCEL. The returned points are the input
+    /// `points`, so their indices stay valid, followed by the new ones: the
+    /// boundary vertices inside of the hull and the points where the
+    /// boundary crosses edges. A crossing is shared by the pieces on both
+    /// sides of its edge, so the result has no cracks.
+    ///
+    /// The polygon must be simple, but it may be concave and in either
+    /// orientation. Parts of it outside of the hull are cut off.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Delaunay, Point};
+    /// let points: &[Point] = &[
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(10.0, 0.0),
+    ///     Point::new(10.0, 10.0),
+    ///     Point::new(0.0, 10.0),
+    /// ];
+    /// let boundary = &[
+    ///     Point::new(2.0, 1.0),
+    ///     Point::new(8.0, 1.0),
+    ///     Point::new(8.0, 7.0),
+    ///     Point::new(2.0, 7.0),
+    /// ];
+    /// let t = Delaunay::new(points).unwrap();
+    /// let (clipped_points, clipped) = t.clip_to_polygon(boundary);
+    ///
+    /// // the boundary vertices and two crossings of the diagonal
+    /// assert_eq!(clipped_points.len(), 10);
+    /// assert_eq!(clipped.num_triangles(), 4);
+    /// assert!((clipped.total_area(&clipped_points) - 36.0).abs() < 1e-4);
+    /// ```
+    pub fn clip_to_polygon(&self, boundary: &[Point<T>]) -> (Vec<Point<T>>, TrianglesDCEL) {
+        let points = &self.points;
+        let mut clipper = Clipper::new(boundary, points);
+        let dcel = &self.dcel;
+
+        // walk along every boundary segment to find the triangles it touches
+        let mut segments = vec![Vec::new(); dcel.num_triangles()];
+        let mut hull = None;
+        let mut hint = EdgeIndex::from(0);
+
+        for i in 0..clipper.vertices.len() {
+            let (p, q) = (clipper.ring[i], clipper.ring[i + 1]);
+
+            let start = match locate_from(dcel, hint, p, points) {
+                Some(t) => {
+                    hint = t;
+                    Some(t)
+                }
+                // the segment starts outside of the hull
+                None => hull
+                    .get_or_insert_with(|| dcel.hull_edges().collect::<Vec<_>>())
+                    .iter()
+                    .map(|&e| dcel.triangle_first_edge(e))
+                    .find(|&t| touches(dcel, t, p, q, points)),
+            };
+
+            if let Some(start) = start {
+                for t in touched_triangles(dcel, start, p, q, points) {
+                    segments[t.as_usize() / 3].push(i);
+                }
+            }
+        }
+
+        for (t, segments) in segments.iter().enumerate() {
+            if !segments.is_empty() {
+                clipper.clip_triangle(dcel.triangle_points(EdgeIndex::from(3 * t)), segments);
+            }
+        }
+
+        // the other triangles are wholly inside or outside, which is known
+        // from the corners they share with clipped neighbors, or else tested
+        // once for every connected group of them
+        let mut inside = vec![None; dcel.num_triangles()];
+        for t in 0..dcel.num_triangles() {
+            if !segments[t].is_empty() || inside[t].is_some() {
+                continue;
+            }
+
+            let mut group = vec![t];
+            let mut next = 0;
+            let mut status = None;
+            inside[t] = Some(false);
+
+            while let Some(&u) = group.get(next) {
+                next += 1;
+
+                for e in dcel.triangle_edges(EdgeIndex::from(3 * u)).iter().cloned() {
+                    let neighbor = match dcel.twin(e) {
+                        Some(twin) => twin.as_usize() / 3,
+                        None => continue,
+                    };
+
+                    if !segments[neighbor].is_empty() {
+                        status =
+                            status.or_else(|| Some(clipper.inside.contains(&dcel.vertices[e])));
+                    } else if inside[neighbor].is_none() {
+                        inside[neighbor] = Some(false);
+                        group.push(neighbor);
+                    }
+                }
+            }
+
+            let status = status.unwrap_or_else(|| {
+                ring_contains(
+                    &clipper.ring,
+                    dcel.triangle(EdgeIndex::from(3 * t), points).centroid(),
+                )
+            });
+            for u in group {
+                inside[u] = Some(status);
+            }
+        }
+
+        for (t, inside) in inside.into_iter().enumerate() {
+            if inside == Some(true) {
+                clipper
+                    .triangles
+                    .push(dcel.triangle_points(EdgeIndex::from(3 * t)));
+            }
+        }
+
+        clipper.into_dcel()
+    }
+}
+
+/// Returns whether the closed triangle `t` and the segment from `p` to `q`
+/// have a point in common
+fn touches<T: Scalar>(
+    dcel: &TrianglesDCEL,
+    t: EdgeIndex,
+    p: Point<T>,
+    q: Point<T>,
+    points: &[Point<T>],
+) -> bool {
+    let corners = dcel.triangle_points(t).map(|c| points[c]);
+    let sides = corners
+        .iter()
+        .map(|&c| Triangle(p, q, c).robust_orientation())
+        .collect::<Vec<_>>();
+
+    let separated_by_segment = sides.iter().all(|&o| o > 0.0) || sides.iter().all(|&o| o < 0.0);
+    let separated_by_edge = (0..3).any(|k| {
+        let (a, b) = (corners[k], corners[(k + 1) % 3]);
+        Triangle(a, b, p).robust_orientation() < 0.0 && Triangle(a, b, q).robust_orientation() < 0.0
+    });
+
+    !separated_by_segment && !separated_by_edge
+}
+
+/// Returns the triangles touching the segment from `p` to `q`, searching
+/// around the corners of the triangles found so far, starting from `start`
+/// which has to touch it
+fn touched_triangles<T: Scalar>(
+    dcel: &TrianglesDCEL,
+    start: EdgeIndex,
+    p: Point<T>,
+    q: Point<T>,
+    points: &[Point<T>],
+) -> Vec<EdgeIndex> {
+    let mut found = vec![dcel.triangle_first_edge(start)];
+    let mut seen = HashSet::new();
+    seen.insert(found[0]);
+
+    let mut next = 0;
+    while let Some(&t) = found.get(next) {
+        next += 1;
+
+        for e in dcel.triangle_edges(t).iter().cloned() {
+            for around in TrianglesAroundPoint::new(dcel, e) {
+                let u = dcel.triangle_first_edge(around);
+                if seen.insert(u) && touches(dcel, u, p, q, points) {
+                    found.push(u);
+                }
+            }
+        }
+    }
+
+    found
+}
+
+/// Clips triangles to a polygon one at a time, collecting the pieces.
+///
+/// Where the boundary passes exactly through points or along edges, it is
+/// treated as if it was moved by an infinitesimal offset `(ε, ε²)`, which
+/// keeps the decisions for neighboring triangles consistent.
+struct Clipper<T> {
+    /// Closed ring of the polygon, with the inside on the left of its edges
+    /// like in the triangles
+    ring: Vec<Point<T>>,
+
+    /// Input points followed by the added ones
+    points: Vec<Point<T>>,
+
+    /// Added boundary vertices
+    vertices: Vec<Option<PointIndex>>,
+
+    /// Added crossings by the edge, as its ends in increasing order, and the
+    /// boundary segment
+    crossings: HashMap<(PointIndex, PointIndex, usize), PointIndex>,
+
+    /// Points of the pieces, among them the corners of the clipped triangles
+    /// which are inside of the polygon
+    inside: HashSet<PointIndex>,
+
+    triangles: Vec<[PointIndex; 3]>,
+}
+
+/// Position along the border of a triangle counted in edges from its first
+/// corner, followed by its derivatives by the offset of the boundary
+type Position = [f64; 3];
+
+/// Point where the boundary passes while going around a clipped triangle
+#[derive(Clone, Copy, Debug)]
+enum Stop {
+    /// Boundary vertex inside of the triangle
+    Vertex(PointIndex),
+    /// Crossing of the border of the triangle
+    Crossing {
+        point: PointIndex,
+        entering: bool,
+        position: Position,
+    },
+}
+
+/// Part of the boundary inside of a triangle
+struct Chain {
+    points: Vec<PointIndex>,
+    entry: Position,
+    exit: Position,
+}
+
+impl<T: Scalar> Clipper<T> {
+    fn new(boundary: &[Point<T>], points: &[Point<T>]) -> Clipper<T> {
+        let mut ring = boundary.to_vec();
+        ring.dedup();
+        if ring.len() > 1 && ring.first() == ring.last() {
+            ring.pop();
+        }
+
+        let twice_area: f64 = (1..ring.len().saturating_sub(1))
+            .map(|i| Triangle(ring[0], ring[i], ring[i + 1]).robust_orientation())
+            .sum();
+        if twice_area < 0.0 {
+            ring.reverse();
+        }
+
+        let vertices = vec![None; ring.len()];
+        if let Some(&first) = ring.first() {
+            ring.push(first);
+        }
+
+        Clipper {
+            ring,
+            points: points.to_vec(),
+            vertices,
+            crossings: HashMap::new(),
+            inside: HashSet::new(),
+            triangles: Vec::new(),
+        }
+    }
+
+    /// Clips a triangle to the polygon, given the boundary segments which
+    /// touch it in the order of the ring
+    fn clip_triangle(&mut self, corners: [PointIndex; 3], segments: &[usize]) {
+        let mut stops = Vec::new();
+
+        for &i in segments {
+            let vertex = self.ring[i];
+            if (0..3).all(|k| self.inner_side(corners[k], corners[(k + 1) % 3], vertex)) {
+                let point = corners
+                    .iter()
+                    .find(|&&c| self.points[c] == vertex)
+                    .map_or_else(|| self.vertex(i), |&c| c);
+                stops.push(Stop::Vertex(point));
+            }
+
+            // a segment enters a convex triangle before leaving it
+            let mut crossings = (0..3)
+                .filter_map(|k| self.crossing(corners, k, i))
+                .collect::<Vec<_>>();
+            crossings.sort_by_key(|stop| match *stop {
+                Stop::Crossing { entering, .. } => !entering,
+                Stop::Vertex(_) => unreachable!(),
+            });
+            stops.extend(crossings);
+        }
+
+        let first_entry = stops
+            .iter()
+            .position(|stop| matches!(stop, Stop::Crossing { entering: true, .. }));
+
+        let first_entry = match first_entry {
+            Some(first_entry) => first_entry,
+            // the boundary is inside of the triangle, or doesn't touch it
+            None => {
+                if !stops.is_empty() {
+                    let polygon = stops
+                        .iter()
+                        .filter_map(|stop| match *stop {
+                            Stop::Vertex(v) => Some(v),
+                            Stop::Crossing { .. } => None,
+                        })
+                        .collect();
+                    self.add_piece(polygon);
+                } else {
+                    let triangle = Triangle(
+                        self.points[corners[0]],
+                        self.points[corners[1]],
+                        self.points[corners[2]],
+                    );
+                    if ring_contains(&self.ring, triangle.centroid()) {
+                        self.inside.extend(corners.iter().cloned());
+                        self.triangles.push(corners);
+                    }
+                }
+                return;
+            }
+        };
+        stops.rotate_left(first_entry);
+
+        let mut chains: Vec<Chain> = Vec::new();
+        for stop in stops {
+            match stop {
+                Stop::Crossing {
+                    point,
+                    entering: true,
+                    position,
+                } => chains.push(Chain {
+                    points: vec![point],
+                    entry: position,
+                    exit: position,
+                }),
+                Stop::Crossing {
+                    point, position, ..
+                } => {
+                    let chain = chains.last_mut().unwrap();
+                    chain.points.push(point);
+                    chain.exit = position;
+                }
+                Stop::Vertex(v) => chains.last_mut().unwrap().points.push(v),
+            }
+        }
+
+        // join the chains along the border of the triangle (Weiler-Atherton)
+        let mut used = vec![false; chains.len()];
+        for start in 0..chains.len() {
+            let mut polygon = Vec::new();
+            let mut current = start;
+
+            while !used[current] {
+                used[current] = true;
+                let chain = &chains[current];
+                polygon.extend_from_slice(&chain.points);
+
+                let ahead = |position: Position| ahead(chain.exit, position);
+                let next = (0..chains.len())
+                    .min_by(|&a, &b| {
+                        ahead(chains[a].entry)
+                            .partial_cmp(&ahead(chains[b].entry))
+                            .unwrap()
+                    })
+                    .unwrap();
+
+                let corner = |k: usize| [k as f64, 0.0, 0.0];
+                let mut passed = (0..3)
+                    .filter(|&k| ahead(corner(k)) < ahead(chains[next].entry))
+                    .collect::<Vec<_>>();
+                passed.sort_by(|&a, &b| ahead(corner(a)).partial_cmp(&ahead(corner(b))).unwrap());
+                polygon.extend(passed.into_iter().map(|k| corners[k]));
+
+                current = next;
+            }
+
+            if !polygon.is_empty() {
+                self.add_piece(polygon);
+            }
+        }
+    }
+
+    /// Whether the boundary point `p` is on the side of the edge from `a` to
+    /// `b` where the triangle having that edge is
+    fn inner_side(&self, a: PointIndex, b: PointIndex, p: Point<T>) -> bool {
+        let (a, b) = (self.points[a], self.points[b]);
+
+        match Triangle(a, b, p).robust_orientation() {
+            o if o != 0.0 => o > 0.0,
+            // derivatives by the offset
+            _ if a.y != b.y => a.y < b.y,
+            _ => a.x > b.x,
+        }
+    }
+
+    /// Returns the crossing of the boundary segment `i` with the edge `k` of
+    /// the triangle, if there is one
+    fn crossing(&mut self, corners: [PointIndex; 3], k: usize, i: usize) -> Option<Stop> {
+        let (a, b) = (corners[k], corners[(k + 1) % 3]);
+        let (p, q) = (self.ring[i], self.ring[i + 1]);
+
+        let entering = self.inner_side(a, b, q);
+        if entering == self.inner_side(a, b, p) {
+            return None;
+        }
+
+        // orientations of the ends of the edge to the segment, which change
+        // by `(p.y - q.y) * ε - (p.x - q.x) * ε²` with the offset
+        let (pa, pb) = (self.points[a], self.points[b]);
+        let (oa, ob) = (
+            Triangle(p, q, pa).robust_orientation(),
+            Triangle(p, q, pb).robust_orientation(),
+        );
+        let inside = |o: f64| match o {
+            o if o != 0.0 => o > 0.0,
+            _ if p.y != q.y => p.y > q.y,
+            _ => p.x < q.x,
+        };
+        if inside(oa) == inside(ob) {
+            return None;
+        }
+
+        let on_edge = |x: Point<T>| Triangle(pa, pb, x).robust_orientation() == 0.0;
+        let along_edge = |x: Point<T>| ((x - pa).dot(pb - pa) / (pb - pa).length_sq()).to_f64();
+
+        let (s, point) = if oa == 0.0 {
+            (0.0, a)
+        } else if ob == 0.0 {
+            (1.0, b)
+        } else if on_edge(p) {
+            (along_edge(p), self.vertex(i))
+        } else if on_edge(q) {
+            (along_edge(q), self.vertex((i + 1) % self.vertices.len()))
+        } else {
+            // computed from the edge in a fixed direction, so the neighboring
+            // triangle gets the same point
+            let (lo, hi) = if a.as_usize() < b.as_usize() {
+                (a, b)
+            } else {
+                (b, a)
+            };
+            let (op, oq) = (
+                Triangle(self.points[lo], self.points[hi], p).robust_orientation(),
+                Triangle(self.points[lo], self.points[hi], q).robust_orientation(),
+            );
+            let t = T::from_f64(op / (op - oq));
+
+            let points = &mut self.points;
+            let point = *self.crossings.entry((lo, hi, i)).or_insert_with(|| {
+                points.push(p + (q - p) * t);
+                PointIndex::from(points.len() - 1)
+            });
+
+            (oa / (oa - ob), point)
+        };
+
+        let d = oa - ob;
+        let mut position = [
+            k as f64 + s,
+            (p.y - q.y).to_f64() / d,
+            (q.x - p.x).to_f64() / d,
+        ];
+        if position[0] >= 3.0 {
+            position[0] -= 3.0;
+        }
+
+        Some(Stop::Crossing {
+            point,
+            entering,
+            position,
+        })
+    }
+
+    /// Returns the index of the boundary vertex `i`, adding it to the points
+    fn vertex(&mut self, i: usize) -> PointIndex {
+        if let Some(v) = self.vertices[i] {
+            return v;
+        }
+
+        let v = PointIndex::from(self.points.len());
+        self.points.push(self.ring[i]);
+        self.vertices[i] = Some(v);
+        v
+    }
+
+    /// Triangulates a piece of a triangle, skipping slivers left where the
+    /// boundary only touches it
+    fn add_piece(&mut self, mut polygon: Vec<PointIndex>) {
+        self.inside.extend(polygon.iter().cloned());
+        let points = &self.points;
+
+        polygon.dedup_by(|a, b| points[*a] == points[*b]);
+        while polygon.len() > 1 && points[polygon[0]] == points[polygon[polygon.len() - 1]] {
+            polygon.pop();
+        }
+
+        let twice_area: f64 = (1..polygon.len().saturating_sub(1))
+            .map(|i| {
+                Triangle(
+                    points[polygon[0]],
+                    points[polygon[i]],
+                    points[polygon[i + 1]],
+                )
+                .robust_orientation()
+            })
+            .sum();
+
+        if polygon.len() >= 3 && twice_area > 0.0 {
+            self.triangles.extend(clip_ears(&polygon, points));
+        }
+    }
+
+    /// Links the collected triangles into a DCEL
+    fn into_dcel(self) -> (Vec<Point<T>>, TrianglesDCEL) {
+        let mut dcel = TrianglesDCEL::with_capacity(self.triangles.len());
+        for &t in &self.triangles {
+            dcel.add_triangle(t);
+        }
+
+        let lookup = EdgeLookup::new(&dcel);
+        for e in (0..dcel.vertices.len()).map(EdgeIndex::from) {
+            if let Some(twin) = lookup.edge_between(dcel.edge_endpoint(e), dcel.vertices[e]) {
+                dcel.link(e, twin);
+            }
+        }
+
+        (self.points, dcel)
+    }
+}
+
+/// Returns how far `position` is ahead of `from` going around the triangle
+fn ahead(from: Position, position: Position) -> Position {
+    let mut delta = [
+        position[0] - from[0],
+        position[1] - from[1],
+        position[2] - from[2],
+    ];
+
+    if delta < [0.0; 3] {
+        delta[0] += 3.0;
+    }
+
+    delta
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::testing::random_points;
+
+    fn star(center: Point, count: usize) -> Vec<Point> {
+        (0..2 * count)
+            .map(|i| {
+                let angle = i as f32 * std::f32::consts::PI / count as f32;
+                let radius = if i % 2 == 0 { 40.0 } else { 15.0 };
+                center + Point::new(angle.cos(), angle.sin()) * radius
+            })
+            .collect()
+    }
+
+    fn polygon_area(polygon: &[Point]) -> f64 {
+        let twice: f64 = (1..polygon.len() - 1)
+            .map(|i| Triangle(polygon[0], polygon[i], polygon[i + 1]).robust_orientation())
+            .sum();
+
+        twice.abs() / 2.0
+    }
+
+    fn on_boundary(boundary: &[Point], x: Point) -> bool {
+        (0..boundary.len()).any(|i| {
+            let (p, q) = (boundary[i], boundary[(i + 1) % boundary.len()]);
+            let d = q - p;
+            let t = (x - p).dot(d) / d.length_sq();
+
+            t > -1e-4 && t < 1.0 + 1e-4 && (x - p).cross(d).abs() / d.length() < 1e-3
+        })
+    }
+
+    /// Checks the links, orientations and that the result is a disk
+    fn assert_watertight(points: &[Point], dcel: &TrianglesDCEL) {
+        assert_eq!(dcel.validate(), Ok(()));
+        assert!(dcel.triangles(points).all(|t| t.robust_orientation() > 0.0));
+
+        let vertices = dcel.vertices.iter().collect::<HashSet<_>>().len();
+        let edges = dcel.num_interior_edges() + dcel.num_boundary_edges();
+        assert_eq!(vertices + dcel.num_triangles(), edges + 1);
+    }
+
+    #[test]
+    fn concave_boundary() {
+        let points = random_points(500, 3);
+        let t = Delaunay::new(&points).unwrap();
+        let boundary = star(Point::new(50.0, 50.0), 7);
+
+        let (clipped_points, clipped) = t.clip_to_polygon(&boundary);
+        assert_watertight(&clipped_points, &clipped);
+        assert_eq!(&clipped_points[..points.len()], &points[..]);
+
+        let area = f64::from(clipped.total_area(&clipped_points));
+        assert!((area - polygon_area(&boundary)).abs() < 1e-2);
+
+        for e in clipped.hull_edges() {
+            let a = clipped_points[clipped.vertices[e]];
+            let b = clipped_points[clipped.edge_endpoint(e)];
+            assert!(on_boundary(&boundary, a) && on_boundary(&boundary, (a + b) / 2.0));
+        }
+
+        let reversed = boundary.iter().rev().cloned().collect::<Vec<_>>();
+        let (_, clipped_reversed) = t.clip_to_polygon(&reversed);
+        assert_eq!(clipped_reversed.num_triangles(), clipped.num_triangles());
+    }
+
+    #[test]
+    fn f64_points() {
+        let points = random_points(500, 3)
+            .iter()
+            .map(|p| p.cast())
+            .collect::<Vec<Point<f64>>>();
+        let t = crate::Delaunay64::new(&points).unwrap();
+        let boundary = star(Point::new(50.0, 50.0), 7);
+
+        let (clipped_points, clipped) =
+            t.clip_to_polygon(&boundary.iter().map(|p| p.cast()).collect::<Vec<_>>());
+        assert_eq!(&clipped_points[..points.len()], &points[..]);
+        assert!(clipped.validate().is_ok());
+
+        let area = clipped.total_area(&clipped_points);
+        assert!((area - polygon_area(&boundary)).abs() < 1e-6 * area);
+    }
+
+    #[test]
+    fn halves() {
+        let points = random_points(300, 8);
+        let t = Delaunay::new(&points).unwrap();
+
+        let left = [
+            Point::new(-10.0, -10.0),
+            Point::new(50.0, -10.0),
+            Point::new(50.0, 110.0),
+            Point::new(-10.0, 110.0),
+        ];
+        let right = [
+            Point::new(50.0, -10.0),
+            Point::new(110.0, -10.0),
+            Point::new(110.0, 110.0),
+            Point::new(50.0, 110.0),
+        ];
+
+        let (left_points, left) = t.clip_to_polygon(&left);
+        let (right_points, right) = t.clip_to_polygon(&right);
+        assert_watertight(&left_points, &left);
+        assert_watertight(&right_points, &right);
+
+        assert!(left_points[points.len()..].iter().all(|p| p.x == 50.0));
+        let total = left.total_area(&left_points) + right.total_area(&right_points);
+        assert!((total - t.dcel.total_area(&points)).abs() < 1e-2);
+    }
+
+    #[test]
+    fn whole_and_nothing() {
+        let points = random_points(100, 1);
+        let t = Delaunay::new(&points).unwrap();
+
+        let around = [
+            Point::new(-1.0, -1.0),
+            Point::new(101.0, -1.0),
+            Point::new(101.0, 101.0),
+            Point::new(-1.0, 101.0),
+        ];
+        let (clipped_points, clipped) = t.clip_to_polygon(&around);
+        assert_eq!(clipped_points.len(), points.len());
+        assert_eq!(clipped.vertices, t.dcel.vertices);
+
+        let away = around.map(|p| p + Point::new(200.0, 0.0));
+        assert_eq!(t.clip_to_polygon(&away).1.num_triangles(), 0);
+    }
+
+    #[test]
+    fn inside_one_triangle() {
+        let points: &[Point] = &[
+            Point::new(0.0, 0.0),
+            Point::new(100.0, 0.0),
+            Point::new(0.0, 100.0),
+        ];
+        let t = Delaunay::new(points).unwrap();
+        let boundary = star(Point::new(30.0, 30.0), 3)
+            .into_iter()
+            .map(|p| (p - Point::new(30.0, 30.0)) * 0.5 + Point::new(30.0, 30.0))
+            .collect::<Vec<_>>();
+
+        let (clipped_points, clipped) = t.clip_to_polygon(&boundary);
+        assert_watertight(&clipped_points, &clipped);
+        assert_eq!(clipped_points.len(), 3 + boundary.len());
+        assert_eq!(clipped.num_triangles(), boundary.len() - 2);
+    }
+
+    #[test]
+    fn boundary_through_points() {
+        let points = (0..121)
+            .map(|i| Point::new((i % 11) as f32, (i / 11) as f32))
+            .collect::<Vec<_>>();
+        let t = Delaunay::new(&points).unwrap();
+
+        let boundaries = [
+            // the hull itself
+            vec![
+                Point::new(0.0, 0.0),
+                Point::new(10.0, 0.0),
+                Point::new(10.0, 10.0),
+                Point::new(0.0, 10.0),
+            ],
+            // along edges and through the diagonals
+            vec![
+                Point::new(5.0, 0.0),
+                Point::new(10.0, 5.0),
+                Point::new(5.0, 10.0),
+                Point::new(0.0, 5.0),
+            ],
+            // a reflex vertex on a point
+            vec![
+                Point::new(1.0, 1.0),
+                Point::new(9.0, 1.0),
+                Point::new(9.0, 9.0),
+                Point::new(5.0, 2.0),
+                Point::new(1.0, 9.0),
+            ],
+        ];
+
+        for boundary in &boundaries {
+            let (clipped_points, clipped) = t.clip_to_polygon(boundary);
+            assert_watertight(&clipped_points, &clipped);
+
+            let area = f64::from(clipped.total_area(&clipped_points));
+            assert!((area - polygon_area(boundary)).abs() < 1e-4);
+        }
+    }
+}
@@ -0,0 +1,717 @@
+//! Contour lines and filled bands of scalar fields defined at the points
+
+use std::collections::{HashMap, HashSet};
+
+use crate::dcel::{EdgeIndex, PointIndex, TrianglesDCEL};
+use crate::geom::{Point, Scalar};
+use crate::Triangulation;
+
+/// Extracts the lines along which the piecewise-linear function with the
+/// given values at the points is equal to each of the levels.
+///
+/// Returns the polylines of every level in the order of `levels`. Closed
+/// lines end with their first point repeated, open lines start and end on the
+/// hull. Lines run with the higher values on their left in the y-down
+/// coordinates, which is on the right when y goes up.
+///
+/// A value equal to a level counts as above it, so a contour passing exactly
+/// through points doesn't produce repeated points or segments, and contours
+/// touching a level at a lone point only are dropped.
+///
+/// # Panics
+///
+/// Panics if there are fewer values than points.
+///
+/// # Examples
+/// ```
+/// # use triangulation::{contour::contours, Delaunay, Point};
+/// let points = &[
+///     Point::new(0.0, 0.0),
+///     Point::new(10.0, 0.0),
+///     Point::new(10.0, 10.0),
+///     Point::new(0.0, 10.0),
+/// ];
+/// let t = Delaunay::new(points).unwrap();
+/// let lines = contours(&t, points, &[0.0, 10.0, 10.0, 0.0], &[5.0]);
+///
+/// assert_eq!(lines.len(), 1);
+/// assert_eq!(lines[0].len(), 1);
+/// assert!(lines[0][0].iter().all(|p| p.x == 5.0));
+/// ```
+pub fn contours<T: Triangulation + ?Sized>(
+    triangulation: &T,
+    points: &[Point<T::Scalar>],
+    values: &[T::Scalar],
+    levels: &[T::Scalar],
+) -> Vec<Vec<Vec<Point<T::Scalar>>>> {
+    let dcel = triangulation.dcel();
+    assert!(values.len() >= points.len());
+
+    levels
+        .iter()
+        .map(|&level| {
+            Contour {
+                dcel,
+                points,
+                values,
+                level,
+                visited: vec![false; dcel.num_triangles()],
+            }
+            .lines()
+        })
+        .collect()
+}
+
+/// Polygon with holes, which are rings closed with their first point repeated
+#[derive(Clone, Debug, PartialEq)]
+pub struct Polygon<T = f32> {
+    /// Outer ring, ordered like the triangles of the triangulation
+    pub exterior: Vec<Point<T>>,
+    /// Rings of the holes, ordered the other way around
+    pub holes: Vec<Vec<Point<T>>>,
+}
+
+impl<T: Scalar> Polygon<T> {
+    /// Returns the area inside of the exterior and outside of the holes
+    pub fn area(&self) -> T {
+        let holes = self.holes.iter().map(|hole| ring_area(hole).abs());
+        holes.fold(ring_area(&self.exterior).abs(), |area, hole| area - hole)
+    }
+}
+
+/// Extracts the areas where the piecewise-linear function with the given
+/// values at the points lies between consecutive levels.
+///
+/// Returns the polygons of `levels.len() + 1` bands: below the first level,
+/// between every two consecutive levels and above the last one. Every
+/// triangle is clipped to the bands, and the pieces of a band are merged
+/// along the edges they share. The bands cover the triangulation without
+/// overlapping, parts of the triangulation which are flat at a level belong to
+/// the band above it.
+///
+/// # Panics
+///
+/// Panics if there are fewer values than points, or if the levels aren't
+/// increasing.
+///
+/// # Examples
+/// ```
+/// # use triangulation::{contour::isobands, Delaunay, Point};
+/// let points = &[
+///     Point::new(0.0, 0.0),
+///     Point::new(10.0, 0.0),
+///     Point::new(10.0, 10.0),
+///     Point::new(0.0, 10.0),
+/// ];
+/// let t = Delaunay::new(points).unwrap();
+/// let bands = isobands(&t, points, &[0.0, 10.0, 10.0, 0.0], &[2.0, 5.0]);
+///
+/// let areas = bands
+///     .iter()
+///     .map(|band| band.iter().map(|p| p.area()).sum::<f32>())
+///     .collect::<Vec<_>>();
+/// assert_eq!(areas, vec![20.0, 30.0, 50.0]);
+/// ```
+pub fn isobands<T: Triangulation + ?Sized>(
+    triangulation: &T,
+    points: &[Point<T::Scalar>],
+    values: &[T::Scalar],
+    levels: &[T::Scalar],
+) -> Vec<Vec<Polygon<T::Scalar>>> {
+    assert!(values.len() >= points.len());
+    assert!(
+        levels.windows(2).all(|w| w[0] < w[1]),
+        "levels must be increasing"
+    );
+
+    let dcel = triangulation.dcel();
+
+    (0..=levels.len())
+        .map(|band| {
+            let low = band.checked_sub(1).map(|i| levels[i]);
+            let high = levels.get(band).cloned();
+            let mut boundary = Boundary::default();
+
+            for t in (0..dcel.num_triangles()).map(|t| EdgeIndex::from(3 * t)) {
+                if let Some(piece) = band_piece(dcel, t, points, values, low, high) {
+                    boundary.add_ring(&piece);
+                }
+            }
+
+            boundary.polygons()
+        })
+        .collect()
+}
+
+/// Traces the lines of a single level
+struct Contour<'a, T> {
+    dcel: &'a TrianglesDCEL,
+    points: &'a [Point<T>],
+    values: &'a [T],
+    level: T,
+
+    /// Triangles already crossed, each is crossed at most once
+    visited: Vec<bool>,
+}
+
+impl<'a, T: Scalar> Contour<'a, T> {
+    fn lines(mut self) -> Vec<Vec<Point<T>>> {
+        let mut lines = Vec::new();
+        let edges = (0..self.dcel.vertices.len()).map(EdgeIndex::from);
+
+        // open lines first, so that they are traced from their very start
+        for e in edges.clone() {
+            if self.dcel.twin(e).is_none() && self.is_entry(e) {
+                lines.push(self.trace(e));
+            }
+        }
+
+        for e in edges {
+            if !self.visited[e.as_usize() / 3] && self.is_entry(e) {
+                lines.push(self.trace(e));
+            }
+        }
+
+        let lines = lines.into_iter().filter_map(simplify).collect();
+        remove_repeated_segments(lines)
+    }
+
+    fn is_above(&self, e: EdgeIndex) -> bool {
+        self.values[self.dcel.vertices[e].as_usize()] >= self.level
+    }
+
+    /// Whether the line enters the triangle of the edge through it, which is
+    /// when the edge goes from above the level to below it
+    fn is_entry(&self, e: EdgeIndex) -> bool {
+        self.is_above(e) && !self.is_above(self.dcel.next_edge(e))
+    }
+
+    /// Follows the line from the entry edge until it leaves the hull or comes
+    /// back to where it started
+    fn trace(&mut self, entry: EdgeIndex) -> Vec<Point<T>> {
+        let mut line = vec![self.crossing(entry)];
+        let mut e = entry;
+
+        loop {
+            self.visited[e.as_usize() / 3] = true;
+
+            // the line leaves through the other edge crossing the level,
+            // which goes from below to above
+            let (next, prev) = (self.dcel.next_edge(e), self.dcel.prev_edge(e));
+            let exit = if self.is_above(prev) { next } else { prev };
+
+            line.push(self.crossing(exit));
+
+            match self.dcel.twin(exit) {
+                Some(twin) if !self.visited[twin.as_usize() / 3] => e = twin,
+                _ => break line,
+            }
+        }
+    }
+
+    /// Point of the edge at the level, the same for both of its halves
+    fn crossing(&self, e: EdgeIndex) -> Point<T> {
+        let (a, b) = (self.dcel.vertices[e], self.dcel.edge_endpoint(e));
+        crossing(self.points, self.values, a, b, self.level)
+    }
+}
+
+/// Point of the edge between `a` and `b` at the level, computed the same way
+/// whichever the order of the points, and exactly at a point with the value
+/// of the level
+fn crossing<T: Scalar>(
+    points: &[Point<T>],
+    values: &[T],
+    a: PointIndex,
+    b: PointIndex,
+    level: T,
+) -> Point<T> {
+    let (a, b) = if a.as_usize() < b.as_usize() {
+        (a.as_usize(), b.as_usize())
+    } else {
+        (b.as_usize(), a.as_usize())
+    };
+
+    let (va, vb) = (values[a], values[b]);
+
+    if va == level {
+        points[a]
+    } else if vb == level {
+        points[b]
+    } else {
+        let t = (level - va) / (vb - va);
+        points[a].lerp(points[b], t)
+    }
+}
+
+/// Removes repeated points and the spikes going back along the same segment,
+/// which appear when the level is met exactly at the points. Returns `None`
+/// if nothing but a single point is left.
+fn simplify<T: Scalar>(line: Vec<Point<T>>) -> Option<Vec<Point<T>>> {
+    let closed = line.len() > 2 && line.first() == line.last();
+    let mut out: Vec<Point<T>> = Vec::with_capacity(line.len());
+
+    for p in line {
+        if out.last() == Some(&p) {
+            continue;
+        }
+
+        if out.len() >= 2 && out[out.len() - 2] == p {
+            out.pop();
+            continue;
+        }
+
+        out.push(p);
+    }
+
+    if closed {
+        // a spike at the start of the loop wraps around its end
+        while out.len() >= 3 && out[1] == out[out.len() - 2] {
+            out.pop();
+            out.remove(0);
+        }
+
+        if out.len() < 4 {
+            return None;
+        }
+    } else if out.len() < 2 {
+        return None;
+    }
+
+    Some(out)
+}
+
+/// Removes the segments which were already output in either direction,
+/// splitting the lines at them.
+///
+/// When the level is met exactly along an edge with lower values on both
+/// sides, the line comes along the edge on both sides.
+fn remove_repeated_segments<T: Scalar>(lines: Vec<Vec<Point<T>>>) -> Vec<Vec<Point<T>>> {
+    let mut seen = HashSet::new();
+    let mut out = Vec::with_capacity(lines.len());
+
+    for line in lines {
+        let closed = line.first() == line.last();
+        let first = out.len();
+        let mut current = Vec::new();
+        let mut split = false;
+
+        for w in line.windows(2) {
+            let (a, b) = (w[0], w[1]);
+
+            if seen.contains(&(b, a)) || !seen.insert((a, b)) {
+                split = true;
+                if current.len() >= 2 {
+                    out.push(current);
+                }
+                current = Vec::new();
+                continue;
+            }
+
+            if current.is_empty() {
+                current.push(a);
+            }
+            current.push(b);
+        }
+
+        // a split loop continues from its end to its start
+        if closed && split && current.len() >= 2 && out.len() > first && out[first][0] == line[0] {
+            current.extend_from_slice(&out.remove(first)[1..]);
+        }
+
+        if current.len() >= 2 {
+            out.push(current);
+        }
+    }
+
+    out
+}
+
+/// Clips the triangle to the band between the levels, `None` meaning
+/// unbounded. Returns `None` if nothing but a point or a segment is left.
+fn band_piece<T: Scalar>(
+    dcel: &TrianglesDCEL,
+    t: EdgeIndex,
+    points: &[Point<T>],
+    values: &[T],
+    low: Option<T>,
+    high: Option<T>,
+) -> Option<Vec<Point<T>>> {
+    let value = |p: PointIndex| values[p.as_usize()];
+
+    // flat at the upper level, so it belongs to the band above
+    if high.is_some()
+        && dcel
+            .triangle_points(t)
+            .iter()
+            .all(|&p| Some(value(p)) == high)
+    {
+        return None;
+    }
+
+    let in_band = |v: T| low.map_or(true, |low| v >= low) && high.map_or(true, |high| v <= high);
+    let mut piece: Vec<Point<T>> = Vec::with_capacity(5);
+
+    for &e in &dcel.triangle_edges(t) {
+        let (a, b) = (dcel.vertices[e], dcel.edge_endpoint(e));
+        let (va, vb) = (value(a), value(b));
+
+        if in_band(va) {
+            piece.push(points[a.as_usize()]);
+        }
+
+        // levels crossed going from `a` to `b`
+        let mut levels = [low, high];
+        if va > vb {
+            levels.reverse();
+        }
+
+        for &level in levels.iter().flatten() {
+            if va.min(vb) < level && level < va.max(vb) {
+                let p = crossing(points, values, a, b, level);
+
+                if piece.last() != Some(&p) {
+                    piece.push(p);
+                }
+            }
+        }
+    }
+
+    if piece.len() > 1 && piece.first() == piece.last() {
+        piece.pop();
+    }
+
+    if piece.len() < 3 {
+        return None;
+    }
+
+    Some(piece)
+}
+
+/// Edges of the pieces of a band not shared by two of them
+#[derive(Default)]
+struct Boundary<T> {
+    count: HashMap<(Point<T>, Point<T>), usize>,
+
+    /// Edges in the order they were added, to make the output deterministic
+    order: Vec<(Point<T>, Point<T>)>,
+}
+
+impl<T: Scalar> Boundary<T> {
+    fn add_ring(&mut self, ring: &[Point<T>]) {
+        for (i, &a) in ring.iter().enumerate() {
+            let b = ring[(i + 1) % ring.len()];
+
+            // the edge shared with an already added piece is inside
+            if let Some(count) = self.count.get_mut(&(b, a)) {
+                *count -= 1;
+                if *count == 0 {
+                    self.count.remove(&(b, a));
+                }
+                continue;
+            }
+
+            *self.count.entry((a, b)).or_insert(0) += 1;
+            self.order.push((a, b));
+        }
+    }
+
+    fn polygons(self) -> Vec<Polygon<T>> {
+        let mut outgoing: HashMap<Point<T>, Vec<Point<T>>> = HashMap::new();
+        let mut starts = Vec::new();
+
+        for &(a, b) in &self.order {
+            if let Some(count) = self.count.get(&(a, b)) {
+                let ends = outgoing.entry(a).or_default();
+
+                if ends.iter().filter(|&&end| end == b).count() < *count {
+                    ends.push(b);
+                    starts.push((a, b));
+                }
+            }
+        }
+
+        let mut exteriors = Vec::new();
+        let mut holes = Vec::new();
+
+        for (a, b) in starts {
+            let ends = outgoing.get_mut(&a).unwrap();
+            let i = match ends.iter().position(|&end| end == b) {
+                Some(i) => i,
+                None => continue,
+            };
+            ends.swap_remove(i);
+
+            let mut ring = vec![a, b];
+            while ring[ring.len() - 1] != a {
+                let last = ring[ring.len() - 1];
+                let next = outgoing.get_mut(&last).and_then(|ends| ends.pop());
+                ring.push(next.expect("boundary edges form closed rings"));
+            }
+
+            let area = ring_area(&ring);
+            if area < T::ZERO {
+                exteriors.push((
+                    area.abs(),
+                    Polygon {
+                        exterior: ring,
+                        holes: Vec::new(),
+                    },
+                ));
+            } else if area > T::ZERO {
+                holes.push(ring);
+            }
+        }
+
+        // a hole belongs to the smallest exterior around it
+        for hole in holes {
+            let outer = exteriors
+                .iter_mut()
+                .filter(|(_, polygon)| ring_contains(&polygon.exterior, hole[0]))
+                .min_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap());
+
+            if let Some((_, polygon)) = outer {
+                polygon.holes.push(hole);
+            }
+        }
+
+        exteriors.into_iter().map(|(_, polygon)| polygon).collect()
+    }
+}
+
+/// Returns the signed area of the closed ring, negative for the order of the
+/// triangles of a triangulation
+fn ring_area<T: Scalar>(ring: &[Point<T>]) -> T {
+    let twice = ring
+        .windows(2)
+        .fold(T::ZERO, |sum, w| sum + w[0].cross(w[1]));
+    twice / T::from_f64(2.0)
+}
+
+/// Whether the point is inside of the closed ring, by the even-odd rule
+pub(crate) fn ring_contains<T: Scalar>(ring: &[Point<T>], p: Point<T>) -> bool {
+    let mut inside = false;
+
+    for w in ring.windows(2) {
+        let (a, b) = (w[0], w[1]);
+
+        if (a.y > p.y) != (b.y > p.y) && p.x < a.x + (p.y - a.y) / (b.y - a.y) * (b.x - a.x) {
+            inside = !inside;
+        }
+    }
+
+    inside
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::random_points;
+    use crate::Delaunay;
+
+    fn grid(size: usize, spacing: f32) -> Vec<Point> {
+        (0..size * size)
+            .map(|i| Point::new((i % size) as f32 * spacing, (i / size) as f32 * spacing))
+            .collect()
+    }
+
+    fn assert_clean(line: &[Point]) {
+        assert!(line.len() >= 2);
+        assert!(line.windows(2).all(|w| w[0] != w[1]), "{:?}", line);
+        assert!(line.windows(3).all(|w| w[0] != w[2]), "{:?}", line);
+    }
+
+    #[test]
+    fn closed_lines() {
+        let mut points = grid(2, 100.0);
+        points.extend(random_points(500, 25));
+
+        let center = Point::new(50.0, 50.0);
+        let values = points
+            .iter()
+            .map(|p| p.distance(center))
+            .collect::<Vec<_>>();
+
+        let t = Delaunay::new(&points).unwrap();
+        let levels = [10.0, 25.0, 40.0];
+        let lines = contours(&t, &points, &values, &levels);
+
+        for (&level, lines) in levels.iter().zip(&lines) {
+            assert_eq!(lines.len(), 1);
+
+            let line = &lines[0];
+            assert_clean(line);
+            assert_eq!(line.first(), line.last());
+
+            for &p in line {
+                let value = t.interpolate_linear(p, &values).unwrap();
+                assert!((value - level).abs() < 1e-3, "{} {}", value, level);
+            }
+
+            // the higher values outside are on the right in y-up
+            // coordinates, so the line goes counterclockwise around the center
+            let area = line.windows(2).map(|w| w[0].cross(w[1])).sum::<f32>();
+            assert!(area > 0.0);
+        }
+    }
+
+    #[test]
+    fn open_lines() {
+        let points = grid(10, 10.0);
+        let values = points.iter().map(|p| p.x + p.y).collect::<Vec<_>>();
+
+        let t = Delaunay::new(&points).unwrap();
+        let levels = [-5.0, 35.0, 100.0, 200.0];
+        let lines = contours(&t, &points, &values, &levels);
+
+        assert!(lines[0].is_empty());
+        assert!(lines[3].is_empty());
+
+        for (&level, lines) in levels.iter().zip(&lines).skip(1).take(2) {
+            assert_eq!(lines.len(), 1);
+
+            let line = &lines[0];
+            assert_clean(line);
+
+            let on_hull = |p: Point| p.x == 0.0 || p.y == 0.0 || p.x == 90.0 || p.y == 90.0;
+            assert!(on_hull(line[0]) && on_hull(line[line.len() - 1]));
+
+            for p in line {
+                assert!((p.x + p.y - level).abs() < 1e-3);
+            }
+        }
+    }
+
+    #[test]
+    fn exact_levels() {
+        let points = grid(6, 1.0);
+        let values = points.iter().map(|p| p.x).collect::<Vec<_>>();
+
+        let t = Delaunay::new(&points).unwrap();
+        let lines = contours(&t, &points, &values, &[0.0, 2.0, 5.0]);
+
+        // the level of the lowest values has nothing below it
+        assert!(lines[0].is_empty());
+
+        // lines through the points of a column, each of them once
+        for lines in &lines[1..] {
+            assert_eq!(lines.len(), 1);
+            assert_clean(&lines[0]);
+            assert_eq!(lines[0].len(), 6);
+        }
+        assert!(lines[1][0].iter().all(|p| p.x == 2.0));
+        assert!(lines[2][0].iter().all(|p| p.x == 5.0));
+
+        // a peak reaching the level at a single point
+        let mut values = vec![0.0; points.len()];
+        values[14] = 1.0;
+        let lines = contours(&t, &points, &values, &[1.0, 0.5]);
+        assert!(lines[0].is_empty());
+        assert_eq!(lines[1].len(), 1);
+        assert_clean(&lines[1][0]);
+
+        // a ridge along the level
+        let values = points
+            .iter()
+            .map(|p| -(p.y - 2.0).abs())
+            .collect::<Vec<_>>();
+        let lines = contours(&t, &points, &values, &[0.0]);
+        assert_eq!(lines[0].len(), 1);
+        assert_clean(&lines[0][0]);
+        assert_eq!(lines[0][0].len(), 6);
+        assert!(lines[0][0].iter().all(|p| p.y == 2.0));
+    }
+
+    fn band_areas(bands: &[Vec<Polygon>]) -> Vec<f32> {
+        bands
+            .iter()
+            .map(|band| band.iter().map(|p| p.area()).sum())
+            .collect()
+    }
+
+    fn assert_closed(polygon: &Polygon) {
+        for ring in std::iter::once(&polygon.exterior).chain(&polygon.holes) {
+            assert!(ring.len() >= 4);
+            assert_eq!(ring.first(), ring.last());
+        }
+    }
+
+    #[test]
+    fn isobands_planar() {
+        let points = grid(10, 10.0);
+        let values = points.iter().map(|p| p.x).collect::<Vec<_>>();
+
+        let t = Delaunay::new(&points).unwrap();
+        let bands = isobands(&t, &points, &values, &[25.0, 50.0]);
+
+        assert_eq!(bands.len(), 3);
+        for band in &bands {
+            assert_eq!(band.len(), 1);
+            assert!(band[0].holes.is_empty());
+            assert_closed(&band[0]);
+        }
+
+        let areas = band_areas(&bands);
+        for (area, expected) in areas.iter().zip(&[25.0 * 90.0, 25.0 * 90.0, 40.0 * 90.0]) {
+            assert!((area - expected).abs() < 1e-2, "{:?}", areas);
+        }
+    }
+
+    #[test]
+    fn isobands_holes() {
+        let mut points = grid(2, 100.0);
+        points.extend(random_points(500, 26));
+
+        let center = Point::new(50.0, 50.0);
+        let values = points
+            .iter()
+            .map(|p| p.distance(center))
+            .collect::<Vec<_>>();
+
+        let t = Delaunay::new(&points).unwrap();
+        let bands = isobands(&t, &points, &values, &[10.0, 30.0]);
+
+        // a disk, a ring around it and the rest of the square
+        assert_eq!(
+            bands.iter().map(|band| band.len()).collect::<Vec<_>>(),
+            vec![1, 1, 1]
+        );
+        assert_eq!(bands[0][0].holes.len(), 0);
+        assert_eq!(bands[1][0].holes.len(), 1);
+        assert_eq!(bands[2][0].holes.len(), 1);
+        bands.iter().flatten().for_each(assert_closed);
+
+        // the bands cover the triangulation exactly
+        let areas = band_areas(&bands);
+        let total = areas.iter().sum::<f32>();
+        assert!((total - 10000.0).abs() < 1e-1, "{}", total);
+
+        // the inner hole is the disk
+        assert_eq!(
+            ring_area(&bands[1][0].holes[0]).abs(),
+            ring_area(&bands[0][0].exterior).abs()
+        );
+    }
+
+    #[test]
+    fn isobands_exact_levels() {
+        let points = grid(6, 1.0);
+        let values = points.iter().map(|p| p.x).collect::<Vec<_>>();
+
+        let t = Delaunay::new(&points).unwrap();
+        let bands = isobands(&t, &points, &values, &[2.0, 3.0]);
+        assert_eq!(band_areas(&bands), vec![10.0, 5.0, 10.0]);
+        bands.iter().flatten().for_each(assert_closed);
+
+        // a level below everything
+        let bands = isobands(&t, &points, &values, &[-1.0]);
+        assert!(bands[0].is_empty());
+        assert_eq!(band_areas(&bands), vec![0.0, 25.0]);
+
+        // flat at the level, which belongs to the band above
+        let values = vec![1.0; points.len()];
+        let bands = isobands(&t, &points, &values, &[1.0]);
+        assert!(bands[0].is_empty());
+        assert_eq!(band_areas(&bands), vec![0.0, 25.0]);
+    }
+}
@@ -1,6 +1,9 @@
 use core::ops::{Add, Index, IndexMut, Sub};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
 
-use crate::{OptionIndex, Point, Triangle};
+use crate::geom::Circumcircle;
+use crate::{OptionIndex, Point, Scalar, Triangle};
 
 /// Doubly connected edge list (a.k.a. half-edge data structure) of triangles
 #[derive(Debug, Clone)]
@@ -33,15 +36,43 @@ impl TrianglesDCEL {
     }
 
     /// Returns the iterator over all triangles in the triangulation
-    pub fn triangles<'a, 'b: 'a>(
+    pub fn triangles<'a, 'b: 'a, T: Scalar>(
         &'a self,
-        points: &'b [Point],
-    ) -> impl Iterator<Item = Triangle> + 'a {
+        points: &'b [Point<T>],
+    ) -> impl Iterator<Item = Triangle<T>> + 'a {
         (0..self.vertices.len())
             .step_by(3)
             .map(move |t| self.triangle(t.into(), points))
     }
 
+    /// Returns the iterator over the circumcircles of all triangles
+    pub fn circumcircles<'a, T: Scalar>(
+        &'a self,
+        points: &'a [Point<T>],
+    ) -> impl Iterator<Item = Circumcircle<T>> + 'a {
+        self.triangles(points).map(Triangle::circumcircle)
+    }
+
+    /// Returns the sum of the areas of all triangles, which for a Delaunay
+    /// triangulation is the area of the convex hull.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Delaunay, Point};
+    /// let points = &[
+    ///     Point::new(10.0, 10.0),
+    ///     Point::new(10.0, 110.0),
+    ///     Point::new(110.0, 10.0),
+    /// ];
+    /// let t = Delaunay::new(points).unwrap();
+    /// assert_eq!(t.dcel.total_area(points), 5000.0);
+    /// ```
+    pub fn total_area<T: Scalar>(&self, points: &[Point<T>]) -> T {
+        let total: f64 = self.triangles(points).map(|t| t.area().to_f64()).sum();
+
+        T::from_f64(total)
+    }
+
     /// Adds a new triangle from given point ids to the DCEL and returns its `id`.
     /// Triangles `id + 1` and `id + 2` will reference to the same triangle
     /// viewed from different points.
@@ -51,6 +82,12 @@ impl TrianglesDCEL {
     pub fn add_triangle(&mut self, points: [PointIndex; 3]) -> EdgeIndex {
         let t = self.vertices.len();
         self.vertices.extend_from_slice(&points);
+
+        if self.halfedges.len() < self.vertices.len() {
+            self.halfedges
+                .resize(self.vertices.len(), OptionIndex::none());
+        }
+
         t.into()
     }
 
@@ -92,7 +129,7 @@ impl TrianglesDCEL {
     /// assert_eq!(dcel.triangle(t, points), Triangle(points[0], points[1], points[2]));
     /// ```
     #[inline]
-    pub fn triangle(&self, t: EdgeIndex, points: &[Point]) -> Triangle {
+    pub fn triangle<T: Scalar>(&self, t: EdgeIndex, points: &[Point<T>]) -> Triangle<T> {
         let [a, b, c] = self.triangle_points(t);
         Triangle(points[a], points[b], points[c])
     }
@@ -114,6 +151,58 @@ impl TrianglesDCEL {
         (t.0 - t.0 % 3).into()
     }
 
+    /// Returns the first edges of the triangles sharing the edges of the
+    /// given triangle, in the order of
+    /// [`triangle_edges`](TrianglesDCEL::triangle_edges), with `None` for the
+    /// edges on the boundary.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::dcel::TrianglesDCEL;
+    /// let mut dcel = TrianglesDCEL::with_capacity(2);
+    /// let a = dcel.add_triangle([0.into(), 1.into(), 2.into()]);
+    /// let b = dcel.add_triangle([2.into(), 1.into(), 3.into()]);
+    /// dcel.link(a + 1, b);
+    ///
+    /// assert_eq!(dcel.triangle_neighbours(a), [None, Some(b), None]);
+    /// assert_eq!(dcel.triangle_neighbours(a + 1), [Some(b), None, None]);
+    /// assert_eq!(dcel.triangle_neighbours(b + 1), [None, None, Some(a)]);
+    /// ```
+    #[inline]
+    pub fn triangle_neighbours(&self, t: EdgeIndex) -> [Option<EdgeIndex>; 3] {
+        let [a, b, c] = self.triangle_edges(t);
+        let neighbour = |e| self.twin(e).map(|twin| self.triangle_first_edge(twin));
+
+        [neighbour(a), neighbour(b), neighbour(c)]
+    }
+
+    /// Returns the first edges of the triangles across from the points of
+    /// the given triangle, in the order of
+    /// [`triangle_points`](TrianglesDCEL::triangle_points), with `None` for
+    /// the edges on the boundary.
+    ///
+    /// This is [`triangle_neighbours`](TrianglesDCEL::triangle_neighbours)
+    /// ordered by the opposite point instead of the shared edge, which suits
+    /// walking towards a point using barycentric coordinates.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::dcel::TrianglesDCEL;
+    /// let mut dcel = TrianglesDCEL::with_capacity(2);
+    /// let a = dcel.add_triangle([0.into(), 1.into(), 2.into()]);
+    /// let b = dcel.add_triangle([2.into(), 1.into(), 3.into()]);
+    /// dcel.link(a + 1, b);
+    ///
+    /// // the triangle `b` is across from the point 0
+    /// assert_eq!(dcel.triangle_neighbours_by_opposite_point(a), [Some(b), None, None]);
+    /// assert_eq!(dcel.triangle_neighbours_by_opposite_point(b), [None, None, Some(a)]);
+    /// ```
+    #[inline]
+    pub fn triangle_neighbours_by_opposite_point(&self, t: EdgeIndex) -> [Option<EdgeIndex>; 3] {
+        let [a, b, c] = self.triangle_neighbours(t);
+        [b, c, a]
+    }
+
     /// Returns the edge next to the specified one (counter-clockwise order).
     ///
     /// # Examples
@@ -163,6 +252,46 @@ impl TrianglesDCEL {
         self.vertices[self.next_edge(edge)]
     }
 
+    /// Returns the length of the edge.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{dcel::TrianglesDCEL, Point};
+    /// let points = &[Point::new(0.0, 0.0), Point::new(4.0, 0.0), Point::new(0.0, 3.0)];
+    ///
+    /// let mut dcel = TrianglesDCEL::with_capacity(1);
+    /// let t = dcel.add_triangle([0.into(), 1.into(), 2.into()]);
+    ///
+    /// assert_eq!(dcel.edge_length(t, points), 4.0);
+    /// assert_eq!(dcel.edge_length_sq(t + 1, points), 25.0);
+    /// assert_eq!(dcel.longest_edge(t + 2, points), t + 1);
+    /// ```
+    #[inline]
+    pub fn edge_length<T: Scalar>(&self, edge: EdgeIndex, points: &[Point<T>]) -> T {
+        self.edge_length_sq(edge, points).sqrt()
+    }
+
+    /// Returns the squared length of the edge, cheaper for comparisons
+    #[inline]
+    pub fn edge_length_sq<T: Scalar>(&self, edge: EdgeIndex, points: &[Point<T>]) -> T {
+        points[self.vertices[edge]].distance_sq(points[self.edge_endpoint(edge)])
+    }
+
+    /// Returns the longest edge of the triangle, the one to split in
+    /// longest-edge bisection. Of edges with the same length the first one
+    /// starting from `t` is picked.
+    pub fn longest_edge<T: Scalar>(&self, t: EdgeIndex, points: &[Point<T>]) -> EdgeIndex {
+        let [a, b, c] = self.triangle_edges(t);
+
+        [b, c].iter().fold(a, |longest, &e| {
+            if self.edge_length_sq(e, points) > self.edge_length_sq(longest, points) {
+                e
+            } else {
+                longest
+            }
+        })
+    }
+
     /// Mark two given edges as twins.
     ///
     /// # Examples
@@ -198,31 +327,681 @@ impl TrianglesDCEL {
         }
     }
 
-    /// Returns an iterator of outgoing edges from the given point.
+    /// Flips the edge shared by two triangles to the other diagonal of
+    /// their quadrilateral.
+    ///
+    /// For the edge `pr→pl` in triangle `[p0, pr, pl]` and its twin in
+    /// `[p1, pl, pr]` the triangles become `[p0, p1, pl]` and `[p1, p0, pr]`.
+    /// The two triangles keep their places, but their edges are reassigned, so
+    /// the new diagonal `p0→p1` ends up as the previous edge of `edge`, and it
+    /// is returned. Flipping it again restores the original two triangles.
+    /// Returns `None` without changing anything if the edge is on the
+    /// boundary.
+    ///
+    /// The caller is responsible for the quadrilateral being convex and for
+    /// the Delaunay condition, if it is needed. The point-to-triangle map is
+    /// invalidated.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::dcel::TrianglesDCEL;
+    /// let mut dcel = TrianglesDCEL::with_capacity(2);
+    /// let a = dcel.add_triangle([0.into(), 1.into(), 2.into()]);
+    /// let b = dcel.add_triangle([2.into(), 1.into(), 3.into()]);
+    /// dcel.link(a + 1, b);
+    ///
+    /// let diagonal = dcel.flip(a + 1).unwrap();
+    /// assert_eq!(dcel.triangle_points(a), [0.into(), 3.into(), 2.into()]);
+    /// assert_eq!(dcel.triangle_points(b), [0.into(), 1.into(), 3.into()]);
+    /// assert_eq!((dcel.vertices[diagonal], dcel.edge_endpoint(diagonal)), (0.into(), 3.into()));
+    /// assert_eq!(dcel.flip(a + 2), None);
+    /// ```
+    pub fn flip(&mut self, edge: EdgeIndex) -> Option<EdgeIndex> {
+        let a = edge;
+        let b = self.twin(a)?;
+
+        let ar = self.prev_edge(a);
+        let bl = self.prev_edge(b);
+
+        let p0 = self.vertices[ar];
+        let p1 = self.vertices[bl];
+
+        self.vertices[a] = p1;
+        self.vertices[b] = p0;
+
+        let outer_a = self.twin(bl);
+        let outer_b = self.twin(ar);
+
+        self.link_option(a, outer_a);
+        self.link_option(b, outer_b);
+        self.link(ar, bl);
+
+        self.points_to_triangles = None;
+
+        Some(ar)
+    }
+
+    /// Splits the edge at a new point, splitting each of the one or two
+    /// triangles sharing the edge in two.
+    ///
+    /// For the edge `a→b` in triangle `[a, b, c]` the triangle becomes
+    /// `[a, v, c]` in place, and `[v, b, c]` is added. The triangle on the
+    /// other side, if any, is split the same way. Returns the edges `a→v`,
+    /// `v→b`, `v→c` and `c→v`, the twins of the first two lead to the other
+    /// side.
+    ///
+    /// The point-to-triangle map is invalidated.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::dcel::TrianglesDCEL;
+    /// let mut dcel = TrianglesDCEL::with_capacity(1);
+    /// let t = dcel.add_triangle([0.into(), 1.into(), 2.into()]);
+    ///
+    /// let [av, vb, vc, cv] = dcel.split_edge(t, 3.into());
+    /// assert_eq!(dcel.num_triangles(), 2);
+    /// assert_eq!(dcel.triangle_points(av), [0.into(), 3.into(), 2.into()]);
+    /// assert_eq!(dcel.triangle_points(vb), [3.into(), 1.into(), 2.into()]);
+    /// assert_eq!(dcel.twin(vc), Some(cv));
+    /// ```
+    pub fn split_edge(&mut self, e: EdgeIndex, new_vertex: PointIndex) -> [EdgeIndex; 4] {
+        let v = new_vertex;
+        let twin = self.twin(e);
+
+        let (vb, vc, cv) = self.split_half(e, v);
+
+        if let Some(f) = twin {
+            let (va, _, _) = self.split_half(f, v);
+            self.link(e, va);
+            self.link(f, vb);
+        }
+
+        self.points_to_triangles = None;
+
+        [e, vb, vc, cv]
+    }
+
+    /// Splits the triangle of `e` at the new point on `e`, keeping the first
+    /// half of the edge in place. Returns the edges `v→b`, `v→c` and `c→v`.
+    fn split_half(&mut self, e: EdgeIndex, v: PointIndex) -> (EdgeIndex, EdgeIndex, EdgeIndex) {
+        let n = self.next_edge(e);
+        let b = self.vertices[n];
+        let c = self.vertices[self.prev_edge(e)];
+        let outer = self.twin(n);
+
+        self.unlink(e);
+        self.vertices[n] = v;
+
+        let t = self.add_triangle([v, b, c]);
+        self.link_option(t + 1, outer);
+        self.link(n, t + 2);
+
+        (t, n, t + 2)
+    }
+
+    /// Splits the triangle into three at a new point inside of it.
+    ///
+    /// For the triangle `[a, b, c]` the triangle becomes `[a, b, v]` in place,
+    /// and `[b, c, v]` and `[c, a, v]` are added. The outer edges stay the
+    /// first edges of the triangles and keep their twins. Returns the first
+    /// edges of the three triangles.
+    ///
+    /// The point-to-triangle map is invalidated.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::dcel::TrianglesDCEL;
+    /// let mut dcel = TrianglesDCEL::with_capacity(1);
+    /// let t = dcel.add_triangle([0.into(), 1.into(), 2.into()]);
+    ///
+    /// let [t0, t1, t2] = dcel.split_triangle(t, 3.into());
+    /// assert_eq!(dcel.num_triangles(), 3);
+    /// assert_eq!(dcel.triangle_points(t0), [0.into(), 1.into(), 3.into()]);
+    /// assert_eq!(dcel.triangle_points(t1), [1.into(), 2.into(), 3.into()]);
+    /// assert_eq!(dcel.triangle_points(t2), [2.into(), 0.into(), 3.into()]);
+    /// ```
+    pub fn split_triangle(&mut self, t: EdgeIndex, new_vertex: PointIndex) -> [EdgeIndex; 3] {
+        let t = self.triangle_first_edge(t);
+        let [a, b, c] = self.triangle_points(t);
+        let (outer_bc, outer_ca) = (self.twin(t + 1), self.twin(t + 2));
+
+        self.vertices[t + 2] = new_vertex;
+
+        let t1 = self.add_triangle([b, c, new_vertex]);
+        let t2 = self.add_triangle([c, a, new_vertex]);
+
+        self.link_option(t1, outer_bc);
+        self.link_option(t2, outer_ca);
+
+        self.link(t + 1, t1 + 2);
+        self.link(t1 + 1, t2 + 2);
+        self.link(t2 + 1, t + 2);
+
+        self.points_to_triangles = None;
+
+        [t, t1, t2]
+    }
+
+    /// Removes the triangle, moving the last triangle into its place.
+    ///
+    /// Edges which were shared with the removed triangle become boundary
+    /// edges. Any `EdgeIndex` of the last triangle is invalidated, as well as
+    /// the point-to-triangle map.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::dcel::TrianglesDCEL;
+    /// let mut dcel = TrianglesDCEL::with_capacity(2);
+    /// let a = dcel.add_triangle([0.into(), 1.into(), 2.into()]);
+    /// let b = dcel.add_triangle([2.into(), 1.into(), 3.into()]);
+    /// dcel.link(a + 1, b);
+    ///
+    /// dcel.swap_remove_triangle(a);
+    /// assert_eq!(dcel.num_triangles(), 1);
+    /// assert_eq!(dcel.triangle_points(a), [2.into(), 1.into(), 3.into()]);
+    /// assert_eq!(dcel.twin(a), None);
+    /// ```
+    pub fn swap_remove_triangle(&mut self, t: EdgeIndex) {
+        let t = self.triangle_first_edge(t);
+        let last = EdgeIndex::from(self.vertices.len() - 3);
+
+        for &e in &self.triangle_edges(t) {
+            if let Some(twin) = self.twin(e) {
+                self.unlink(twin);
+            }
+
+            self.unlink(e);
+        }
+
+        if t != last {
+            for i in 0..3 {
+                let (from, to) = (last + i, t + i);
+                self.vertices[to] = self.vertices[from];
+                self.link_option(to, self.twin(from));
+                self.unlink(from);
+            }
+        }
+
+        self.vertices.truncate(last.as_usize());
+        self.points_to_triangles = None;
+    }
+
+    /// Collapses the edge, merging its two points into one.
+    ///
+    /// The one or two triangles sharing the edge are removed with
+    /// [`swap_remove_triangle`](TrianglesDCEL::swap_remove_triangle), in the
+    /// order listed in the returned [`CollapseInfo`], and the remaining edges
+    /// of both points are attached to the surviving point chosen by `keep`.
+    ///
+    /// The collapse is rejected without changing anything if it would make
+    /// the mesh non-manifold (the link condition), or if any of the remaining
+    /// triangles would not be right-handed at the position of the surviving
+    /// point in `points`.
+    ///
+    /// The point-to-triangle map is invalidated.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{dcel::{CollapseTarget, TrianglesDCEL}, Point};
+    /// let points = &[
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(1.0, 2.0),
+    ///     Point::new(2.0, 0.0),
+    ///     Point::new(1.0, 0.5),
+    /// ];
+    ///
+    /// let mut dcel = TrianglesDCEL::with_capacity(3);
+    /// let a = dcel.add_triangle([0.into(), 1.into(), 3.into()]);
+    /// let b = dcel.add_triangle([1.into(), 2.into(), 3.into()]);
+    /// let c = dcel.add_triangle([2.into(), 0.into(), 3.into()]);
+    /// dcel.link(a + 1, b + 2);
+    /// dcel.link(b + 1, c + 2);
+    /// dcel.link(c + 1, a + 2);
+    ///
+    /// let info = dcel.collapse_edge(a + 2, CollapseTarget::Endpoint, points).unwrap();
+    /// assert_eq!(info.vertex, 0.into());
+    /// assert_eq!(info.removed_triangles, vec![c, a]);
+    /// assert_eq!(dcel.triangle_points(0.into()), [1.into(), 2.into(), 0.into()]);
+    /// ```
+    pub fn collapse_edge<T: Scalar>(
+        &mut self,
+        e: EdgeIndex,
+        keep: CollapseTarget,
+        points: &[Point<T>],
+    ) -> Result<CollapseInfo, CollapseError> {
+        let a = self.vertices[e];
+        let b = self.edge_endpoint(e);
+        let twin = self.twin(e);
+
+        let vertex = match keep {
+            CollapseTarget::Origin => a,
+            CollapseTarget::Endpoint => b,
+            CollapseTarget::Point(p) => p,
+        };
+
+        let mut dead = vec![self.triangle_first_edge(e)];
+        dead.extend(twin.map(|t| self.triangle_first_edge(t)));
+
+        let fan_a = TrianglesAroundPoint::new(self, e).collect::<Vec<_>>();
+        let fan_b = TrianglesAroundPoint::new(self, self.next_edge(e)).collect::<Vec<_>>();
+
+        // link condition, the only common neighbors are the opposite points
+        let neighbors = |fan: &[EdgeIndex]| {
+            fan.iter()
+                .flat_map(|&x| vec![self.edge_endpoint(x), self.vertices[self.prev_edge(x)]])
+                .collect::<HashSet<_>>()
+        };
+
+        let opposite = [Some(e), twin]
+            .iter()
+            .flatten()
+            .map(|&x| self.vertices[self.prev_edge(x)])
+            .collect::<HashSet<_>>();
+
+        if neighbors(&fan_a)
+            .intersection(&neighbors(&fan_b))
+            .cloned()
+            .collect::<HashSet<_>>()
+            != opposite
+        {
+            return Err(CollapseError::NonManifold);
+        }
+
+        let on_boundary = |fan: &[EdgeIndex]| self.twin(fan[0]).is_none();
+
+        match twin {
+            // an interior edge between two boundary points pinches the mesh
+            Some(_) if on_boundary(&fan_a) && on_boundary(&fan_b) => {
+                return Err(CollapseError::NonManifold);
+            }
+            // the triangle is an ear, the opposite point would be left alone
+            None if self.twin(self.next_edge(e)).is_none()
+                && self.twin(self.prev_edge(e)).is_none() =>
+            {
+                return Err(CollapseError::NonManifold);
+            }
+            _ => {}
+        }
+
+        let survivors = fan_a
+            .iter()
+            .chain(&fan_b)
+            .cloned()
+            .filter(|&x| !dead.contains(&self.triangle_first_edge(x)))
+            .collect::<Vec<_>>();
+
+        for &x in &survivors {
+            let [p0, p1, p2] =
+                self.triangle_points(x)
+                    .map(|p| if p == a || p == b { vertex } else { p });
+            let triangle = Triangle(
+                points[p0.as_usize()],
+                points[p1.as_usize()],
+                points[p2.as_usize()],
+            );
+
+            if triangle.robust_orientation() <= 0.0 {
+                return Err(CollapseError::FlippedTriangle);
+            }
+        }
+
+        for &x in &survivors {
+            self.vertices[x] = vertex;
+        }
+
+        // the outer edges of the removed triangles become twins
+        for &x in [Some(e), twin].iter().flatten() {
+            let outer_next = self.twin(self.next_edge(x));
+            let outer_prev = self.twin(self.prev_edge(x));
+
+            for &y in &self.triangle_edges(x) {
+                self.unlink(y);
+            }
+
+            match (outer_next, outer_prev) {
+                (Some(n), p) => self.link_option(n, p),
+                (None, Some(p)) => self.unlink(p),
+                (None, None) => {}
+            }
+        }
+
+        // removing the later triangle first keeps the other one in place
+        dead.sort_by_key(|t| std::cmp::Reverse(t.as_usize()));
+        for &t in &dead {
+            self.swap_remove_triangle(t);
+        }
+
+        Ok(CollapseInfo {
+            vertex,
+            removed_triangles: dead,
+        })
+    }
+
+    /// Checks that the triangles and their twin links are consistent,
+    /// returning the first problem found.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::dcel::{DcelError, TrianglesDCEL};
+    /// let mut dcel = TrianglesDCEL::with_capacity(2);
+    /// let a = dcel.add_triangle([0.into(), 1.into(), 2.into()]);
+    /// let b = dcel.add_triangle([2.into(), 1.into(), 3.into()]);
+    /// dcel.link(a + 1, b);
+    /// assert_eq!(dcel.validate(), Ok(()));
+    ///
+    /// dcel.unlink(b);
+    /// assert_eq!(dcel.validate(), Err(DcelError::AsymmetricTwin(a + 1)));
+    /// ```
+    pub fn validate(&self) -> Result<(), DcelError> {
+        let len = self.vertices.len();
+
+        if len % 3 != 0 {
+            return Err(DcelError::VertexCount(len));
+        }
+
+        if self.halfedges.len() < len {
+            return Err(DcelError::HalfedgeCount(self.halfedges.len()));
+        }
+
+        for e in (0..len).map(EdgeIndex) {
+            let twin = match self.twin(e) {
+                Some(twin) => twin,
+                None => continue,
+            };
+
+            if twin.0 >= len {
+                return Err(DcelError::TwinOutOfRange(e));
+            }
+
+            if twin == e {
+                return Err(DcelError::SelfTwin(e));
+            }
+
+            if self.twin(twin) != Some(e) {
+                return Err(DcelError::AsymmetricTwin(e));
+            }
+
+            if self.vertices[twin] != self.edge_endpoint(e)
+                || self.edge_endpoint(twin) != self.vertices[e]
+            {
+                return Err(DcelError::MismatchedTwin(e));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns true if every triangle can be reached from the first one by
+    /// crossing shared edges.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::dcel::TrianglesDCEL;
+    /// let mut dcel = TrianglesDCEL::with_capacity(6);
+    /// let a = dcel.add_triangle([0.into(), 1.into(), 2.into()]);
+    /// let b = dcel.add_triangle([2.into(), 1.into(), 3.into()]);
+    /// assert!(!dcel.is_connected());
+    ///
+    /// dcel.link(a + 1, b);
+    /// assert!(dcel.is_connected());
+    /// ```
+    pub fn is_connected(&self) -> bool {
+        let num_triangles = self.num_triangles();
+
+        if num_triangles == 0 {
+            return true;
+        }
+
+        let mut visited = vec![false; num_triangles];
+        let mut stack = vec![EdgeIndex(0)];
+        let mut reached = 1;
+        visited[0] = true;
+
+        while let Some(t) = stack.pop() {
+            for &neighbor in self.triangle_neighbours(t).iter().flatten() {
+                if !visited[neighbor.0 / 3] {
+                    visited[neighbor.0 / 3] = true;
+                    reached += 1;
+                    stack.push(neighbor);
+                }
+            }
+        }
+
+        reached == num_triangles
+    }
+
+    /// Returns the iterator over all unique undirected edges.
+    ///
+    /// Each edge is yielded once as `(start, end, is_boundary)`, which is
+    /// handy for wireframe rendering.
+    pub fn wireframe_edges<'a>(
+        &'a self,
+    ) -> impl Iterator<Item = (PointIndex, PointIndex, bool)> + 'a {
+        (0..self.vertices.len())
+            .map(EdgeIndex)
+            .filter_map(move |e| match self.twin(e) {
+                Some(twin) if twin < e => None,
+                twin => Some((self.vertices[e], self.edge_endpoint(e), twin.is_none())),
+            })
+    }
+
+    /// Returns an iterator of the interior edges, the ones shared by two
+    /// triangles, as pairs of twin half-edges.
+    ///
+    /// Every edge is yielded once, with the lower half-edge index first.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::dcel::TrianglesDCEL;
+    /// let mut dcel = TrianglesDCEL::with_capacity(2);
+    /// let a = dcel.add_triangle([0.into(), 1.into(), 2.into()]);
+    /// let b = dcel.add_triangle([2.into(), 1.into(), 3.into()]);
+    /// dcel.link(a + 1, b);
+    ///
+    /// assert_eq!(dcel.interior_edges().collect::<Vec<_>>(), vec![(a + 1, b)]);
+    /// assert_eq!(dcel.num_interior_edges(), 1);
+    /// assert_eq!(dcel.num_boundary_edges(), 4);
+    /// ```
+    pub fn interior_edges<'a>(&'a self) -> impl Iterator<Item = (EdgeIndex, EdgeIndex)> + 'a {
+        (0..self.vertices.len())
+            .map(EdgeIndex)
+            .filter_map(move |e| match self.twin(e) {
+                Some(twin) if e < twin => Some((e, twin)),
+                _ => None,
+            })
+    }
+
+    /// Returns the number of edges shared by two triangles
+    pub fn num_interior_edges(&self) -> usize {
+        self.interior_edges().count()
+    }
+
+    /// Returns the number of edges belonging to one triangle only
+    pub fn num_boundary_edges(&self) -> usize {
+        (0..self.vertices.len())
+            .filter(|&e| self.twin(EdgeIndex(e)).is_none())
+            .count()
+    }
+
+    /// Returns an iterator of the boundary edges, the ones without twins, in
+    /// order along the boundary.
+    ///
+    /// The loops are yielded one after another, each starting at its first
+    /// edge in the DCEL. The outer boundary goes counter-clockwise like the
+    /// hull, while the boundaries of holes go the other way.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::dcel::TrianglesDCEL;
+    /// let mut dcel = TrianglesDCEL::with_capacity(2);
+    /// let a = dcel.add_triangle([0.into(), 1.into(), 2.into()]);
+    /// let b = dcel.add_triangle([2.into(), 1.into(), 3.into()]);
+    /// dcel.link(a + 1, b);
+    ///
+    /// let edges = dcel.hull_edges().collect::<Vec<_>>();
+    /// assert_eq!(edges, vec![a, b + 1, b + 2, a + 2]);
+    /// ```
+    pub fn hull_edges<'a>(&'a self) -> impl Iterator<Item = EdgeIndex> + 'a {
+        let mut visited = vec![false; self.vertices.len()];
+        let mut scanned = 0;
+        let mut next: Option<EdgeIndex> = None;
+
+        std::iter::from_fn(move || {
+            let edge = match next {
+                Some(e) if !visited[e.0] => e,
+                // the loop is closed, continue with the next one
+                _ => {
+                    let start = (scanned..self.vertices.len())
+                        .map(EdgeIndex)
+                        .find(|&e| self.twin(e).is_none() && !visited[e.0])?;
+                    scanned = start.0 + 1;
+                    start
+                }
+            };
+
+            visited[edge.0] = true;
+            next = Some(self.next_boundary_edge(edge));
+            Some(edge)
+        })
+    }
+
+    /// Returns the boundary edge following the given one, found by rotating
+    /// around its endpoint
+    pub(crate) fn next_boundary_edge(&self, edge: EdgeIndex) -> EdgeIndex {
+        let mut edge = self.next_edge(edge);
+
+        while let Some(twin) = self.twin(edge) {
+            edge = self.next_edge(twin);
+        }
+
+        edge
+    }
+
+    /// Returns an iterator of outgoing edges from the given point.
+    ///
+    /// [`init_revmap`](TrianglesDCEL::init_revmap) must be called beforehand
+    /// to initialize the point-to-triangle map.
+    pub fn outgoing_edges<'a>(&'a self, p: PointIndex) -> EdgesAroundPoint<'a> {
+        let start = self
+            .points_to_triangles
+            .as_ref()
+            .expect("initialize point-to-triangle map calling init_revmap")[p.0];
+
+        EdgesAroundPoint {
+            dcel: self,
+            start,
+            current: Some(start),
+            backward: false,
+        }
+    }
+
+    /// Returns the edge going from `a` to `b`, if it exists.
+    ///
+    /// Hull edges exist only in one direction, so `edge_between(b, a)` may be
+    /// `None` even if `edge_between(a, b)` is not. Takes O(degree) time.
+    ///
+    /// [`init_revmap`](TrianglesDCEL::init_revmap) must be called beforehand.
+    pub fn edge_between(&self, a: PointIndex, b: PointIndex) -> Option<EdgeIndex> {
+        self.outgoing_edges(a).find(|&e| self.edge_endpoint(e) == b)
+    }
+
+    /// Returns an iterator of the triangles around the given point in
+    /// counter-clockwise order.
+    ///
+    /// Every triangle is represented by its edge starting at `p`. For points
+    /// on the hull the iteration starts at the boundary.
+    ///
+    /// [`init_revmap`](TrianglesDCEL::init_revmap) must be called beforehand
+    /// to initialize the point-to-triangle map, which is used to find an
+    /// edge going out of the point for
+    /// [`triangles_around_edge`](TrianglesDCEL::triangles_around_edge).
+    pub fn triangles_around_point<'a>(&'a self, p: PointIndex) -> TrianglesAroundPoint<'a> {
+        let start = self
+            .points_to_triangles
+            .as_ref()
+            .expect("initialize point-to-triangle map calling init_revmap")[p.0];
+
+        self.triangles_around_edge(start)
+    }
+
+    /// Returns an iterator of the triangles around the start point of the
+    /// given edge in counter-clockwise order, without needing the
+    /// point-to-triangle map.
+    ///
+    /// Every triangle is represented by its edge starting at the point. For
+    /// points on the hull the iteration starts at the boundary, otherwise at
+    /// the given edge.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::dcel::TrianglesDCEL;
+    /// let mut dcel = TrianglesDCEL::with_capacity(2);
+    /// let a = dcel.add_triangle([0.into(), 1.into(), 2.into()]);
+    /// let b = dcel.add_triangle([2.into(), 1.into(), 3.into()]);
+    /// dcel.link(a + 1, b);
+    ///
+    /// let around = dcel.triangles_around_edge(b + 1).collect::<Vec<_>>();
+    /// assert_eq!(around, vec![b + 1, a + 1]);
+    /// ```
+    pub fn triangles_around_edge<'a>(&'a self, start: EdgeIndex) -> TrianglesAroundPoint<'a> {
+        TrianglesAroundPoint::new(self, start)
+    }
+
+    /// Returns the number of triangles having the given point as a vertex.
+    ///
+    /// Scans all the vertices in O(n) time without needing the
+    /// point-to-triangle map, which is fine for occasional queries. Use
+    /// [`vertex_valence_fast`](TrianglesDCEL::vertex_valence_fast) when
+    /// querying many points.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::dcel::TrianglesDCEL;
+    /// let mut dcel = TrianglesDCEL::with_capacity(2);
+    /// let a = dcel.add_triangle([0.into(), 1.into(), 2.into()]);
+    /// let b = dcel.add_triangle([2.into(), 1.into(), 3.into()]);
+    /// dcel.link(a + 1, b);
+    ///
+    /// assert_eq!(dcel.vertex_valence(1.into()), 2);
+    /// assert_eq!(dcel.vertex_valence(3.into()), 1);
+    /// assert_eq!(dcel.vertex_valence(4.into()), 0);
+    /// ```
+    pub fn vertex_valence(&self, p: PointIndex) -> usize {
+        self.vertices.iter().filter(|&&v| v == p).count()
+    }
+
+    /// Returns the number of triangles having the given point as a vertex in
+    /// O(valence) time, by walking around the point.
     ///
     /// [`init_revmap`](TrianglesDCEL::init_revmap) must be called beforehand
-    /// to initialize the point-to-triangle map.
-    pub fn outgoing_edges<'a>(&'a self, p: PointIndex) -> EdgesAroundPoint<'a> {
+    /// to initialize the point-to-triangle map, which takes O(n) time and
+    /// memory once but pays off when querying many points.
+    pub fn vertex_valence_fast(&self, p: PointIndex) -> usize {
         let start = self
             .points_to_triangles
             .as_ref()
-            .expect("initialize point-to-triangle map calling init_revmap")[p.0];
+            .expect("initialize point-to-triangle map calling init_revmap")
+            .get(p.0);
 
-        EdgesAroundPoint {
-            dcel: self,
-            start,
-            current: Some(start),
-            backward: false,
+        match start {
+            Some(&start) if self.vertices[start] == p => self.triangles_around_edge(start).count(),
+            _ => 0,
         }
     }
 
+    /// Drops the point-to-triangle map after the triangles were changed
+    pub(crate) fn invalidate_revmap(&mut self) {
+        self.points_to_triangles = None;
+    }
+
     /// Initializes the point-to-triangle map.
     pub fn init_revmap(&mut self) {
         if self.points_to_triangles.is_some() {
             return;
         }
 
-        let mut map = vec![0.into(); self.vertices.len()];
+        let len = self.vertices.iter().map(|p| p.0 + 1).max().unwrap_or(0);
+        let mut map = vec![0.into(); len];
 
         for (t, &p) in self.vertices.iter().enumerate() {
             map[p.0] = t.into();
@@ -232,6 +1011,112 @@ impl TrianglesDCEL {
     }
 }
 
+/// Point surviving an edge collapse
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CollapseTarget {
+    /// Keep the start point of the edge
+    Origin,
+    /// Keep the end point of the edge
+    Endpoint,
+    /// Replace both points with another one, e.g. one added at the middle of
+    /// the edge
+    Point(PointIndex),
+}
+
+/// Outcome of a successful edge collapse, for updating side tables
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CollapseInfo {
+    /// Point which replaced both ends of the edge
+    pub vertex: PointIndex,
+
+    /// Removed triangles in the order of removal. Every removal moves the
+    /// last triangle into the slot of the removed one.
+    pub removed_triangles: Vec<EdgeIndex>,
+}
+
+/// Reason for rejecting an edge collapse
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CollapseError {
+    /// The collapse would make the mesh non-manifold
+    NonManifold,
+    /// A triangle around the surviving point would be flipped or degenerate
+    FlippedTriangle,
+}
+
+impl fmt::Display for CollapseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CollapseError::NonManifold => {
+                write!(f, "edge collapse would make the mesh non-manifold")
+            }
+            CollapseError::FlippedTriangle => write!(f, "edge collapse would flip a triangle"),
+        }
+    }
+}
+
+impl std::error::Error for CollapseError {}
+
+/// Inconsistency found by [`validate`](TrianglesDCEL::validate)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DcelError {
+    /// The number of vertices isn't a multiple of 3
+    VertexCount(usize),
+    /// There are fewer halfedges than vertices
+    HalfedgeCount(usize),
+    /// The twin of the edge doesn't exist
+    TwinOutOfRange(EdgeIndex),
+    /// The edge is its own twin
+    SelfTwin(EdgeIndex),
+    /// The twin of the edge has a different twin
+    AsymmetricTwin(EdgeIndex),
+    /// The edge and its twin don't connect the same points in opposite order
+    MismatchedTwin(EdgeIndex),
+}
+
+impl fmt::Display for DcelError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DcelError::VertexCount(len) => write!(f, "{} vertices don't make whole triangles", len),
+            DcelError::HalfedgeCount(len) => {
+                write!(f, "{} halfedges are too few for the vertices", len)
+            }
+            DcelError::TwinOutOfRange(e) => write!(f, "twin of {} doesn't exist", e),
+            DcelError::SelfTwin(e) => write!(f, "{} is its own twin", e),
+            DcelError::AsymmetricTwin(e) => write!(f, "twin of {} isn't linked back", e),
+            DcelError::MismatchedTwin(e) => {
+                write!(f, "{} and its twin connect different points", e)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DcelError {}
+
+/// Hash map from point pairs to edges, for callers doing lots of lookups.
+///
+/// This is a snapshot: it must be rebuilt after the DCEL is modified.
+#[derive(Debug, Clone)]
+pub struct EdgeLookup {
+    map: HashMap<(PointIndex, PointIndex), EdgeIndex>,
+}
+
+impl EdgeLookup {
+    /// Builds the lookup table for all edges of the DCEL.
+    pub fn new(dcel: &TrianglesDCEL) -> EdgeLookup {
+        let map = (0..dcel.vertices.len())
+            .map(EdgeIndex)
+            .map(|e| ((dcel.vertices[e], dcel.edge_endpoint(e)), e))
+            .collect();
+
+        EdgeLookup { map }
+    }
+
+    /// Returns the edge going from `a` to `b`, if it exists.
+    pub fn edge_between(&self, a: PointIndex, b: PointIndex) -> Option<EdgeIndex> {
+        self.map.get(&(a, b)).cloned()
+    }
+}
+
 /// Iterator of edges around a certain point in DCEL
 #[derive(Debug, Clone)]
 pub struct EdgesAroundPoint<'a> {
@@ -272,32 +1157,69 @@ impl<'a> Iterator for EdgesAroundPoint<'a> {
 
 impl<'a> std::iter::FusedIterator for EdgesAroundPoint<'a> {}
 
+/// Iterator of triangles around a certain point in DCEL, in counter-clockwise order
+#[derive(Debug, Clone)]
+pub struct TrianglesAroundPoint<'a> {
+    dcel: &'a TrianglesDCEL,
+    start: EdgeIndex,
+    current: Option<EdgeIndex>,
+}
+
+impl<'a> TrianglesAroundPoint<'a> {
+    /// Starts from any edge going out of the point
+    pub(crate) fn new(dcel: &'a TrianglesDCEL, edge: EdgeIndex) -> TrianglesAroundPoint<'a> {
+        // rewind clockwise to the boundary, if there is one
+        let mut start = edge;
+
+        while let Some(twin) = dcel.twin(start) {
+            start = dcel.next_edge(twin);
+
+            if start == edge {
+                break;
+            }
+        }
+
+        TrianglesAroundPoint {
+            dcel,
+            start,
+            current: Some(start),
+        }
+    }
+}
+
+impl<'a> Iterator for TrianglesAroundPoint<'a> {
+    type Item = EdgeIndex;
+
+    fn next(&mut self) -> Option<EdgeIndex> {
+        let result = self.current?;
+
+        self.current = self
+            .dcel
+            .twin(self.dcel.prev_edge(result))
+            .filter(|&e| e != self.start);
+
+        Some(result)
+    }
+}
+
+impl<'a> std::iter::FusedIterator for TrianglesAroundPoint<'a> {}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashSet;
 
     use super::*;
+    use crate::testing::{circular, random_points};
     use crate::Delaunay;
 
-    fn circular(count: usize) -> TrianglesDCEL {
-        let mut points = Vec::with_capacity(count + 1);
-
-        points.push(Point::new(100.0, 100.0));
-
-        for i in 0..count {
-            let angle = i as f32 / count as f32 * 2.0 * std::f32::consts::PI;
-            let (sin, cos) = angle.sin_cos();
-            points.push(Point::new(cos * 100.0 + 100.0, sin * 100.0 + 100.0));
-        }
-
-        let t = Delaunay::new(&points).unwrap();
-        t.dcel
+    fn circular_dcel(count: usize) -> TrianglesDCEL {
+        Delaunay::new(&circular(count)).unwrap().dcel
     }
 
     #[test]
     fn around_center() {
         let count = 10;
-        let mut dcel = circular(count);
+        let mut dcel = circular_dcel(count);
         assert_eq!(dcel.num_triangles(), count);
 
         dcel.init_revmap();
@@ -315,7 +1237,7 @@ mod tests {
     #[test]
     fn around_hull_vertex() {
         let count = 10;
-        let mut dcel = circular(count);
+        let mut dcel = circular_dcel(count);
         assert_eq!(dcel.num_triangles(), count);
 
         dcel.init_revmap();
@@ -329,9 +1251,776 @@ mod tests {
             assert_eq!(dcel.vertices[p], 1.into());
         }
     }
+
+    #[test]
+    fn circular_hull() {
+        use crate::Triangulation;
+
+        let count = 16;
+        let t = Delaunay::new(&circular(count)).unwrap();
+
+        let mut hull = t.hull().to_vec();
+        assert_eq!(hull.len(), count);
+
+        // the rim is generated with increasing angle, so the right-handed
+        // hull visits it backwards
+        let first = hull.iter().position(|&p| p == count.into()).unwrap();
+        hull.rotate_left(first);
+        assert_eq!(
+            hull,
+            (1..=count).rev().map(PointIndex::from).collect::<Vec<_>>()
+        );
+
+        let mut chain = t.hull_snapshot().chain();
+        let first = chain.iter().position(|&p| p == count.into()).unwrap();
+        chain.rotate_left(first);
+        assert_eq!(hull, chain);
+    }
+
+    #[test]
+    fn circumcircles() {
+        let points = circular(12);
+        let t = Delaunay::new(&points).unwrap();
+
+        let circles = t.dcel.circumcircles(&points).collect::<Vec<_>>();
+        assert_eq!(circles.len(), t.dcel.num_triangles());
+
+        for (circle, tri) in circles.iter().zip(t.dcel.triangles(&points)) {
+            assert_eq!(circle.center, tri.circumcenter());
+            assert_eq!(circle.radius_sq, tri.circumradius_sq());
+        }
+
+        let points = points.iter().map(|p| p.cast::<f64>()).collect::<Vec<_>>();
+        for (circle, tri) in t.dcel.circumcircles(&points).zip(t.dcel.triangles(&points)) {
+            assert_eq!(circle.center, tri.circumcenter());
+            assert_eq!(circle.radius_sq, tri.circumradius_sq());
+        }
+    }
+
+    #[test]
+    fn total_area() {
+        let points = circular(64);
+        let t = Delaunay::new(&points).unwrap();
+
+        let area = t.dcel.total_area(&points);
+        assert!((area - t.hull_area()).abs() < 1e-3 * area);
+
+        let points = points.iter().map(|p| p.cast::<f64>()).collect::<Vec<_>>();
+        let area_f64 = t.dcel.total_area(&points);
+        assert!((area_f64 - f64::from(area)).abs() < 1e-3 * area_f64);
+    }
+
+    #[test]
+    fn wireframe() {
+        let count = 10;
+        let dcel = circular_dcel(count);
+
+        let edges = dcel.wireframe_edges().collect::<Vec<_>>();
+        assert_eq!(edges.len(), 2 * count);
+
+        for &(a, b, boundary) in &edges {
+            let on_rim = a != 0.into() && b != 0.into();
+            assert_eq!(boundary, on_rim);
+        }
+    }
+
+    #[test]
+    fn interior_edges() {
+        let count = 10;
+        let dcel = circular_dcel(count);
+
+        assert_eq!(dcel.num_interior_edges(), count);
+        assert_eq!(dcel.num_boundary_edges(), count);
+
+        for (e, twin) in dcel.interior_edges() {
+            assert!(e < twin);
+            assert_eq!(dcel.twin(twin), Some(e));
+            assert!(dcel.vertices[e] == 0.into() || dcel.edge_endpoint(e) == 0.into());
+        }
+    }
+
+    #[test]
+    fn euler_characteristic() {
+        let (mut dcel, _) = jittered_grid(10);
+
+        // the outside and every hole are faces too
+        for holes in 0..3 {
+            let vertices = dcel.vertices.iter().collect::<HashSet<_>>().len() as isize;
+            let edges = (dcel.num_interior_edges() + dcel.num_boundary_edges()) as isize;
+            let faces = (dcel.num_triangles() + 1 + holes) as isize;
+            assert_eq!(vertices - edges + faces, 2);
+
+            assert_eq!(
+                3 * dcel.num_triangles(),
+                2 * dcel.num_interior_edges() + dcel.num_boundary_edges()
+            );
+
+            // remove a triangle away from the boundary and the other holes
+            let hole = (0..dcel.vertices.len())
+                .map(EdgeIndex::from)
+                .find(|&e| {
+                    let t = dcel.triangle_first_edge(e);
+                    dcel.triangle_edges(t).iter().all(|&x| {
+                        is_interior(&dcel, x)
+                            && is_interior(&dcel, dcel.next_edge(dcel.twin(x).unwrap()))
+                    })
+                })
+                .unwrap();
+            dcel.swap_remove_triangle(hole);
+        }
+    }
+
+    #[test]
+    fn hull_edges() {
+        let count = 10;
+        let dcel = circular_dcel(count);
+
+        let edges = dcel.hull_edges().collect::<Vec<_>>();
+        assert_eq!(edges.len(), count);
+
+        for (i, &e) in edges.iter().enumerate() {
+            let next = edges[(i + 1) % count];
+            assert_eq!(dcel.twin(e), None);
+            assert_eq!(dcel.edge_endpoint(e), dcel.vertices[next]);
+        }
+    }
+
+    #[test]
+    fn hull_edges_around_hole() {
+        let (mut dcel, _) = jittered_grid(6);
+        let outer = dcel.hull_edges().count();
+
+        let hole = (0..dcel.vertices.len())
+            .map(EdgeIndex::from)
+            .find(|&e| {
+                let t = dcel.triangle_first_edge(e);
+                dcel.triangle_edges(t)
+                    .iter()
+                    .all(|&x| is_interior(&dcel, x))
+            })
+            .unwrap();
+        let rim = dcel.triangle_points(hole);
+        dcel.swap_remove_triangle(hole);
+
+        let edges = dcel.hull_edges().collect::<Vec<_>>();
+        assert_eq!(edges.len(), outer + 3);
+
+        // the loop around the hole goes around the other way
+        let inner = edges
+            .iter()
+            .filter(|&&e| rim.contains(&dcel.vertices[e]) && rim.contains(&dcel.edge_endpoint(e)))
+            .map(|&e| (dcel.vertices[e], dcel.edge_endpoint(e)))
+            .collect::<HashSet<_>>();
+        let expected = (0..3)
+            .map(|i| (rim[(i + 1) % 3], rim[i]))
+            .collect::<HashSet<_>>();
+        assert_eq!(inner, expected);
+    }
+
+    #[test]
+    fn edge_between() {
+        let points = random_points(200, 42);
+
+        let mut dcel = Delaunay::new(&points).unwrap().dcel;
+        dcel.init_revmap();
+        let lookup = EdgeLookup::new(&dcel);
+
+        for (a, b, boundary) in dcel.wireframe_edges() {
+            let ab = dcel.edge_between(a, b);
+            let ba = dcel.edge_between(b, a);
+
+            assert_eq!(lookup.edge_between(a, b), ab);
+            assert_eq!(lookup.edge_between(b, a), ba);
+
+            if boundary {
+                assert!(ab.is_some() != ba.is_some());
+            } else {
+                assert_eq!(dcel.twin(ab.unwrap()), ba);
+                assert_eq!(dcel.vertices[ab.unwrap()], a);
+            }
+        }
+    }
+
+    #[test]
+    fn triangles_around() {
+        let count = 10;
+        let mut dcel = circular_dcel(count);
+        dcel.init_revmap();
+
+        let center = dcel.triangles_around_point(0.into()).collect::<Vec<_>>();
+        assert_eq!(center.len(), count);
+        assert_eq!(center.iter().collect::<HashSet<_>>().len(), count);
+
+        for w in center.windows(2) {
+            assert_eq!(dcel.twin(dcel.prev_edge(w[0])), Some(w[1]));
+        }
+
+        let rim = dcel.triangles_around_point(1.into()).collect::<Vec<_>>();
+        assert_eq!(rim.len(), 2);
+        assert_eq!(dcel.twin(rim[0]), None);
+        assert_eq!(dcel.twin(dcel.prev_edge(rim[1])), None);
+    }
+
+    #[test]
+    fn triangles_around_edge() {
+        let count = 10;
+        let mut dcel = circular_dcel(count);
+
+        // every edge going out of a point walks the same fan, without the
+        // point-to-triangle map
+        let fans = (0..dcel.vertices.len())
+            .map(|e| {
+                let mut fan = dcel.triangles_around_edge(e.into()).collect::<Vec<_>>();
+                assert!(fan.iter().all(|&f| dcel.vertices[f] == dcel.vertices[e]));
+                assert!(fan.contains(&e.into()));
+                fan.sort_unstable_by_key(|f| f.as_usize());
+                (dcel.vertices[e], fan)
+            })
+            .collect::<Vec<_>>();
+        assert!(dcel.points_to_triangles.is_none());
+
+        dcel.init_revmap();
+        for (p, fan) in fans {
+            let mut around = dcel.triangles_around_point(p).collect::<Vec<_>>();
+            around.sort_unstable_by_key(|f| f.as_usize());
+            assert_eq!(fan, around);
+        }
+
+        // the fan of a hull point starts at the boundary wherever it begins
+        let rim = dcel.triangles_around_point(1.into()).collect::<Vec<_>>();
+        let from_last = dcel.triangles_around_edge(rim[1]).collect::<Vec<_>>();
+        assert_eq!(rim, from_last);
+    }
+
+    #[test]
+    fn triangle_neighbours_by_opposite_point() {
+        let points = random_points(300, 13);
+        let dcel = Delaunay::new(&points).unwrap().dcel;
+
+        let mut missing = 0;
+        for t in (0..dcel.vertices.len()).step_by(3).map(EdgeIndex) {
+            let on_hull = dcel
+                .triangle_edges(t)
+                .iter()
+                .any(|&e| dcel.twin(e).is_none());
+            let neighbors = dcel.triangle_neighbours_by_opposite_point(t);
+            assert_eq!(neighbors.contains(&None), on_hull);
+
+            let corners = dcel.triangle_points(t);
+            for (k, neighbor) in neighbors.iter().enumerate() {
+                if let Some(n) = *neighbor {
+                    let across = dcel.triangle_points(n);
+                    assert!(!across.contains(&corners[k]));
+                    assert!(across.contains(&corners[(k + 1) % 3]));
+                    assert!(across.contains(&corners[(k + 2) % 3]));
+                } else {
+                    missing += 1;
+                }
+            }
+        }
+
+        assert_eq!(missing, dcel.num_boundary_edges());
+    }
+
+    #[test]
+    fn longest_edge() {
+        let points = random_points(200, 21);
+        let dcel = Delaunay::new(&points).unwrap().dcel;
+
+        for t in (0..dcel.vertices.len()).map(EdgeIndex) {
+            let longest = dcel.longest_edge(t, &points);
+            assert_eq!(
+                dcel.triangle_first_edge(longest),
+                dcel.triangle_first_edge(t)
+            );
+
+            let triangle = dcel.triangle(t, &points);
+            let (a, b) = triangle.longest_edge_endpoints();
+            assert!((dcel.edge_length(longest, &points) - a.distance(b)).abs() < 1e-4);
+
+            if let Some(twin) = dcel.twin(t) {
+                assert_eq!(
+                    dcel.edge_length_sq(t, &points),
+                    dcel.edge_length_sq(twin, &points)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn vertex_valence() {
+        let points = random_points(500, 5);
+
+        let mut dcel = Delaunay::new(&points).unwrap().dcel;
+        dcel.init_revmap();
+        let hull = dcel
+            .hull_edges()
+            .map(|e| dcel.vertices[e])
+            .collect::<HashSet<_>>();
+
+        let mut total = 0;
+        for p in (0..points.len()).map(PointIndex::from) {
+            let valence = dcel.vertex_valence(p);
+            assert_eq!(dcel.vertex_valence_fast(p), valence);
+
+            if hull.contains(&p) {
+                assert!(valence >= 1);
+            } else {
+                assert!(valence >= 3);
+            }
+
+            total += valence;
+        }
+
+        assert_eq!(total, dcel.vertices.len());
+        assert_eq!(dcel.vertex_valence_fast(points.len().into()), 0);
+    }
+
+    fn assert_links(dcel: &TrianglesDCEL) {
+        assert_eq!(dcel.validate(), Ok(()));
+
+        for e in (0..dcel.vertices.len()).map(EdgeIndex::from) {
+            assert_eq!(dcel.next_edge(dcel.prev_edge(e)), e);
+
+            if let Some(twin) = dcel.twin(e) {
+                assert_ne!(dcel.triangle_first_edge(twin), dcel.triangle_first_edge(e));
+            }
+        }
+    }
+
+    #[test]
+    fn validate() {
+        let mut dcel = circular_dcel(8);
+        assert_eq!(dcel.validate(), Ok(()));
+
+        let e = (0..dcel.vertices.len())
+            .map(EdgeIndex::from)
+            .find(|&e| dcel.twin(e).is_some())
+            .unwrap();
+        let twin = dcel.twin(e).unwrap();
+
+        let mut broken = dcel.clone();
+        broken.halfedges[e] = OptionIndex::some(e);
+        assert_eq!(broken.validate(), Err(DcelError::SelfTwin(e)));
+
+        let mut broken = dcel.clone();
+        broken.unlink(twin);
+        assert_eq!(broken.validate(), Err(DcelError::AsymmetricTwin(e)));
+
+        let mut broken = dcel.clone();
+        broken.halfedges[e] = OptionIndex::some(1000.into());
+        assert_eq!(broken.validate(), Err(DcelError::TwinOutOfRange(e)));
+
+        let mut broken = dcel.clone();
+        let next = broken.next_edge(twin);
+        broken.vertices[next] = 100.into();
+        assert!(matches!(
+            broken.validate(),
+            Err(DcelError::MismatchedTwin(_))
+        ));
+
+        let mut broken = dcel.clone();
+        broken.vertices.pop();
+        assert_eq!(broken.validate(), Err(DcelError::VertexCount(3 * 8 - 1)));
+
+        dcel.halfedges.truncate(3 * 8 - 1);
+        assert_eq!(dcel.validate(), Err(DcelError::HalfedgeCount(3 * 8 - 1)));
+    }
+
+    #[test]
+    fn split_edge() {
+        let count = 8;
+        let mut dcel = circular_dcel(count);
+        let mut expected = dcel.num_triangles();
+        let mut next_point = count + 1;
+
+        let interior = (0..dcel.vertices.len())
+            .map(EdgeIndex::from)
+            .find(|&e| dcel.twin(e).is_some())
+            .unwrap();
+
+        let [av, vb, vc, cv] = dcel.split_edge(interior, next_point.into());
+        expected += 2;
+        next_point += 1;
+
+        assert_eq!(dcel.num_triangles(), expected);
+        assert_links(&dcel);
+        assert!(dcel.twin(av).is_some() && dcel.twin(vb).is_some());
+        assert_eq!(dcel.twin(vc), Some(cv));
+
+        let boundary = (0..dcel.vertices.len())
+            .map(EdgeIndex::from)
+            .find(|&e| dcel.twin(e).is_none())
+            .unwrap();
+
+        let [av, vb, _, _] = dcel.split_edge(boundary, next_point.into());
+        expected += 1;
+        next_point += 1;
+
+        assert_eq!(dcel.num_triangles(), expected);
+        assert_links(&dcel);
+        assert!(dcel.twin(av).is_none() && dcel.twin(vb).is_none());
+
+        // keep splitting the resulting edges
+        let mut queue = vec![av, vb];
+        for _ in 0..30 {
+            let e = queue.remove(0);
+            expected += if dcel.twin(e).is_some() { 2 } else { 1 };
+
+            queue.extend_from_slice(&dcel.split_edge(e, next_point.into()));
+            next_point += 1;
+
+            assert_eq!(dcel.num_triangles(), expected);
+            assert_links(&dcel);
+        }
+    }
+
+    #[test]
+    fn split_triangle() {
+        let count = 8;
+        let mut dcel = circular_dcel(count);
+        let triangles = dcel.num_triangles();
+
+        let [t0, t1, t2] = dcel.split_triangle(3.into(), (count + 1).into());
+        assert_eq!(dcel.num_triangles(), triangles + 2);
+        assert_links(&dcel);
+
+        for &t in &[t0, t1, t2] {
+            assert_eq!(dcel.triangle_points(t)[2], (count + 1).into());
+        }
+
+        for _ in 0..10 {
+            let [_, t1, _] = dcel.split_triangle(t1, (dcel.num_triangles() + count).into());
+            assert_links(&dcel);
+            dcel.split_triangle(t1, (dcel.num_triangles() + count).into());
+            assert_links(&dcel);
+        }
+
+        assert_eq!(dcel.num_triangles(), triangles + 42);
+    }
+
+    #[test]
+    fn flip() {
+        let mut dcel = circular_dcel(8);
+
+        // triangles as sorted rotations starting at the smallest point
+        let triangles = |dcel: &TrianglesDCEL| {
+            let mut triangles = (0..dcel.num_triangles())
+                .map(|t| {
+                    let mut t = dcel.triangle_points((3 * t).into());
+                    while t[0].0 > t[1].0 || t[0].0 > t[2].0 {
+                        t.rotate_left(1);
+                    }
+                    t.map(|p| p.0)
+                })
+                .collect::<Vec<_>>();
+            triangles.sort_unstable();
+            triangles
+        };
+        let before = triangles(&dcel);
+
+        let boundary = (0..dcel.vertices.len())
+            .map(EdgeIndex::from)
+            .find(|&e| dcel.twin(e).is_none())
+            .unwrap();
+        assert_eq!(dcel.flip(boundary), None);
+        assert_eq!(triangles(&dcel), before);
+
+        for e in (0..dcel.vertices.len()).map(EdgeIndex::from) {
+            let twin = match dcel.twin(e) {
+                Some(twin) => twin,
+                None => continue,
+            };
+
+            let [p0, pr, pl] = dcel.triangle_points(dcel.prev_edge(e));
+            let p1 = dcel.vertices[dcel.prev_edge(twin)];
+
+            let diagonal = dcel.flip(e).unwrap();
+            assert_links(&dcel);
+            assert_eq!(
+                (dcel.vertices[diagonal], dcel.edge_endpoint(diagonal)),
+                (p0, p1)
+            );
+            assert_eq!(dcel.triangle_points(diagonal), [p0, p1, pl]);
+            assert_eq!(dcel.twin(diagonal), Some(dcel.prev_edge(twin)));
+            assert_eq!(dcel.triangle_points(dcel.prev_edge(twin)), [p1, p0, pr]);
+            assert_ne!(triangles(&dcel), before);
+
+            dcel.flip(diagonal).unwrap();
+            assert_links(&dcel);
+            assert_eq!(triangles(&dcel), before);
+        }
+    }
+
+    #[test]
+    fn incremental_insertion() {
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(21);
+        let mut points = vec![
+            Point::new(-1000.0, -1000.0),
+            Point::new(-1000.0, 1000.0),
+            Point::new(1000.0, 0.0),
+        ];
+        points.extend(
+            (0..100).map(|_| Point::new(rng.gen_range(0.0, 100.0), rng.gen_range(0.0, 100.0))),
+        );
+
+        let mut dcel = TrianglesDCEL::with_capacity(1);
+        dcel.add_triangle([0.into(), 1.into(), 2.into()]);
+        assert!(dcel.triangle(0.into(), &points).robust_orientation() > 0.0);
+
+        let illegal = |dcel: &TrianglesDCEL, e: EdgeIndex| {
+            dcel.twin(e).map_or(false, |f| {
+                let d = points[dcel.vertices[dcel.prev_edge(f)]];
+                dcel.triangle(e, &points).in_circumcircle(d)
+            })
+        };
+
+        for i in 3..points.len() {
+            let p = points[i];
+            let t = (0..dcel.num_triangles())
+                .map(|t| EdgeIndex::from(3 * t))
+                .find(|&t| {
+                    dcel.triangle_edges(t).iter().all(|&e| {
+                        let (a, b) = (points[dcel.vertices[e]], points[dcel.edge_endpoint(e)]);
+                        Triangle(a, b, p).robust_orientation() >= 0.0
+                    })
+                })
+                .unwrap();
+
+            let on_edge = dcel.triangle_edges(t).iter().cloned().find(|&e| {
+                let (a, b) = (points[dcel.vertices[e]], points[dcel.edge_endpoint(e)]);
+                Triangle(a, b, p).robust_orientation() == 0.0
+            });
+
+            match on_edge {
+                Some(e) => {
+                    dcel.split_edge(e, i.into());
+                }
+                None => {
+                    dcel.split_triangle(t, i.into());
+                }
+            }
+
+            // Lawson's flipping until every edge is locally Delaunay
+            while let Some(e) = (0..dcel.vertices.len())
+                .map(EdgeIndex::from)
+                .find(|&e| illegal(&dcel, e))
+            {
+                dcel.flip(e);
+            }
+
+            assert_links(&dcel);
+        }
+
+        assert_eq!(dcel.num_triangles(), 2 * points.len() - 2 - 3);
+        assert!(dcel
+            .triangles(&points)
+            .all(|t| t.robust_orientation() > 0.0));
+
+        // every triangle has an empty circumcircle
+        for t in dcel.triangles(&points) {
+            assert!(points.iter().all(|&p| !t.in_circumcircle(p)));
+        }
+    }
+
+    fn fan() -> (TrianglesDCEL, Vec<Point>) {
+        let points = vec![
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 2.0),
+            Point::new(2.0, 0.0),
+            Point::new(1.0, 0.5),
+            Point::new(1.0, 5.0),
+        ];
+
+        let mut dcel = TrianglesDCEL::with_capacity(1);
+        let t = dcel.add_triangle([0.into(), 1.into(), 2.into()]);
+        dcel.split_triangle(t, 3.into());
+        dcel.init_revmap();
+
+        (dcel, points)
+    }
+
+    fn jittered_grid(size: usize) -> (TrianglesDCEL, Vec<Point>) {
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(3);
+        let points = (0..size * size)
+            .map(|i| {
+                Point::new(
+                    (i % size) as f32 * 10.0 + rng.gen_range(-2.0, 2.0),
+                    (i / size) as f32 * 10.0 + rng.gen_range(-2.0, 2.0),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        (Delaunay::new(&points).unwrap().dcel, points)
+    }
+
+    fn is_interior(dcel: &TrianglesDCEL, e: EdgeIndex) -> bool {
+        TrianglesAroundPoint::new(dcel, e).all(|x| dcel.twin(x).is_some())
+    }
+
+    #[test]
+    fn collapse_interior() {
+        let (mut dcel, points) = jittered_grid(6);
+        let triangles = dcel.num_triangles();
+
+        let e = (0..dcel.vertices.len())
+            .map(EdgeIndex::from)
+            .find(|&e| is_interior(&dcel, e) && is_interior(&dcel, dcel.next_edge(e)))
+            .unwrap();
+        let (a, b) = (dcel.vertices[e], dcel.edge_endpoint(e));
+
+        let info = dcel
+            .collapse_edge(e, CollapseTarget::Endpoint, &points)
+            .unwrap();
+        assert_eq!(info.vertex, b);
+        assert_eq!(info.removed_triangles.len(), 2);
+        assert!(info.removed_triangles[0] > info.removed_triangles[1]);
+
+        assert_eq!(dcel.num_triangles(), triangles - 2);
+        assert_links(&dcel);
+        assert!(dcel.is_connected());
+        assert!(!dcel.vertices.contains(&a));
+        assert!(dcel
+            .triangles(&points)
+            .all(|t| t.robust_orientation() > 0.0));
+
+        // the merged point is still surrounded by a closed fan
+        let start = EdgeIndex::from(dcel.vertices.iter().position(|&v| v == b).unwrap());
+        assert!(is_interior(&dcel, start));
+    }
+
+    #[test]
+    fn collapse_boundary() {
+        let (mut dcel, mut points) = jittered_grid(6);
+        let triangles = dcel.num_triangles();
+
+        let e = (0..dcel.vertices.len())
+            .map(EdgeIndex::from)
+            .find(|&e| dcel.twin(e).is_none() && is_interior(&dcel, dcel.prev_edge(e)))
+            .unwrap();
+        let (a, b) = (dcel.vertices[e], dcel.edge_endpoint(e));
+
+        let (pa, pb) = (points[a.as_usize()], points[b.as_usize()]);
+        points.push(Point::new((pa.x + pb.x) / 2.0, (pa.y + pb.y) / 2.0));
+        let middle = PointIndex::from(points.len() - 1);
+
+        let info = dcel
+            .collapse_edge(e, CollapseTarget::Point(middle), &points)
+            .unwrap();
+        assert_eq!(info.vertex, middle);
+        assert_eq!(info.removed_triangles.len(), 1);
+
+        assert_eq!(dcel.num_triangles(), triangles - 1);
+        assert_links(&dcel);
+        assert!(dcel.is_connected());
+        assert!(!dcel.vertices.contains(&a) && !dcel.vertices.contains(&b));
+        assert!(dcel
+            .triangles(&points)
+            .all(|t| t.robust_orientation() > 0.0));
+
+        let hull_starts = (0..dcel.vertices.len())
+            .map(EdgeIndex::from)
+            .filter(|&e| dcel.twin(e).is_none() && dcel.vertices[e] == middle)
+            .count();
+        assert_eq!(hull_starts, 1);
+    }
+
+    #[test]
+    fn collapse_rejected() {
+        let (mut dcel, points) = fan();
+        let before = (dcel.vertices.clone(), dcel.halfedges.clone());
+
+        // 0 and 1 share the neighbor 2 as well as the opposite point 3
+        let e = dcel.edge_between(0.into(), 1.into()).unwrap();
+        assert_eq!(
+            dcel.collapse_edge(e, CollapseTarget::Origin, &points),
+            Err(CollapseError::NonManifold)
+        );
+
+        // moving both points of an interior edge far away flips a triangle
+        let e = dcel.edge_between(3.into(), 0.into()).unwrap();
+        assert_eq!(
+            dcel.collapse_edge(e, CollapseTarget::Point(4.into()), &points),
+            Err(CollapseError::FlippedTriangle)
+        );
+
+        assert_eq!((dcel.vertices.clone(), dcel.halfedges.clone()), before);
+
+        // an interior edge between two hull points would pinch the mesh
+        let points = vec![
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(10.0, 10.0),
+            Point::new(0.0, 10.0),
+        ];
+        let mut dcel = Delaunay::new(&points).unwrap().dcel;
+        let e = (0..dcel.vertices.len())
+            .map(EdgeIndex::from)
+            .find(|&e| dcel.twin(e).is_some())
+            .unwrap();
+        assert_eq!(
+            dcel.collapse_edge(e, CollapseTarget::Origin, &points),
+            Err(CollapseError::NonManifold)
+        );
+
+        // a lone triangle is an ear
+        let mut dcel = TrianglesDCEL::with_capacity(1);
+        dcel.add_triangle([0.into(), 1.into(), 2.into()]);
+        assert_eq!(
+            dcel.collapse_edge(0.into(), CollapseTarget::Origin, &points),
+            Err(CollapseError::NonManifold)
+        );
+    }
+
+    #[test]
+    fn collapse_repeatedly() {
+        let (mut dcel, points) = jittered_grid(8);
+        let mut collapsed = 0;
+
+        while (0..dcel.vertices.len()).map(EdgeIndex::from).any(|e| {
+            dcel.collapse_edge(e, CollapseTarget::Origin, &points)
+                .is_ok()
+        }) {
+            collapsed += 1;
+            assert_links(&dcel);
+            assert!(dcel.is_connected());
+            assert!(dcel
+                .triangles(&points)
+                .all(|t| t.robust_orientation() > 0.0));
+        }
+
+        assert!(collapsed > 10);
+    }
+
+    #[test]
+    fn connected() {
+        assert!(circular_dcel(10).is_connected());
+
+        let mut dcel = TrianglesDCEL::with_capacity(3);
+        dcel.add_triangle([0.into(), 1.into(), 2.into()]);
+        let a = dcel.add_triangle([2.into(), 1.into(), 3.into()]);
+        let b = dcel.add_triangle([3.into(), 1.into(), 4.into()]);
+        dcel.link(a + 1, b);
+        assert!(!dcel.is_connected());
+    }
+
+    #[test]
+    fn display() {
+        let mut dcel = TrianglesDCEL::with_capacity(1);
+        let t = dcel.add_triangle([4.into(), 7.into(), 12.into()]);
+
+        assert_eq!((t + 2).to_string(), "e2");
+        assert_eq!(dcel.vertices[t + 2].to_string(), "p12");
+        assert_eq!(format!("{} -> {}", t, dcel.edge_endpoint(t)), "e0 -> p7");
+    }
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EdgeIndex(usize);
 
 impl EdgeIndex {
@@ -346,12 +2035,18 @@ impl From<usize> for EdgeIndex {
     }
 }
 
-impl From<EdgeIndex> for usize  {
+impl From<EdgeIndex> for usize {
     fn from(idx: EdgeIndex) -> Self {
         idx.0
     }
 }
 
+impl fmt::Display for EdgeIndex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "e{}", self.0)
+    }
+}
+
 // For indexing into vertex records
 impl Index<EdgeIndex> for [PointIndex] {
     type Output = PointIndex;
@@ -427,6 +2122,7 @@ impl Sub<usize> for EdgeIndex {
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PointIndex(usize);
 
 impl PointIndex {
@@ -441,35 +2137,41 @@ impl From<usize> for PointIndex {
     }
 }
 
-impl From<PointIndex> for usize  {
+impl From<PointIndex> for usize {
     fn from(idx: PointIndex) -> Self {
         idx.0
     }
 }
 
-impl Index<PointIndex> for [Point] {
-    type Output = Point;
+impl fmt::Display for PointIndex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "p{}", self.0)
+    }
+}
+
+impl<T> Index<PointIndex> for [Point<T>] {
+    type Output = Point<T>;
 
     fn index(&self, idx: PointIndex) -> &Self::Output {
         self.get(idx.0).unwrap()
     }
 }
 
-impl IndexMut<PointIndex> for [Point] {
+impl<T> IndexMut<PointIndex> for [Point<T>] {
     fn index_mut(&mut self, idx: PointIndex) -> &mut Self::Output {
         self.get_mut(idx.0).unwrap()
     }
 }
 
-impl Index<PointIndex> for Vec<Point> {
-    type Output = Point;
+impl<T> Index<PointIndex> for Vec<Point<T>> {
+    type Output = Point<T>;
 
     fn index(&self, idx: PointIndex) -> &Self::Output {
         self.get(idx.0).unwrap()
     }
 }
 
-impl IndexMut<PointIndex> for Vec<Point> {
+impl<T> IndexMut<PointIndex> for Vec<Point<T>> {
     fn index_mut(&mut self, idx: PointIndex) -> &mut Self::Output {
         self.get_mut(idx.0).unwrap()
     }
@@ -1,28 +1,122 @@
+//! Divide-and-conquer Delaunay triangulation, see [`Delaunay::new_divconq`].
+//!
+//! Every triangle stored in a [`Half`] is right-handed (counter-clockwise),
+//! the same convention as [`TrianglesDCEL`] uses.
+
+use std::collections::{HashMap, VecDeque};
 use std::ops::Range;
 
-use crate::geom::{Point, Triangle};
-use crate::OptionIndex;
+use crate::dcel::{EdgeIndex, PointIndex, TrianglesDCEL};
+use crate::geom::{Point, Scalar, Triangle};
+use crate::{Delaunay, DelaunayError, OptionIndex};
+
+impl<T: Scalar> Delaunay<T> {
+    /// Triangulates a set of given points with the divide-and-conquer
+    /// algorithm instead of the incremental one [`new`](Delaunay::new) uses.
+    ///
+    /// The points are sorted by their coordinates, split in halves down to
+    /// two or three points, and the halves are merged back along their
+    /// common tangents. The result is the same triangulation up to the
+    /// order of the triangles and the choice between cocircular points.
+    /// Duplicate points are skipped like [`new`](Delaunay::new) does.
+    ///
+    /// This is meant for checking results against a different algorithm,
+    /// not for speed: on uniform input it is two to three times slower than
+    /// [`new`](Delaunay::new), and the input isn't normalized like by the
+    /// [`DelaunayBuilder`](crate::DelaunayBuilder).
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Delaunay, DelaunayError, Point};
+    /// let points = &[
+    ///     Point::new(10.0, 10.0),
+    ///     Point::new(100.0, 20.0),
+    ///     Point::new(60.0, 120.0),
+    ///     Point::new(50.0, 50.0),
+    /// ];
+    ///
+    /// let t = Delaunay::new_divconq(points).unwrap();
+    /// assert_eq!(t.dcel.num_triangles(), 3);
+    ///
+    /// let line = &[Point::new(0.0, 0.0), Point::new(1.0, 1.0), Point::new(2.0, 2.0)];
+    /// assert_eq!(Delaunay::new_divconq(line).err(), Some(DelaunayError::AllCollinear));
+    /// ```
+    pub fn new_divconq(points: &[Point<T>]) -> Result<Delaunay<T>, DelaunayError> {
+        if points.iter().any(|p| !p.x.is_finite() || !p.y.is_finite()) {
+            return Err(DelaunayError::InvalidCoordinate);
+        }
+
+        if points.len() < 3 {
+            return Err(DelaunayError::TooFewPoints);
+        }
+
+        let mut order = (0..points.len()).collect::<Vec<_>>();
+        order.sort_by(|&a, &b| {
+            let (a, b) = (points[a], points[b]);
+            a.x.partial_cmp(&b.x)
+                .unwrap()
+                .then(a.y.partial_cmp(&b.y).unwrap())
+        });
+        order.dedup_by(|&mut a, &mut b| points[a].approx_eq(points[b]));
+
+        let sorted = order.iter().map(|&i| points[i]).collect::<Vec<_>>();
+
+        // the first and the last point are distinct after removing the
+        // duplicates, so all points are collinear if they lie on their line
+        let (first, last) = (sorted[0], sorted[sorted.len() - 1]);
+        if !sorted
+            .iter()
+            .any(|&p| Triangle(first, last, p).robust_orientation() != 0.0)
+        {
+            return Err(DelaunayError::AllCollinear);
+        }
+
+        let half = triangulate(0..sorted.len(), Side::Left, &sorted);
+
+        Ok(Delaunay::from_dcel(half.into_dcel(&order), points))
+    }
+}
+
+/// Triangulates the sorted points in `range`, splitting them in halves until
+/// two or three are left
+fn triangulate<T: Scalar>(range: Range<usize>, side: Side, points: &[Point<T>]) -> Half {
+    let len = range.end - range.start;
+
+    if len <= 3 {
+        return Half::new(range, side, points);
+    }
 
+    let mid = range.start + len / 2;
+    let left = triangulate(range.start..mid, Side::Left, points);
+    let right = triangulate(mid..range.end, Side::Right, points);
+    left.merge(right, side, points)
+}
+
+/// Triangulation of a range of the sorted points, with the point indices
+/// relative to the start of the range
 #[derive(Clone, Debug)]
-struct Half {
+pub(crate) struct Half {
     triangles: Vec<usize>,
-    halfedges: Vec<OptionIndex>,
+    halfedges: Vec<OptionIndex<usize>>,
     bottom_most: usize,
     offset: usize,
+    /// True if the points are collinear and `triangles` holds the loop going
+    /// along them and back instead of triangles
+    chain: bool,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
-enum Side {
+pub(crate) enum Side {
     Left,
     Right,
 }
 
 impl Half {
-    pub fn new(range: Range<usize>, side: Side, points: &[Point]) -> Half {
+    pub fn new<T: Scalar>(range: Range<usize>, side: Side, points: &[Point<T>]) -> Half {
         let len = range.end - range.start;
 
         if len == 2 {
-            Half::new_single_edge(range.start, side, points)
+            Half::new_chain(range, side, points)
         } else if len == 3 {
             Half::new_single_tri(range.start, side, points)
         } else {
@@ -30,69 +124,158 @@ impl Half {
         }
     }
 
-    fn new_single_edge(offset: usize, side: Side, points: &[Point]) -> Half {
-        let bottom_most = (0..2)
-            .min_by(|a, b| {
-                let a = points[a + offset];
-                let b = points[b + offset];
-
-                if side == Side::Left {
-                    b.y.partial_cmp(&a.y)
-                        .unwrap()
-                        .then(b.x.partial_cmp(&a.x).unwrap())
-                } else {
-                    b.y.partial_cmp(&a.y)
-                        .unwrap()
-                        .then(a.x.partial_cmp(&b.x).unwrap())
-                }
-            })
-            .unwrap();
+    /// Creates a half from a triangulation of the sorted points starting at
+    /// `offset`, numbered from zero like in its own slice of them.
+    ///
+    /// The triangulation mustn't be collinear, and [`Half::with_side`] has to
+    /// be called before merging.
+    pub(crate) fn from_dcel(dcel: &TrianglesDCEL, offset: usize) -> Half {
+        let len = dcel.vertices.len();
 
         Half {
-            triangles: vec![1, 0],
-            halfedges: vec![OptionIndex::none(); 2],
-            bottom_most,
+            triangles: dcel.vertices.iter().map(|v| v.as_usize()).collect(),
+            halfedges: dcel.halfedges[..len]
+                .iter()
+                .map(|h| match h.get() {
+                    Some(h) => OptionIndex::some(h.as_usize()),
+                    None => OptionIndex::none(),
+                })
+                .collect(),
+            bottom_most: 0,
             offset,
+            chain: false,
         }
     }
 
-    fn new_single_tri(offset: usize, side: Side, points: &[Point]) -> Half {
-        let mut bottom_most = (0..3)
-            .min_by(|a, b| {
-                let a = points[a + offset];
-                let b = points[b + offset];
+    /// Sets the side the half takes in the next merge
+    pub(crate) fn with_side<T: Scalar>(mut self, side: Side, points: &[Point<T>]) -> Half {
+        self.bottom_most = self.find_bottom_most(0..self.triangles.len(), side, points);
+        self
+    }
 
-                if side == Side::Left {
-                    b.y.partial_cmp(&a.y)
-                        .unwrap()
-                        .then(a.x.partial_cmp(&b.x).unwrap())
-                } else {
-                    b.y.partial_cmp(&a.y)
-                        .unwrap()
-                        .then(b.x.partial_cmp(&a.x).unwrap())
-                }
-            })
-            .unwrap();
+    /// Converts a half starting at the first sorted point to a DCEL, taking
+    /// the indices of the points from `order`
+    pub(crate) fn into_dcel(self, order: &[usize]) -> TrianglesDCEL {
+        let mut dcel = TrianglesDCEL::with_capacity(self.triangles.len() / 3);
+        for t in self.triangles.chunks(3) {
+            dcel.add_triangle([
+                PointIndex::from(order[t[0]]),
+                PointIndex::from(order[t[1]]),
+                PointIndex::from(order[t[2]]),
+            ]);
+        }
+
+        for (e, h) in self.halfedges.iter().enumerate() {
+            if let Some(h) = h.get() {
+                dcel.link(EdgeIndex::from(e), EdgeIndex::from(h));
+            }
+        }
+
+        dcel
+    }
+
+    /// Creates a half of sorted collinear points, linked from the last one
+    /// down to the first one and back up
+    fn new_chain<T: Scalar>(range: Range<usize>, side: Side, points: &[Point<T>]) -> Half {
+        let len = range.end - range.start;
+        let triangles = (0..len).rev().chain(1..len - 1).collect::<Vec<_>>();
 
+        let mut half = Half {
+            halfedges: vec![OptionIndex::none(); triangles.len()],
+            triangles,
+            bottom_most: 0,
+            offset: range.start,
+            chain: true,
+        };
+
+        half.bottom_most = half.find_bottom_most(0..half.triangles.len(), side, points);
+        half
+    }
+
+    fn new_single_tri<T: Scalar>(offset: usize, side: Side, points: &[Point<T>]) -> Half {
         let tri = Triangle(points[offset], points[offset + 1], points[offset + 2]);
+        let orientation = tri.robust_orientation();
 
-        let triangles = if tri.is_right_handed() {
+        if orientation == 0.0 {
+            return Half::new_chain(offset..offset + 3, side, points);
+        }
+
+        let triangles = if orientation > 0.0 {
             vec![0, 1, 2]
         } else {
-            bottom_most = match bottom_most {
-                2 => 1,
-                1 => 2,
-                a => a,
-            };
-
             vec![0, 2, 1]
         };
 
-        Half {
+        let mut half = Half {
             triangles,
             halfedges: vec![OptionIndex::none(); 3],
-            bottom_most,
+            bottom_most: 0,
             offset,
+            chain: false,
+        };
+
+        // `bottom_most` is an edge index, so it has to be looked up after the
+        // winding is fixed
+        half.bottom_most = half.find_bottom_most(0..3, side, points);
+        half.debug_assert_ccw(points);
+        half
+    }
+
+    /// Returns the edge starting at the lowest point, ties are broken towards
+    /// the outer side of the half
+    fn find_bottom_most<T: Scalar>(
+        &self,
+        edges: Range<usize>,
+        side: Side,
+        points: &[Point<T>],
+    ) -> usize {
+        // the lowest point lies on the hull, which every point on it starts
+        // exactly one edge without a twin of
+        edges
+            .filter(|&e| self.halfedges[e].is_none())
+            .min_by(|&a, &b| {
+                let a = self.point(a, points);
+                let b = self.point(b, points);
+                let by_y = b.y.partial_cmp(&a.y).unwrap();
+
+                if side == Side::Left {
+                    by_y.then(a.x.partial_cmp(&b.x).unwrap())
+                } else {
+                    by_y.then(b.x.partial_cmp(&a.x).unwrap())
+                }
+            })
+            .unwrap()
+    }
+
+    /// Checks that every live triangle is right-handed
+    fn debug_assert_ccw<T: Scalar>(&self, points: &[Point<T>]) {
+        if !cfg!(debug_assertions) || self.chain {
+            return;
+        }
+
+        for t in (0..self.triangles.len()).step_by(3) {
+            let [a, b, c] = [
+                self.triangles[t],
+                self.triangles[t + 1],
+                self.triangles[t + 2],
+            ];
+
+            // deleted triangles are zeroed out
+            if a == b && b == c {
+                continue;
+            }
+
+            let tri = Triangle(
+                self.point(t, points),
+                self.point(t + 1, points),
+                self.point(t + 2, points),
+            );
+
+            debug_assert!(
+                tri.is_right_handed(),
+                "triangle {} is not counter-clockwise",
+                t / 3
+            );
         }
     }
 
@@ -112,50 +295,128 @@ impl Half {
         }
     }
 
-    fn point(&self, edge: usize, points: &[Point]) -> Point {
+    fn point<T: Scalar>(&self, edge: usize, points: &[Point<T>]) -> Point<T> {
         points[self.offset + self.triangles[edge]]
     }
 
-    fn find_base_lr(&self, other: &Half, points: &[Point]) -> (usize, usize) {
-        let left_is_lower =
-            self.point(self.bottom_most, points).y > other.point(other.bottom_most, points).y;
+    /// Returns the edge going the opposite way along a chain
+    fn chain_twin(&self, edge: usize) -> usize {
+        self.triangles.len() - 1 - edge
+    }
+
+    /// Returns true if the triangle of `edge` has been deleted
+    fn is_removed(&self, edge: usize) -> bool {
+        let t = self.triangle_first_edge(edge);
+        self.triangles[t] == self.triangles[t + 1] && self.triangles[t + 1] == self.triangles[t + 2]
+    }
+
+    /// Rotates around the starting point of `edge` to the hull edge leaving it
+    fn outgoing_hull_edge(&self, mut edge: usize) -> usize {
+        if self.chain {
+            return edge;
+        }
+
+        while let Some(twin) = self.halfedges[edge].get() {
+            edge = self.next_edge(twin);
+        }
+
+        edge
+    }
+
+    /// Rotates around the starting point of `edge` to the hull edge entering it
+    fn incoming_hull_edge(&self, edge: usize) -> usize {
+        if self.chain {
+            return (edge + self.triangles.len() - 1) % self.triangles.len();
+        }
+
+        let mut edge = self.prev_edge(edge);
+
+        while let Some(twin) = self.halfedges[edge].get() {
+            edge = self.prev_edge(twin);
+        }
+
+        edge
+    }
+
+    /// Returns the hull edge following the hull edge `edge` counter-clockwise
+    fn next_hull_edge(&self, edge: usize) -> usize {
+        if self.chain {
+            (edge + 1) % self.triangles.len()
+        } else {
+            self.outgoing_hull_edge(self.next_edge(edge))
+        }
+    }
 
-        let (victim, culprit) = if left_is_lower {
-            (self, other)
+    /// Finds the lower common tangent of the two halves, walking from their
+    /// bottom-most points towards each other to the extreme ones and from
+    /// there down both hulls.
+    ///
+    /// Returns the hull edge leaving the left point, and the edge leaving the
+    /// right point in the triangle of its incoming hull edge, which are the
+    /// base edges [`Half::candidates`] starts from.
+    fn find_base_lr<T: Scalar>(&self, other: &Half, points: &[Point<T>]) -> (usize, usize) {
+        // the ends of chains are already the extreme points, while their
+        // bottom-most point may be anywhere along them
+        let mut left = if self.chain {
+            0
         } else {
-            (other, self)
+            self.outgoing_hull_edge(self.bottom_most)
+        };
+        let mut right = if other.chain {
+            other.triangles.len() / 2 - 1
+        } else {
+            other.incoming_hull_edge(other.bottom_most)
         };
 
-        let start = culprit.bottom_most;
-        let start_pt = culprit.point(start, points);
-        let mut end = victim.bottom_most;
+        // points sharing the extreme x are told apart by y, the same way
+        // they are sorted, or a tangent through them may skip one
+        let key = |p: Point<T>| (p.x, p.y);
 
         loop {
-            let next_in_tri = victim.next_edge(end);
+            let next = self.next_hull_edge(left);
 
-            let next = victim.halfedges[next_in_tri]
-                .get()
-                .map(|e| victim.next_edge(e))
-                .unwrap_or(next_in_tri);
+            if key(self.point(next, points)) <= key(self.point(left, points)) {
+                break;
+            }
 
-            let tri = Triangle(
-                victim.point(end, points),
-                start_pt,
-                victim.point(next, points),
-            );
+            left = next;
+        }
 
-            if left_is_lower && tri.is_right_handed() || !left_is_lower && tri.is_left_handed() {
-                break;
+        while key(other.point(right, points))
+            < key(other.point(other.next_hull_edge(right), points))
+        {
+            right = other.incoming_hull_edge(right);
+        }
+
+        loop {
+            let l = self.point(left, points);
+            let r_next = other.next_hull_edge(right);
+            let r = other.point(r_next, points);
+
+            let l_prev = self.incoming_hull_edge(left);
+
+            if Triangle(l, r, self.point(l_prev, points)).is_left_handed() {
+                left = l_prev;
+                continue;
+            }
+
+            let r_next_pt = other.point(other.next_hull_edge(r_next), points);
+
+            if Triangle(l, r, r_next_pt).is_left_handed() {
+                right = r_next;
+                continue;
             }
 
-            end = next;
+            break;
         }
 
-        if left_is_lower {
-            (end, start)
+        let right = if other.chain {
+            other.chain_twin(right)
         } else {
-            (start, end)
-        }
+            other.next_edge(right)
+        };
+
+        (left, right)
     }
 
     fn candidates(&self, side: Side, edge: usize) -> Candidates<'_> {
@@ -171,25 +432,91 @@ impl Half {
         t - t % 3
     }
 
-    fn delete_triangle(&mut self, side: Side, t: usize, base: &mut usize) -> bool {
-        let t = self.triangle_first_edge(t);
-        let base_t = self.triangle_first_edge(*base);
+    fn link(&mut self, a: usize, b: usize) {
+        self.halfedges[a] = OptionIndex::some(b);
+        self.halfedges[b] = OptionIndex::some(a);
+    }
 
-        if base_t == t {
-            let next = if side == Side::Right {
-                self.halfedges[*base].get().map(|v| self.next_edge(v))
-            } else {
-                self.halfedges[self.prev_edge(*base)].get()
-            };
+    /// Returns the points a step of the walk along `side` goes from and to
+    fn step_points(&self, side: Side, step: Step) -> (usize, usize) {
+        match step {
+            Step::Edge(e) => {
+                let (a, b) = (self.triangles[e], self.triangles[self.next_edge(e)]);
+
+                if side == Side::Left {
+                    (a, b)
+                } else {
+                    (b, a)
+                }
+            }
+            Step::Lone(a, b) => (a, b),
+        }
+    }
+
+    /// Returns the hull edge following `edge` in the walk along `side`
+    fn next_walk_edge(&self, side: Side, edge: usize) -> usize {
+        match side {
+            Side::Left => self.outgoing_hull_edge(self.next_edge(edge)),
+            Side::Right => self.incoming_hull_edge(edge),
+        }
+    }
+
+    /// Deletes the triangle of the first step of `frontier`, removing the edge
+    /// to its candidate, and walks along the two edges left instead
+    fn delete_triangle(
+        &mut self,
+        side: Side,
+        edge: usize,
+        frontier: &mut VecDeque<Step>,
+        pending: &mut Pending,
+    ) {
+        let (near, far) = match side {
+            Side::Left => (self.prev_edge(edge), self.next_edge(edge)),
+            Side::Right => (self.next_edge(edge), self.prev_edge(edge)),
+        };
 
-            if let Some(next) = next {
-                *base = next;
+        let (base, candidate) = self.step_points(side, Step::Edge(edge));
+        let third = self.triangles[self.prev_edge(edge)];
 
-                if self.bottom_most == base_t {
-                    self.bottom_most = next;
+        frontier.pop_front();
+
+        // an edge left without triangles is walked along in both directions,
+        // the way back replacing the hull edge it was
+        let far_step = match self.halfedges[far].get() {
+            Some(twin) => Step::Edge(twin),
+            None => {
+                if let Some(i) = frontier.iter().position(|&s| s == Step::Edge(far)) {
+                    frontier[i] = Step::Lone(candidate, third);
                 }
-            } else {
-                return false;
+                Step::Lone(third, candidate)
+            }
+        };
+        let near_step = match self.halfedges[near].get() {
+            Some(twin) => Step::Edge(twin),
+            None => {
+                if let Some(i) = frontier.iter().rposition(|&s| s == Step::Edge(near)) {
+                    frontier[i] = Step::Lone(third, base);
+                }
+                Step::Lone(base, third)
+            }
+        };
+
+        frontier.push_front(far_step);
+        frontier.push_front(near_step);
+
+        self.remove_triangle(edge, pending);
+    }
+
+    /// Zeroes out the triangle of `edge` and unlinks it from its neighbors,
+    /// cross edges waiting for one of its edges are left on a lone edge
+    fn remove_triangle(&mut self, edge: usize, pending: &mut Pending) {
+        let t = self.triangle_first_edge(edge);
+        pending.removed.push(t);
+
+        for e in t..t + 3 {
+            if let Some(cross) = pending.halves.remove(&e) {
+                let key = (self.triangles[cross], self.triangles[self.next_edge(cross)]);
+                pending.lone.insert(key, cross);
             }
         }
 
@@ -201,54 +528,291 @@ impl Half {
                 self.halfedges[h] = OptionIndex::none();
             }
         }
-
-        true
     }
 
-    fn select_candidate(
+    /// Picks the candidate at the start of `frontier` for a cross edge
+    /// ending at `end`, deleting the triangles which fail the empty circle
+    /// test on the way, and returns its point.
+    fn select_candidate<T: Scalar>(
         &mut self,
         side: Side,
-        mut base: usize,
-        end: Point,
-        points: &[Point],
+        frontier: &mut VecDeque<Step>,
+        end: Point<T>,
+        pending: &mut Pending,
+        points: &[Point<T>],
     ) -> Option<usize> {
-        let base_pt = self.point(base, points);
-
         loop {
-            let mut candidates = self.candidates(side, base);
-            let curr = candidates.next()?;
-            let next = candidates.next();
+            let step = *frontier.front()?;
+            let (base, candidate) = self.step_points(side, step);
+            let base_pt = points[self.offset + base];
+            let candidate_pt = points[self.offset + candidate];
 
-            let mut tri = Triangle(self.point(curr, points), base_pt, end);
-
-            if side == Side::Left && tri.is_left_handed()
-                || side == Side::Right && tri.is_right_handed()
-            {
+            if !is_valid(side, candidate_pt, base_pt, end) {
                 // do you love me?
                 return None;
             }
 
+            let edge = match step {
+                Step::Edge(edge) => edge,
+                Step::Lone(..) => return Some(candidate),
+            };
+
+            let base_edge = match side {
+                Side::Left => edge,
+                Side::Right => self.next_edge(edge),
+            };
+
+            let next = self.candidates(side, base_edge).nth(1);
+            let mut tri = Triangle(candidate_pt, base_pt, end);
+
             if !tri.is_right_handed() {
                 std::mem::swap(&mut tri.1, &mut tri.2);
             }
 
-            if let Some(next) = next {
-                if tri.in_circumcircle(self.point(next, points)) {
-                    if !self.delete_triangle(side, curr, &mut base) {
-                        return Some(next);
+            match next {
+                Some(next) if tri.in_circumcircle(self.point(next, points)) => {
+                    self.delete_triangle(side, edge, frontier, pending);
+                }
+                _ => return Some(candidate),
+            }
+        }
+    }
+
+    /// Appends the triangles of `half` and returns the walk around its hull
+    /// starting from its base edge
+    fn append(&mut self, half: Half, side: Side, base: usize) -> VecDeque<Step> {
+        let shift = half.offset - self.offset;
+
+        if half.chain {
+            let len = half.triangles.len();
+
+            // both sides of the edges may face the other half, and going
+            // around a chain is the same in both directions
+            return (base..base + len)
+                .map(|e| {
+                    Step::Lone(
+                        half.triangles[e % len] + shift,
+                        half.triangles[(e + 1) % len] + shift,
+                    )
+                })
+                .collect();
+        }
+
+        let edges = self.triangles.len();
+        if edges == 0 && shift == 0 {
+            // the left half is taken over without copying
+            self.triangles = half.triangles;
+            self.halfedges = half.halfedges;
+        } else {
+            self.triangles
+                .extend(half.triangles.iter().map(|&v| v + shift));
+            self.halfedges
+                .extend(half.halfedges.iter().map(|h| match h.get() {
+                    Some(h) => OptionIndex::some(h + edges),
+                    None => OptionIndex::none(),
+                }));
+        }
+
+        let first = match side {
+            Side::Left => base + edges,
+            Side::Right => self.prev_edge(base + edges),
+        };
+
+        let mut walk = VecDeque::new();
+        let mut edge = first;
+
+        loop {
+            walk.push_back(Step::Edge(edge));
+            edge = self.next_walk_edge(side, edge);
+
+            if edge == first {
+                break walk;
+            }
+        }
+    }
+
+    /// Returns true if the points of two chains lie on a single line
+    fn is_collinear_with<T: Scalar>(&self, other: &Half, points: &[Point<T>]) -> bool {
+        // edge 0 of a chain starts at its last point
+        let (first, last) = (points[self.offset], other.point(0, points));
+
+        Triangle(first, last, self.point(0, points)).robust_orientation() == 0.0
+            && Triangle(first, last, points[other.offset]).robust_orientation() == 0.0
+    }
+
+    /// Drops the deleted triangles, moving the last remaining ones into
+    /// their places.
+    ///
+    /// Only the triangles along the seam of a merge are deleted, and they
+    /// are passed in `removed` so that the work doesn't grow with the size
+    /// of the halves.
+    fn compact(&mut self, removed: &mut [usize]) {
+        removed.sort_unstable();
+
+        let kept = self.triangles.len() - 3 * removed.len();
+        let mut last = self.triangles.len();
+
+        for &hole in removed.iter().take_while(|&&t| t < kept) {
+            // the triangles from `kept` on are either deleted or moved down
+            let t = loop {
+                last -= 3;
+
+                if !self.is_removed(last) {
+                    break last;
+                }
+            };
+
+            for i in 0..3 {
+                self.triangles[hole + i] = self.triangles[t + i];
+                self.halfedges[hole + i] = self.halfedges[t + i];
+
+                if let Some(twin) = self.halfedges[t + i].get() {
+                    self.halfedges[twin] = OptionIndex::some(hole + i);
+                }
+            }
+        }
+
+        self.triangles.truncate(kept);
+        self.halfedges.truncate(kept);
+    }
+
+    /// Merges with the half holding the points right after the ones of
+    /// `self`, zipping cross triangles up from the lower common tangent
+    /// until the upper one is reached.
+    ///
+    /// `side` is the side the merged half takes in the next merge.
+    pub(crate) fn merge<T: Scalar>(self, other: Half, side: Side, points: &[Point<T>]) -> Half {
+        if self.chain && other.chain && self.is_collinear_with(&other, points) {
+            let end = other.offset + other.triangles.len() / 2 + 1;
+            return Half::new_chain(self.offset..end, side, points);
+        }
+
+        let (left_base, right_base) = self.find_base_lr(&other, points);
+
+        let mut merged = Half {
+            triangles: Vec::new(),
+            halfedges: Vec::new(),
+            bottom_most: 0,
+            offset: self.offset,
+            chain: false,
+        };
+
+        let mut left = merged.append(self, Side::Left, left_base);
+        let mut right = merged.append(other, Side::Right, right_base);
+
+        let mut l = merged.step_points(Side::Left, left[0]).0;
+        let mut r = merged.step_points(Side::Right, right[0]).0;
+
+        let mut pending = Pending::default();
+        let mut base = None;
+
+        loop {
+            let (l_pt, r_pt) = (points[merged.offset + l], points[merged.offset + r]);
+
+            let lc = merged.select_candidate(Side::Left, &mut left, r_pt, &mut pending, points);
+            let rc = merged.select_candidate(Side::Right, &mut right, l_pt, &mut pending, points);
+
+            let (winner, c) = match (lc, rc) {
+                (None, None) => break,
+                (Some(lc), None) => (Side::Left, lc),
+                (None, Some(rc)) => (Side::Right, rc),
+                (Some(lc), Some(rc)) => {
+                    let tri = Triangle(l_pt, r_pt, points[merged.offset + lc]);
+
+                    if tri.in_circumcircle(points[merged.offset + rc]) {
+                        (Side::Right, rc)
+                    } else {
+                        (Side::Left, lc)
                     }
+                }
+            };
+
+            // (l, r, c) is above the base edge, so it is right-handed
+            let t = merged.triangles.len();
+            merged.triangles.extend_from_slice(&[l, r, c]);
+            merged
+                .halfedges
+                .extend_from_slice(&[OptionIndex::none(); 3]);
+
+            if let Some(base) = base {
+                merged.link(t, base);
+            }
 
-                    continue;
+            let (frontier, side_edge) = match winner {
+                Side::Left => {
+                    base = Some(t + 1);
+                    l = c;
+                    (&mut left, t + 2)
                 }
+                Side::Right => {
+                    base = Some(t + 2);
+                    r = c;
+                    (&mut right, t + 1)
+                }
+            };
+
+            match frontier.pop_front() {
+                Some(Step::Edge(e)) => {
+                    pending.halves.insert(e, side_edge);
+                }
+                Some(Step::Lone(..)) => {
+                    let key = (
+                        merged.triangles[side_edge],
+                        merged.triangles[merged.next_edge(side_edge)],
+                    );
+
+                    match pending.lone.remove(&(key.1, key.0)) {
+                        Some(twin) => merged.link(side_edge, twin),
+                        None => {
+                            pending.lone.insert(key, side_edge);
+                        }
+                    }
+                }
+                None => unreachable!(),
             }
+        }
 
-            return Some(curr);
+        for (&a, &b) in &pending.halves {
+            merged.link(a, b);
         }
+
+        merged.compact(&mut pending.removed);
+        merged.bottom_most = merged.find_bottom_most(0..merged.triangles.len(), side, points);
+        merged.debug_assert_ccw(points);
+        merged
     }
+}
 
-    pub fn merge(mut self, other: Half, points: &[Point]) -> Half {
-        let base = self.find_base_lr(&other, points);
-        self
+/// One step of the walk along the hull of a half during a merge
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Step {
+    /// Hull edge of a live triangle, walked forwards on the left side and
+    /// backwards on the right one
+    Edge(usize),
+    /// Edge without triangles left, from the first point to the second one
+    Lone(usize, usize),
+}
+
+/// Edges of cross triangles still waiting for their twins during a merge
+#[derive(Debug, Default)]
+struct Pending {
+    /// Keyed by the edge of a half they are linked to at the end, so that
+    /// [`Half::candidates`] never walks into the cross triangles
+    halves: HashMap<usize, usize>,
+    /// Edges lying on lone edges, keyed by their start and end point
+    lone: HashMap<(usize, usize), usize>,
+    /// First edges of the deleted triangles
+    removed: Vec<usize>,
+}
+
+/// Returns true if `candidate` lies above the base edge from `base` to `end`,
+/// candidates on its line would make a flat triangle
+fn is_valid<T: Scalar>(side: Side, candidate: Point<T>, base: Point<T>, end: Point<T>) -> bool {
+    let tri = Triangle(candidate, base, end);
+
+    match side {
+        Side::Left => tri.is_right_handed(),
+        Side::Right => tri.is_left_handed(),
     }
 }
 
@@ -302,7 +866,47 @@ impl<'a> std::iter::FusedIterator for Candidates<'a> {}
 
 #[cfg(test)]
 mod tests {
+    use rand::{Rng, SeedableRng};
+
     use super::*;
+    use crate::Triangulation;
+
+    /// Checks the triangle count against the hull size, the winding and the
+    /// empty circle property of every triangle
+    fn assert_delaunay(half: &Half, points: &[Point]) {
+        let n = points.len();
+        let hull = half.halfedges.iter().filter(|h| h.is_none()).count();
+        assert_eq!(half.triangles.len() / 3, 2 * n - 2 - hull);
+
+        for t in (0..half.triangles.len()).step_by(3) {
+            let tri = Triangle(
+                half.point(t, points),
+                half.point(t + 1, points),
+                half.point(t + 2, points),
+            );
+            assert!(tri.is_right_handed(), "triangle {}", t / 3);
+
+            for (i, &p) in points.iter().enumerate() {
+                assert!(!tri.in_circumcircle(p), "point {} in triangle {}", i, t / 3);
+            }
+        }
+
+        for (e, h) in half.halfedges.iter().enumerate() {
+            if let Some(h) = h.get() {
+                assert_eq!(half.halfedges[h].get(), Some(e));
+                assert_eq!(half.triangles[e], half.triangles[half.next_edge(h)]);
+            } else {
+                let (a, b) = (half.point(e, points), half.point(half.next_edge(e), points));
+                for (i, &p) in points.iter().enumerate() {
+                    assert!(
+                        !Triangle(a, b, p).is_left_handed(),
+                        "point {} outside hull",
+                        i
+                    );
+                }
+            }
+        }
+    }
 
     #[test]
     fn bottom_most() {
@@ -319,6 +923,40 @@ mod tests {
         assert!(points[2].approx_eq(r.point(r.bottom_most, &points)));
     }
 
+    #[test]
+    fn winding_permutations() {
+        let tri = [
+            Point::new(60.0, 40.0),
+            Point::new(80.0, 10.0),
+            Point::new(100.0, 40.0),
+        ];
+        let perms = [
+            [0, 1, 2],
+            [0, 2, 1],
+            [1, 0, 2],
+            [1, 2, 0],
+            [2, 0, 1],
+            [2, 1, 0],
+        ];
+
+        for perm in &perms {
+            let points = perm.iter().map(|&i| tri[i]).collect::<Vec<_>>();
+
+            for &(side, expected) in &[(Side::Left, tri[0]), (Side::Right, tri[2])] {
+                let half = Half::new(0..3, side, &points);
+
+                let t = Triangle(
+                    half.point(0, &points),
+                    half.point(1, &points),
+                    half.point(2, &points),
+                );
+
+                assert!(t.is_right_handed(), "{:?} {:?}", perm, side);
+                assert!(expected.approx_eq(half.point(half.bottom_most, &points)));
+            }
+        }
+    }
+
     #[test]
     fn bottom_most_couple() {
         let points = vec![Point::new(50.0, 50.0), Point::new(100.0, 50.0)];
@@ -372,9 +1010,148 @@ mod tests {
         assert!(p1.approx_eq(points[3]));
     }
 
+    #[test]
+    fn merge() {
+        let points = vec![
+            Point::new(0.0, 100.0),
+            Point::new(20.0, 50.0),
+            Point::new(40.0, 80.0),
+            Point::new(60.0, 40.0),
+            Point::new(80.0, 10.0),
+            Point::new(100.0, 40.0),
+        ];
+
+        let l = Half::new(0..3, Side::Left, &points);
+        let r = Half::new(3..6, Side::Right, &points);
+
+        let merged = l.merge(r, Side::Left, &points);
+        assert_eq!(merged.triangles.len(), 5 * 3);
+        assert_delaunay(&merged, &points);
+    }
+
+    #[test]
+    fn merge_recursive() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(11);
+
+        for &count in &[4, 5, 7, 10, 31, 100, 1000] {
+            for _ in 0..10 {
+                let mut points: Vec<Point> = (0..count)
+                    .map(|_| Point::new(rng.gen_range(0.0, 100.0), rng.gen_range(0.0, 100.0)))
+                    .collect();
+                points.sort_by(|a, b| a.x.partial_cmp(&b.x).unwrap());
+
+                let half = triangulate(0..count, Side::Left, &points);
+                assert_delaunay(&half, &points);
+            }
+        }
+    }
+
+    #[test]
+    fn merge_collinear() {
+        let grid = |w: usize, h: usize| {
+            let mut points = (0..w * h)
+                .map(|i| Point::new((i % w) as f32 * 10.0, (i / w) as f32 * 10.0))
+                .collect::<Vec<_>>();
+            points.sort_by(|a, b| (a.x, a.y).partial_cmp(&(b.x, b.y)).unwrap());
+            points
+        };
+
+        for w in 2..12 {
+            for h in 2..12 {
+                let points = grid(w, h);
+                let half = triangulate(0..points.len(), Side::Left, &points);
+                assert_eq!(half.triangles.len() / 3, 2 * (w - 1) * (h - 1));
+                assert_delaunay(&half, &points);
+            }
+        }
+
+        // long runs of collinear points merged with a single point off them
+        for &count in &[2, 3, 4, 5, 8, 33] {
+            let column = (0..count).map(|i| Point::new(0.0, i as f32 * 10.0));
+            let diagonal = (0..count).map(|i| Point::new(i as f32 * 10.0, i as f32 * 5.0));
+
+            let mut points = column
+                .chain(vec![Point::new(10.0, 5.0)])
+                .collect::<Vec<_>>();
+            let half = triangulate(0..points.len(), Side::Left, &points);
+            assert_delaunay(&half, &points);
+
+            points = diagonal.chain(vec![Point::new(1000.0, 0.0)]).collect();
+            let half = triangulate(0..points.len(), Side::Left, &points);
+            assert_delaunay(&half, &points);
+        }
+    }
+
+    #[test]
+    fn new_divconq() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(16);
+
+        fn edges<T: Scalar>(t: &Delaunay<T>) -> Vec<(usize, usize)> {
+            let mut edges = t
+                .dcel
+                .wireframe_edges()
+                .map(|(a, b, _)| {
+                    (
+                        a.as_usize().min(b.as_usize()),
+                        a.as_usize().max(b.as_usize()),
+                    )
+                })
+                .collect::<Vec<_>>();
+            edges.sort();
+            edges
+        }
+
+        for &count in &[3, 4, 10, 100, 1000] {
+            let mut points: Vec<Point> = (0..count)
+                .map(|_| Point::new(rng.gen_range(0.0, 100.0), rng.gen_range(0.0, 100.0)))
+                .collect();
+            points.push(points[count / 2]);
+
+            let divconq = Delaunay::new_divconq(&points).unwrap();
+            let incremental = Delaunay::new(&points).unwrap();
+            assert_eq!(divconq.dcel.validate(), Ok(()));
+            assert_eq!(edges(&divconq), edges(&incremental));
+
+            // the same cycle, if maybe from another point
+            let (hull, expected) = (divconq.hull(), incremental.hull());
+            let start = expected.iter().position(|&p| p == hull[0]).unwrap();
+            assert_eq!(hull, [&expected[start..], &expected[..start]].concat());
+
+            // the hull is usable for inserting outside of it
+            let (mut divconq, mut incremental) = (divconq, incremental);
+            divconq.insert(Point::new(200.0, 200.0)).unwrap();
+            incremental.insert(Point::new(200.0, 200.0)).unwrap();
+            assert_eq!(edges(&divconq), edges(&incremental));
+        }
+
+        // spacing far below the resolution of `f32` at this offset
+        let points = (0..500)
+            .map(|_| Point::new(1e7 + rng.gen_range(0.0, 1.0), 1e7 + rng.gen_range(0.0, 1.0)))
+            .collect::<Vec<Point<f64>>>();
+        let divconq = Delaunay::new_divconq(&points).unwrap();
+        assert_eq!(divconq.dcel.validate(), Ok(()));
+        assert_eq!(edges(&divconq), edges(&Delaunay::new(&points).unwrap()));
+
+        let points = [Point::new(0.0, 0.0), Point::new(1.0, 0.0)];
+        assert_eq!(
+            Delaunay::new_divconq(&points).err(),
+            Some(DelaunayError::TooFewPoints)
+        );
+
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(f32::NAN, 0.0),
+            Point::new(1.0, 1.0),
+        ];
+        assert_eq!(
+            Delaunay::new_divconq(&points).err(),
+            Some(DelaunayError::InvalidCoordinate)
+        );
+    }
+
     #[test]
     fn candidates() {
-        let _points = vec![
+        let _points = [
             Point::new(0.0, 0.0),
             Point::new(60.0, 0.0),
             Point::new(30.0, 30.0),
@@ -390,6 +1167,7 @@ mod tests {
             halfedges: vec![s(11), s(3), n, s(1), s(6), n, s(4), s(10), n, n, s(7), s(0)],
             offset: 0,
             bottom_most: 10,
+            chain: false,
         };
 
         let mut candidates = half.candidates(Side::Right, 10);
@@ -423,19 +1201,36 @@ mod tests {
             halfedges: vec![s(11), s(3), n, s(1), s(6), n, s(4), s(10), n, n, s(7), s(0)],
             offset: 0,
             bottom_most: 10,
+            chain: false,
         };
 
         {
             let mut half = half.clone();
-            let c = half.select_candidate(Side::Right, 10, Point::new(-30.0, 90.0), &points);
-            let point = half.point(c.unwrap(), &points);
+            let mut frontier = vec![Step::Edge(half.prev_edge(10))].into();
+            let end = Point::new(-30.0, 90.0);
+            let c = half.select_candidate(
+                Side::Right,
+                &mut frontier,
+                end,
+                &mut Pending::default(),
+                &points,
+            );
+            let point = points[c.unwrap()];
             assert!(point.approx_eq(Point::new(30.0, 30.0)));
         }
 
         {
             let mut half = half.clone();
-            let c = half.select_candidate(Side::Right, 10, Point::new(0.0, 90.0), &points);
-            let point = half.point(c.unwrap(), &points);
+            let mut frontier = vec![Step::Edge(half.prev_edge(10))].into();
+            let end = Point::new(0.0, 90.0);
+            let c = half.select_candidate(
+                Side::Right,
+                &mut frontier,
+                end,
+                &mut Pending::default(),
+                &points,
+            );
+            let point = points[c.unwrap()];
             assert!(point.approx_eq(Point::new(60.0, 60.0)));
         }
     }
@@ -0,0 +1,214 @@
+//! Dual graph of the triangulation, where nodes are triangles
+
+use std::collections::VecDeque;
+
+use crate::dcel::{EdgeIndex, TrianglesDCEL};
+use crate::geom::{Point, Scalar};
+use crate::Delaunay;
+
+/// Triangle adjacency graph in compressed sparse row form.
+///
+/// Triangles are identified by their first edge, like everywhere else in the
+/// crate. The neighbors of triangle `t` are
+/// `neighbors[offsets[t / 3]..offsets[t / 3 + 1]]`, and `weights` holds the
+/// length of the shared edge for each of them.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DualGraph<T = f32> {
+    /// Start of the neighbor list of every triangle, plus the total length
+    pub offsets: Vec<usize>,
+
+    /// Neighboring triangles
+    pub neighbors: Vec<EdgeIndex>,
+
+    /// Lengths of the edges shared with the neighbors
+    pub weights: Vec<T>,
+}
+
+impl<T: Scalar> DualGraph<T> {
+    /// Builds the dual graph of the DCEL, with the edge lengths measured in
+    /// `points`
+    pub fn new(dcel: &TrianglesDCEL, points: &[Point<T>]) -> DualGraph<T> {
+        let num_triangles = dcel.num_triangles();
+        let mut offsets = vec![0; num_triangles + 1];
+
+        for (e, twin) in dcel.halfedges.iter().enumerate().take(dcel.vertices.len()) {
+            if twin.is_some() {
+                offsets[e / 3 + 1] += 1;
+            }
+        }
+
+        for t in 0..num_triangles {
+            offsets[t + 1] += offsets[t];
+        }
+
+        let len = offsets[num_triangles];
+        let mut neighbors = vec![EdgeIndex::from(0); len];
+        let mut weights = vec![T::ZERO; len];
+        let mut fill = offsets.clone();
+
+        // every shared edge is taken once, from its smaller half
+        for e in 0..dcel.vertices.len() {
+            let e = EdgeIndex::from(e);
+
+            let twin = match dcel.twin(e) {
+                Some(twin) if twin > e => twin,
+                _ => continue,
+            };
+
+            let a = points[dcel.vertices[e].as_usize()];
+            let b = points[dcel.edge_endpoint(e).as_usize()];
+            let weight = a.distance(b);
+
+            for &(from, to) in &[(e, twin), (twin, e)] {
+                let slot = &mut fill[from.as_usize() / 3];
+                neighbors[*slot] = dcel.triangle_first_edge(to);
+                weights[*slot] = weight;
+                *slot += 1;
+            }
+        }
+
+        DualGraph {
+            offsets,
+            neighbors,
+            weights,
+        }
+    }
+
+    /// Returns the number of triangles
+    pub fn num_nodes(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    /// Returns the neighbors of the triangle with the shared edge lengths
+    pub fn neighbors(&self, t: EdgeIndex) -> impl Iterator<Item = (EdgeIndex, T)> + '_ {
+        let node = t.as_usize() / 3;
+        let range = self.offsets[node]..self.offsets[node + 1];
+
+        self.neighbors[range.clone()]
+            .iter()
+            .cloned()
+            .zip(self.weights[range].iter().cloned())
+    }
+
+    /// Returns the triangles reachable from `start` in breadth-first order
+    pub fn bfs(&self, start: EdgeIndex) -> Vec<EdgeIndex> {
+        let mut visited = vec![false; self.num_nodes()];
+        let mut order = Vec::new();
+        self.bfs_from(start, &mut visited, &mut order);
+        order
+    }
+
+    /// Returns the number of connected components.
+    ///
+    /// It is always 1 for a non-empty Delaunay triangulation, other values
+    /// indicate a broken or clipped mesh.
+    pub fn connected_components(&self) -> usize {
+        let mut visited = vec![false; self.num_nodes()];
+        let mut order = Vec::new();
+        let mut components = 0;
+
+        for node in 0..self.num_nodes() {
+            if !visited[node] {
+                self.bfs_from((3 * node).into(), &mut visited, &mut order);
+                components += 1;
+            }
+        }
+
+        components
+    }
+
+    fn bfs_from(&self, start: EdgeIndex, visited: &mut [bool], order: &mut Vec<EdgeIndex>) {
+        let start = EdgeIndex::from(start.as_usize() / 3 * 3);
+        let mut queue = VecDeque::new();
+
+        visited[start.as_usize() / 3] = true;
+        queue.push_back(start);
+
+        while let Some(t) = queue.pop_front() {
+            order.push(t);
+
+            for (n, _) in self.neighbors(t) {
+                if !visited[n.as_usize() / 3] {
+                    visited[n.as_usize() / 3] = true;
+                    queue.push_back(n);
+                }
+            }
+        }
+    }
+}
+
+impl<T: Scalar> Delaunay<T> {
+    /// Returns the dual graph of the triangulation.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Delaunay, Point};
+    /// let points = &[
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(100.0, 0.0),
+    ///     Point::new(100.0, 100.0),
+    ///     Point::new(0.0, 100.0),
+    /// ];
+    /// let t = Delaunay::new(points).unwrap();
+    /// let graph = t.dual_graph();
+    /// assert_eq!(graph.num_nodes(), 2);
+    /// assert_eq!(graph.connected_components(), 1);
+    /// ```
+    pub fn dual_graph(&self) -> DualGraph<T> {
+        DualGraph::new(&self.dcel, &self.points)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::circular;
+
+    #[test]
+    fn cycle() {
+        let count = 10;
+        let points = circular(count);
+        let t = Delaunay::new(&points).unwrap();
+        let graph = t.dual_graph();
+
+        assert_eq!(graph.num_nodes(), count);
+        assert_eq!(graph.neighbors.len(), 2 * count);
+        assert_eq!(graph.connected_components(), 1);
+        assert_eq!(graph.bfs(0.into()).len(), count);
+
+        for node in 0..count {
+            let t = EdgeIndex::from(3 * node);
+            assert_eq!(graph.neighbors(t).count(), 2);
+
+            for (n, weight) in graph.neighbors(t) {
+                assert!(graph.neighbors(n).any(|(back, _)| back == t));
+                // shared edges are the spokes
+                assert!((weight - 100.0).abs() < 1e-3);
+            }
+        }
+    }
+
+    #[test]
+    fn components() {
+        let points = circular(6);
+        let mut dcel = TrianglesDCEL::with_capacity(2);
+        dcel.add_triangle([0.into(), 1.into(), 2.into()]);
+        dcel.add_triangle([0.into(), 3.into(), 4.into()]);
+
+        let graph = DualGraph::new(&dcel, &points);
+        assert_eq!(graph.connected_components(), 2);
+        assert_eq!(graph.bfs(3.into()), vec![3.into()]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_roundtrip() {
+        let points = circular(6);
+        let t = Delaunay::new(&points).unwrap();
+        let graph = t.dual_graph();
+
+        let json = serde_json::to_string(&graph).unwrap();
+        assert_eq!(serde_json::from_str::<DualGraph>(&json).unwrap(), graph);
+    }
+}
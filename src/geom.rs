@@ -1,16 +1,203 @@
+use core::fmt::{self, Debug, Display};
+use core::hash::{Hash, Hasher};
+use core::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+/// Floating-point type of the coordinates, implemented for `f32` and `f64`.
+///
+/// `f32` is the default everywhere. `f64` keeps the predicates accurate for
+/// coordinates which are large compared to their spacing, like projected
+/// GIS data, see [`Point64`] and [`Triangle64`].
+///
+/// The trait is sealed, it can't be implemented outside of this crate.
+pub trait Scalar:
+    private::Sealed
+    + Copy
+    + Debug
+    + Display
+    + Default
+    + PartialOrd
+    + Send
+    + Sync
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const EPSILON: Self;
+    const INFINITY: Self;
+
+    /// Converts from `f64`, rounding if needed
+    fn from_f64(value: f64) -> Self;
+
+    /// Converts into `f64`, which is exact
+    fn to_f64(self) -> f64;
+
+    fn abs(self) -> Self;
+    fn sqrt(self) -> Self;
+    fn hypot(self, other: Self) -> Self;
+    fn min(self, other: Self) -> Self;
+    fn max(self, other: Self) -> Self;
+    fn is_finite(self) -> bool;
+    fn is_nan(self) -> bool;
+    fn atan2(self, other: Self) -> Self;
+    fn round(self) -> Self;
+
+    /// Total order of the values like `f64::total_cmp`, which puts positive
+    /// NaN after infinity
+    fn total_cmp(&self, other: &Self) -> core::cmp::Ordering {
+        // flipping the magnitude bits of negative values makes the bits
+        // order like the values
+        let key = |value: f64| {
+            let bits = value.to_bits() as i64;
+            bits ^ (((bits >> 63) as u64) >> 1) as i64
+        };
+
+        key(self.to_f64()).cmp(&key(other.to_f64()))
+    }
+}
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for f32 {}
+    impl Sealed for f64 {}
+}
+
+macro_rules! impl_scalar {
+    ($t:ident) => {
+        impl Scalar for $t {
+            const ZERO: $t = 0.0;
+            const ONE: $t = 1.0;
+            const EPSILON: $t = $t::EPSILON;
+            const INFINITY: $t = $t::INFINITY;
+
+            #[inline]
+            fn from_f64(value: f64) -> $t {
+                value as $t
+            }
+
+            #[inline]
+            fn to_f64(self) -> f64 {
+                f64::from(self)
+            }
+
+            #[inline]
+            fn abs(self) -> $t {
+                $t::abs(self)
+            }
+
+            #[inline]
+            fn sqrt(self) -> $t {
+                $t::sqrt(self)
+            }
+
+            #[inline]
+            fn hypot(self, other: $t) -> $t {
+                $t::hypot(self, other)
+            }
+
+            #[inline]
+            fn min(self, other: $t) -> $t {
+                $t::min(self, other)
+            }
+
+            #[inline]
+            fn max(self, other: $t) -> $t {
+                $t::max(self, other)
+            }
+
+            #[inline]
+            fn is_finite(self) -> bool {
+                $t::is_finite(self)
+            }
+
+            #[inline]
+            fn is_nan(self) -> bool {
+                $t::is_nan(self)
+            }
+
+            #[inline]
+            fn atan2(self, other: $t) -> $t {
+                $t::atan2(self, other)
+            }
+
+            #[inline]
+            fn round(self) -> $t {
+                $t::round(self)
+            }
+        }
+    };
+}
+
+impl_scalar!(f32);
+impl_scalar!(f64);
+
 /// 2D point represented by x and y coordinates
+///
+/// Points convert from and into `(x, y)` tuples and `[x, y]` arrays.
+///
+/// # Examples
+/// ```
+/// # use triangulation::Point;
+/// let coords = [(0.0, 0.0), (10.0, 0.0), (0.0, 10.0)];
+/// let points = coords.iter().copied().map(Point::from).collect::<Vec<_>>();
+/// assert_eq!(points[1], Point::new(10.0, 0.0));
+///
+/// let [x, y]: [f32; 2] = points[2].into();
+/// assert_eq!((x, y), (0.0, 10.0));
+/// ```
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
-pub struct Point {
-    pub x: f32,
-    pub y: f32,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Point<T = f32> {
+    pub x: T,
+    pub y: T,
 }
 
-impl Point {
+/// Point with `f64` coordinates
+pub type Point64 = Point<f64>;
+
+impl<T: Scalar> Point<T> {
     /// Creates a new point
-    pub fn new(x: f32, y: f32) -> Point {
+    pub fn new(x: T, y: T) -> Point<T> {
         Point { x, y }
     }
 
+    /// Returns the origin
+    pub fn zero() -> Point<T> {
+        Point::new(T::ZERO, T::ZERO)
+    }
+
+    /// Returns the dot product with `other`, taking both points as vectors
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::Point;
+    /// assert_eq!(Point::new(1.0, 2.0).dot(Point::new(3.0, -4.0)), -5.0);
+    /// ```
+    #[inline]
+    pub fn dot(self, other: Point<T>) -> T {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// Returns the z component of the cross product with `other`, taking both
+    /// points as vectors in the plane. It is positive if `other` points to
+    /// the left of `self`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::Point;
+    /// let (x, y) = (Point::new(1.0, 0.0), Point::new(0.0, 1.0));
+    /// assert_eq!(x.cross(y), 1.0);
+    /// assert_eq!(y.cross(x), -1.0);
+    /// ```
+    #[inline]
+    pub fn cross(self, other: Point<T>) -> T {
+        self.x * other.y - self.y * other.x
+    }
+
     /// Returns square of the distance between `self` and `other` point
     ///
     /// # Examples
@@ -21,10 +208,67 @@ impl Point {
     /// assert!((a.distance_sq(b) - 10000.0) < 1e-6);
     /// ```
     #[inline]
-    pub fn distance_sq(self, other: Point) -> f32 {
-        let dx = self.x - other.x;
-        let dy = self.y - other.y;
-        dx * dx + dy * dy
+    pub fn distance_sq(self, other: Point<T>) -> T {
+        (self - other).length_sq()
+    }
+
+    /// Returns the distance between `self` and `other` point
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::Point;
+    /// let a = Point::new(10.0, 10.0);
+    /// let b = Point::new(13.0, 14.0);
+    /// assert_eq!(a.distance(b), 5.0);
+    /// ```
+    #[inline]
+    pub fn distance(self, other: Point<T>) -> T {
+        self.distance_sq(other).sqrt()
+    }
+
+    /// Returns the distance from the origin, the length of the point taken
+    /// as a vector
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::Point;
+    /// assert_eq!(Point::new(-3.0, 4.0).length(), 5.0);
+    /// ```
+    #[inline]
+    pub fn length(self) -> T {
+        self.x.hypot(self.y)
+    }
+
+    /// Returns the square of the [`length`](Point::length)
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::Point;
+    /// assert_eq!(Point::new(-3.0, 4.0).length_sq(), 25.0);
+    /// ```
+    #[inline]
+    pub fn length_sq(self) -> T {
+        self.dot(self)
+    }
+
+    /// Returns the vector of unit length pointing the same way, the zero
+    /// vector has no direction and is returned unchanged
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::Point;
+    /// assert_eq!(Point::new(-3.0, 4.0).normalize(), Point::new(-0.6, 0.8));
+    /// assert_eq!(Point::new(0.0, 0.0).normalize(), Point::new(0.0, 0.0));
+    /// ```
+    #[inline]
+    pub fn normalize(self) -> Point<T> {
+        let length = self.length();
+
+        if length == T::ZERO {
+            return self;
+        }
+
+        self / length
     }
 
     /// Returns true if points are approximately equal
@@ -33,53 +277,313 @@ impl Point {
     ///
     /// ```
     /// # use triangulation::Point;
-    /// let a = Point::new(10.0, 10.0);
+    /// let a = Point::new(10.0f32, 10.0);
     /// let b = Point::new(10.0, 10.0000001);
     /// assert!(a.approx_eq(b))
     /// ```
     #[inline]
-    pub fn approx_eq(self, other: Point) -> bool {
+    pub fn approx_eq(self, other: Point<T>) -> bool {
+        self.approx_eq_eps(other, T::EPSILON)
+    }
+
+    /// Returns true if both coordinates differ by at most `epsilon`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use triangulation::Point;
+    /// let a = Point::new(3840.0, 2160.0);
+    /// let b = Point::new(3840.25, 2159.75);
+    /// assert!(!a.approx_eq(b));
+    /// assert!(a.approx_eq_eps(b, 0.5));
+    /// assert!(!a.approx_eq_eps(b, 0.1));
+    /// ```
+    #[inline]
+    pub fn approx_eq_eps(self, other: Point<T>, epsilon: T) -> bool {
         let dx = self.x - other.x;
         let dy = self.y - other.y;
-        dx.abs() <= std::f32::EPSILON && dy.abs() <= std::f32::EPSILON
+        dx.abs() <= epsilon && dy.abs() <= epsilon
+    }
+
+    /// Converts the coordinates into another scalar type.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::Point;
+    /// let p = Point::new(0.5f64, 10_000_000.25);
+    /// assert_eq!(p.cast::<f32>(), Point::new(0.5f32, 10_000_000.0));
+    /// ```
+    #[inline]
+    pub fn cast<U: Scalar>(self) -> Point<U> {
+        Point::new(U::from_f64(self.x.to_f64()), U::from_f64(self.y.to_f64()))
+    }
+
+    /// Returns the point at `t` along the segment from `self` to `other`,
+    /// extrapolating for `t` outside of `0..=1`
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::Point;
+    /// let a = Point::new(0.0, 10.0);
+    /// let b = Point::new(4.0, 2.0);
+    /// assert_eq!(a.lerp(b, 0.25), Point::new(1.0, 8.0));
+    /// ```
+    #[inline]
+    pub fn lerp(self, other: Point<T>, t: T) -> Point<T> {
+        self + (other - self) * t
     }
 }
 
-impl Into<(i32, i32)> for Point {
-    fn into(self) -> (i32, i32) {
-        (self.x as i32, self.y as i32)
+impl<T: Scalar> Add for Point<T> {
+    type Output = Point<T>;
+
+    #[inline]
+    fn add(self, other: Point<T>) -> Point<T> {
+        Point::new(self.x + other.x, self.y + other.y)
     }
 }
 
-impl Into<(f32, f32)> for Point {
-    fn into(self) -> (f32, f32) {
-        (self.x, self.y)
+impl<T: Scalar> Sub for Point<T> {
+    type Output = Point<T>;
+
+    #[inline]
+    fn sub(self, other: Point<T>) -> Point<T> {
+        Point::new(self.x - other.x, self.y - other.y)
     }
 }
 
-/// A triangle made of 3 points.
+impl<T: Scalar> Neg for Point<T> {
+    type Output = Point<T>;
+
+    #[inline]
+    fn neg(self) -> Point<T> {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl<T: Scalar> Mul<T> for Point<T> {
+    type Output = Point<T>;
+
+    #[inline]
+    fn mul(self, factor: T) -> Point<T> {
+        Point::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl<T: Scalar> Div<T> for Point<T> {
+    type Output = Point<T>;
+
+    #[inline]
+    fn div(self, divisor: T) -> Point<T> {
+        Point::new(self.x / divisor, self.y / divisor)
+    }
+}
+
+impl<T: Scalar> AddAssign for Point<T> {
+    #[inline]
+    fn add_assign(&mut self, other: Point<T>) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Scalar> SubAssign for Point<T> {
+    #[inline]
+    fn sub_assign(&mut self, other: Point<T>) {
+        *self = *self - other;
+    }
+}
+
+impl From<Point> for (i32, i32) {
+    fn from(point: Point) -> (i32, i32) {
+        (point.x as i32, point.y as i32)
+    }
+}
+
+impl<T> From<Point<T>> for (T, T) {
+    fn from(point: Point<T>) -> (T, T) {
+        (point.x, point.y)
+    }
+}
+
+impl<T> From<Point<T>> for [T; 2] {
+    fn from(point: Point<T>) -> [T; 2] {
+        [point.x, point.y]
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Point<T> {
+        Point { x, y }
+    }
+}
+
+impl<T> From<[T; 2]> for Point<T> {
+    fn from([x, y]: [T; 2]) -> Point<T> {
+        Point { x, y }
+    }
+}
+
+/// Points are compared by their coordinates, which is an equivalence as long
+/// as none of them is NaN. Debug builds panic when hashing such a point.
+impl<T: Scalar> Eq for Point<T> {}
+
+/// Hashes the bits of the coordinates, with `-0.0` hashed like `0.0` as they
+/// compare equal
+impl<T: Scalar> Hash for Point<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let (x, y) = (self.x.to_f64(), self.y.to_f64());
+
+        #[cfg(debug_assertions)]
+        {
+            if x.is_nan() || y.is_nan() {
+                panic!("hashing a point with a NaN coordinate: {:?}", self);
+            }
+        }
+
+        (x + 0.0).to_bits().hash(state);
+        (y + 0.0).to_bits().hash(state);
+    }
+}
+
+/// Formats the point as `(x, y)`, passing the format options on to both
+/// coordinates, so `{:.2}` prints them with two decimals
+impl<T: fmt::Display> fmt::Display for Point<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "(")?;
+        self.x.fmt(f)?;
+        write!(f, ", ")?;
+        self.y.fmt(f)?;
+        write!(f, ")")
+    }
+}
+
+/// Axis-aligned rectangle
 #[derive(Clone, Copy, Debug, PartialEq)]
-pub struct Triangle(pub Point, pub Point, pub Point);
+pub struct Rect<T = f32> {
+    pub min: Point<T>,
+    pub max: Point<T>,
+}
+
+impl<T: Scalar> Rect<T> {
+    /// Creates a new rectangle from its minimum and maximum corners
+    pub fn new(min: Point<T>, max: Point<T>) -> Rect<T> {
+        Rect { min, max }
+    }
+
+    /// Returns the bounding box of the given points, or `None` if there are no points.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Point, Rect};
+    /// let points = [Point::new(10.0, 50.0), Point::new(30.0, 20.0)];
+    /// let rect = Rect::from_points(&points).unwrap();
+    /// assert_eq!(rect, Rect::new(Point::new(10.0, 20.0), Point::new(30.0, 50.0)));
+    /// ```
+    pub fn from_points(points: &[Point<T>]) -> Option<Rect<T>> {
+        let first = *points.first()?;
+
+        Some(points.iter().fold(Rect::new(first, first), |rect, p| {
+            Rect::new(
+                Point::new(rect.min.x.min(p.x), rect.min.y.min(p.y)),
+                Point::new(rect.max.x.max(p.x), rect.max.y.max(p.y)),
+            )
+        }))
+    }
 
-impl Triangle {
+    /// Returns the width of the rectangle
     #[inline]
-    fn circumcircle_delta(self) -> (f32, f32) {
-        let p = Point {
-            x: self.1.x - self.0.x,
-            y: self.1.y - self.0.y,
-        };
+    pub fn width(self) -> T {
+        self.max.x - self.min.x
+    }
+
+    /// Returns the height of the rectangle
+    #[inline]
+    pub fn height(self) -> T {
+        self.max.y - self.min.y
+    }
+}
+
+/// Uniform scaling and translation mapping a bounding box into the unit square.
+///
+/// The scale is the same along both axes, so the Delaunay property is
+/// preserved.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Normalization<T = f32> {
+    pub offset: Point<T>,
+    pub scale: T,
+}
 
-        let q = Point {
-            x: self.2.x - self.0.x,
-            y: self.2.y - self.0.y,
+impl<T: Scalar> Normalization<T> {
+    /// Returns the transform mapping `rect` into `[0; 1]²`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Normalization, Point, Rect};
+    /// let rect = Rect::new(Point::new(1000.0, 2000.0), Point::new(1100.0, 2050.0));
+    /// let n = Normalization::from_rect(rect);
+    /// assert_eq!(n.apply(Point::new(1100.0, 2050.0)), Point::new(1.0, 0.5));
+    /// assert_eq!(n.invert(Point::new(1.0, 0.5)), Point::new(1100.0, 2050.0));
+    /// ```
+    pub fn from_rect(rect: Rect<T>) -> Normalization<T> {
+        let extent = rect.width().max(rect.height());
+        let scale = if extent > T::ZERO {
+            T::ONE / extent
+        } else {
+            T::ONE
         };
 
-        let p2 = p.x * p.x + p.y * p.y;
-        let q2 = q.x * q.x + q.y * q.y;
-        let d = 2.0 * (p.x * q.y - p.y * q.x);
+        Normalization {
+            offset: rect.min,
+            scale,
+        }
+    }
+
+    /// Maps a point from the input space into the unit square
+    #[inline]
+    pub fn apply(self, point: Point<T>) -> Point<T> {
+        Point::new(
+            (point.x - self.offset.x) * self.scale,
+            (point.y - self.offset.y) * self.scale,
+        )
+    }
+
+    /// Maps a point from the unit square back into the input space
+    #[inline]
+    pub fn invert(self, point: Point<T>) -> Point<T> {
+        Point::new(
+            point.x / self.scale + self.offset.x,
+            point.y / self.scale + self.offset.y,
+        )
+    }
+}
+
+/// Circle circumscribed around a triangle
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Circumcircle<T = f32> {
+    pub center: Point<T>,
+    pub radius_sq: T,
+}
+
+/// A triangle made of 3 points.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Triangle<T = f32>(pub Point<T>, pub Point<T>, pub Point<T>);
+
+/// Triangle with `f64` coordinates, created with the [`Triangle`] constructor
+pub type Triangle64 = Triangle<f64>;
+
+impl<T: Scalar> Triangle<T> {
+    #[inline]
+    fn circumcircle_delta(self) -> (T, T) {
+        let p = self.1 - self.0;
+        let q = self.2 - self.0;
 
-        if d == 0.0 {
-            return (std::f32::INFINITY, std::f32::INFINITY);
+        let p2 = p.length_sq();
+        let q2 = q.length_sq();
+        let d = T::from_f64(2.0) * p.cross(q);
+
+        if d == T::ZERO {
+            return (T::INFINITY, T::INFINITY);
         }
 
         let dx = (q.y * p2 - p.y * q2) / d;
@@ -101,7 +605,7 @@ impl Triangle {
     /// assert!((t.circumradius_sq() - 5000.0) < 1e-6);
     /// ```
     #[inline]
-    pub fn circumradius_sq(self) -> f32 {
+    pub fn circumradius_sq(self) -> T {
         let (x, y) = self.circumcircle_delta();
         x * x + y * y
     }
@@ -119,7 +623,7 @@ impl Triangle {
     /// assert!(t.circumcenter().approx_eq(Point::new(60.0, 60.0)));
     /// ```
     #[inline]
-    pub fn circumcenter(self) -> Point {
+    pub fn circumcenter(self) -> Point<T> {
         let (x, y) = self.circumcircle_delta();
 
         Point {
@@ -141,25 +645,82 @@ impl Triangle {
     /// );
     /// assert!(t.orientation() > 0.0);
     /// ```
+    ///
+    /// With the `robust` feature it is computed by
+    /// [`robust_orientation`](Triangle::robust_orientation), so the sign is
+    /// correct even for nearly collinear points.
     #[inline]
-    pub fn orientation(self) -> f32 {
-        let v21x = self.0.x - self.1.x;
-        let v21y = self.0.y - self.1.y;
-        let v23x = self.2.x - self.1.x;
-        let v23y = self.2.y - self.1.y;
-        v21x * v23y - v21y * v23x
+    pub fn orientation(self) -> T {
+        #[cfg(feature = "robust")]
+        {
+            T::from_f64(self.robust_orientation())
+        }
+
+        #[cfg(not(feature = "robust"))]
+        {
+            (self.0 - self.1).cross(self.2 - self.1)
+        }
+    }
+
+    /// Returns the orientation like [`orientation`](Triangle::orientation),
+    /// but with the sign computed exactly.
+    ///
+    /// The determinant is expanded into six products of coordinates. If
+    /// their plain sum is too close to zero to trust, it is recomputed as an
+    /// exact floating-point expansion of the products and their rounding
+    /// errors, which are zero for `f32` coordinates.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Triangle, Point};
+    /// let t = Triangle(
+    ///     Point::new(0.0f32, 0.0),
+    ///     Point::new(0.3, 0.1),
+    ///     Point::new(0.6, 0.2)
+    /// );
+    /// assert_eq!(t.robust_orientation(), 0.0);
+    /// ```
+    pub fn robust_orientation(self) -> f64 {
+        let (ax, ay) = (self.0.x.to_f64(), self.0.y.to_f64());
+        let (bx, by) = (self.1.x.to_f64(), self.1.y.to_f64());
+        let (cx, cy) = (self.2.x.to_f64(), self.2.y.to_f64());
+
+        let factors = [
+            (ax, cy),
+            (-ax, by),
+            (-bx, cy),
+            (-ay, cx),
+            (ay, bx),
+            (by, cx),
+        ];
+        let products = factors.map(|(a, b)| a * b);
+
+        let sum: f64 = products.iter().sum();
+        let magnitude: f64 = products.iter().map(|t| t.abs()).sum();
+
+        if sum.abs() > 8.0 * f64::EPSILON * magnitude {
+            return sum;
+        }
+
+        let mut terms = [0.0; 12];
+        for (i, (&(a, b), &product)) in factors.iter().zip(&products).enumerate() {
+            terms[2 * i] = product;
+            terms[2 * i + 1] = a.mul_add(b, -product);
+        }
+
+        exact_sum(terms)
     }
 
     /// Returns true if the triangle is right-handed (conter-clockwise order).
     #[inline]
     pub fn is_right_handed(self) -> bool {
-        self.orientation() > 0.0
+        self.orientation() > T::ZERO
     }
 
     /// Returns true if the triangle is left-handed (clockwise order).
     #[inline]
     pub fn is_left_handed(self) -> bool {
-        self.orientation() < 0.0
+        self.orientation() < T::ZERO
     }
 
     /// Returns true if the given point lies inside the circumcircle of the triangle.
@@ -176,38 +737,1652 @@ impl Triangle {
     /// assert!(t.in_circumcircle(Point::new(30.0, 30.0)));
     /// assert!(!t.in_circumcircle(Point::new(5.0, 5.0)));
     /// ```
+    ///
+    /// With the `robust` feature the test is exact, so nearly cocircular
+    /// points can't make neighboring triangles disagree.
     #[inline]
-    pub fn in_circumcircle(self, point: Point) -> bool {
-        let dx = self.0.x - point.x;
-        let dy = self.0.y - point.y;
-        let ex = self.1.x - point.x;
-        let ey = self.1.y - point.y;
-        let fx = self.2.x - point.x;
-        let fy = self.2.y - point.y;
+    pub fn in_circumcircle(self, point: Point<T>) -> bool {
+        #[cfg(feature = "robust")]
+        {
+            let (a, b, c) = (coord(self.0), coord(self.1), coord(self.2));
+            robust::incircle(a, b, c, coord(point)) < 0.0
+        }
 
-        let ap = dx * dx + dy * dy;
-        let bp = ex * ex + ey * ey;
-        let cp = fx * fx + fy * fy;
+        #[cfg(not(feature = "robust"))]
+        {
+            let d = self.0 - point;
+            let e = self.1 - point;
+            let f = self.2 - point;
 
-        dx * (ey * cp - bp * fy) - dy * (ex * cp - bp * fx) + ap * (ex * fy - ey * fx) < 0.0
+            let ap = d.length_sq();
+            let bp = e.length_sq();
+            let cp = f.length_sq();
+
+            d.x * (e.y * cp - bp * f.y) - d.y * (e.x * cp - bp * f.x) + ap * e.cross(f) < T::ZERO
+        }
     }
 }
 
-/// Monotonically increases with the real angle, returns vales in range [0; 1]
-///
-/// # Examples
-/// ```
-/// # use triangulation::geom::pseudo_angle;
-/// let a = pseudo_angle(1.0, 1.0);  // 45 degrees
-/// let b = pseudo_angle(2.0, 1.0);  // 26 degrees
-/// assert!(a > b);
-/// ```
-pub fn pseudo_angle(dx: f32, dy: f32) -> f32 {
-    let p = dx / (dx.abs() + dy.abs());
+/// Formats the vertices in order as `(x, y) -> (x, y) -> (x, y)`, passing the
+/// format options on to the points
+impl<T: fmt::Display> fmt::Display for Triangle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)?;
+        write!(f, " -> ")?;
+        self.1.fmt(f)?;
+        write!(f, " -> ")?;
+        self.2.fmt(f)
+    }
+}
 
-    if dy > 0.0 {
-        (3.0 - p) / 4.0
-    } else {
-        (1.0 + p) / 4.0
+/// Converts a point for the exact predicates of the `robust` crate
+#[cfg(feature = "robust")]
+#[inline]
+fn coord<T: Scalar>(p: Point<T>) -> robust::Coord<f64> {
+    robust::Coord {
+        x: p.x.to_f64(),
+        y: p.y.to_f64(),
+    }
+}
+
+impl<T: Scalar> Triangle<T> {
+    /// Returns the circumcircle, computing its center and radius at once.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Triangle, Point};
+    /// let t = Triangle(
+    ///     Point::new(10.0, 10.0),
+    ///     Point::new(10.0, 110.0),
+    ///     Point::new(110.0, 10.0)
+    /// );
+    /// let circle = t.circumcircle();
+    /// assert!(circle.center.approx_eq(Point::new(60.0, 60.0)));
+    /// assert!((circle.radius_sq - 5000.0) < 1e-6);
+    /// ```
+    #[inline]
+    pub fn circumcircle(self) -> Circumcircle<T> {
+        let (x, y) = self.circumcircle_delta();
+
+        Circumcircle {
+            center: Point::new(x + self.0.x, y + self.0.y),
+            radius_sq: x * x + y * y,
+        }
+    }
+}
+
+impl<T: Scalar> Triangle<T> {
+    /// Returns the axis-aligned bounding box.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Triangle, Point, Rect};
+    /// let t = Triangle(
+    ///     Point::new(10.0, 50.0),
+    ///     Point::new(30.0, 20.0),
+    ///     Point::new(0.0, 40.0)
+    /// );
+    /// assert_eq!(t.bounding_box(), Rect::new(Point::new(0.0, 20.0), Point::new(30.0, 50.0)));
+    /// ```
+    #[inline]
+    pub fn bounding_box(self) -> Rect<T> {
+        Rect::new(
+            Point::new(
+                self.0.x.min(self.1.x).min(self.2.x),
+                self.0.y.min(self.1.y).min(self.2.y),
+            ),
+            Point::new(
+                self.0.x.max(self.1.x).max(self.2.x),
+                self.0.y.max(self.1.y).max(self.2.y),
+            ),
+        )
+    }
+
+    /// Returns the centroid, the mean of the three vertices.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Triangle, Point};
+    /// let t: Triangle = Triangle(
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(1.0, 0.0),
+    ///     Point::new(0.5, 0.866)
+    /// );
+    /// let c = t.centroid();
+    /// assert!((c.x - 0.5).abs() < 1e-3 && (c.y - 0.289).abs() < 1e-3);
+    /// ```
+    #[inline]
+    pub fn centroid(self) -> Point<T> {
+        Point::new(
+            (self.0.x + self.1.x + self.2.x) / T::from_f64(3.0),
+            (self.0.y + self.1.y + self.2.y) / T::from_f64(3.0),
+        )
+    }
+
+    /// Returns the midpoints of the sides opposite to each vertex, that is of
+    /// the sides 1--2, 2--0 and 0--1.
+    ///
+    /// For a triangle from the DCEL, the midpoint of the edge starting at
+    /// vertex `i` is at `(i + 2) % 3`, which is the point to pass to
+    /// [`TrianglesDCEL::split_edge`](crate::TrianglesDCEL::split_edge) when
+    /// bisecting that edge.
+    #[inline]
+    pub fn midpoints(self) -> [Point<T>; 3] {
+        let half = T::from_f64(0.5);
+
+        [
+            self.1.lerp(self.2, half),
+            self.0.lerp(self.2, half),
+            self.0.lerp(self.1, half),
+        ]
+    }
+
+    /// Splits the triangle at the midpoints of its sides into four similar
+    /// triangles with the same winding: the center one, then the corners at
+    /// vertices 0, 1 and 2.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Triangle, Point};
+    /// let t = Triangle(
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(0.0, 4.0),
+    ///     Point::new(4.0, 0.0)
+    /// );
+    /// let [center, a, b, c] = t.subdivide_4();
+    /// assert_eq!(center.area(), 2.0);
+    /// assert_eq!(a.0, t.0);
+    /// assert!(b.is_right_handed() && c.is_right_handed());
+    /// ```
+    #[inline]
+    pub fn subdivide_4(self) -> [Triangle<T>; 4] {
+        let [m0, m1, m2] = self.midpoints();
+
+        [
+            Triangle(m0, m1, m2),
+            Triangle(self.0, m2, m1),
+            Triangle(m2, self.1, m0),
+            Triangle(m1, m0, self.2),
+        ]
+    }
+
+    /// Returns the lengths of the sides 0--1, 1--2 and 2--0, in the order of
+    /// the edges of a triangle in the DCEL.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Triangle, Point};
+    /// let t = Triangle(
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(4.0, 0.0),
+    ///     Point::new(0.0, 3.0)
+    /// );
+    /// assert_eq!(t.side_lengths(), [4.0, 5.0, 3.0]);
+    /// ```
+    #[inline]
+    pub fn side_lengths(self) -> [T; 3] {
+        [
+            self.0.distance(self.1),
+            self.1.distance(self.2),
+            self.2.distance(self.0),
+        ]
+    }
+
+    /// Returns the index of the vertex opposite to the longest side, so 0
+    /// stands for the side 1--2. Of sides with the same length the first one
+    /// is picked.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Triangle, Point};
+    /// let t = Triangle(
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(4.0, 0.0),
+    ///     Point::new(0.0, 3.0)
+    /// );
+    /// assert_eq!(t.longest_edge_index(), 0);
+    /// assert_eq!(t.shortest_edge_index(), 1);
+    /// ```
+    #[inline]
+    pub fn longest_edge_index(self) -> usize {
+        let sides = self.opposite_sides();
+        (1..3).fold(0, |best, i| if sides[i] > sides[best] { i } else { best })
+    }
+
+    /// Returns the index of the vertex opposite to the shortest side, so 0
+    /// stands for the side 1--2. Of sides with the same length the first one
+    /// is picked.
+    #[inline]
+    pub fn shortest_edge_index(self) -> usize {
+        let sides = self.opposite_sides();
+        (1..3).fold(0, |best, i| if sides[i] < sides[best] { i } else { best })
+    }
+
+    /// Returns the endpoints of the longest side, in the order of the
+    /// vertices.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Triangle, Point};
+    /// let (a, b, c) = (Point::new(0.0, 0.0), Point::new(4.0, 0.0), Point::new(0.0, 3.0));
+    /// let t = Triangle(a, b, c);
+    ///
+    /// assert_eq!(t.longest_edge_endpoints(), (b, c));
+    /// assert_eq!(t.shortest_edge_endpoints(), (c, a));
+    /// ```
+    #[inline]
+    pub fn longest_edge_endpoints(self) -> (Point<T>, Point<T>) {
+        self.opposite_endpoints(self.longest_edge_index())
+    }
+
+    /// Returns the endpoints of the shortest side, in the order of the
+    /// vertices.
+    #[inline]
+    pub fn shortest_edge_endpoints(self) -> (Point<T>, Point<T>) {
+        self.opposite_endpoints(self.shortest_edge_index())
+    }
+
+    /// Returns the lengths of the sides opposite to each vertex
+    #[inline]
+    fn opposite_sides(self) -> [T; 3] {
+        let [c, a, b] = self.side_lengths();
+        [a, b, c]
+    }
+
+    /// Returns the endpoints of the side opposite to the vertex `i`
+    #[inline]
+    fn opposite_endpoints(self, i: usize) -> (Point<T>, Point<T>) {
+        let vertices = [self.0, self.1, self.2];
+        (vertices[(i + 1) % 3], vertices[(i + 2) % 3])
+    }
+
+    /// Returns the sum of the side lengths.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Triangle, Point};
+    /// let t = Triangle(
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(4.0, 0.0),
+    ///     Point::new(0.0, 3.0)
+    /// );
+    /// assert_eq!(t.perimeter(), 12.0);
+    /// ```
+    #[inline]
+    pub fn perimeter(self) -> T {
+        let [a, b, c] = self.side_lengths();
+        a + b + c
+    }
+
+    /// Returns the radius of the inscribed circle.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Triangle, Point};
+    /// let t: Triangle = Triangle(
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(0.0, 3.0),
+    ///     Point::new(4.0, 0.0)
+    /// );
+    /// assert!((t.inradius() - 1.0).abs() < 1e-6);
+    /// ```
+    #[inline]
+    pub fn inradius(self) -> T {
+        self.area() / (self.perimeter() / T::from_f64(2.0))
+    }
+
+    /// Returns the center of the inscribed circle, the average of the
+    /// vertices weighted by the lengths of the opposite sides.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Triangle, Point};
+    /// let t: Triangle = Triangle(
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(0.0, 3.0),
+    ///     Point::new(4.0, 0.0)
+    /// );
+    /// let c = t.incenter();
+    /// assert!((c.x - 1.0).abs() < 1e-6 && (c.y - 1.0).abs() < 1e-6);
+    /// ```
+    #[inline]
+    pub fn incenter(self) -> Point<T> {
+        // sides opposite to the vertices
+        let [c, a, b] = self.side_lengths();
+        let perimeter = a + b + c;
+
+        Point::new(
+            (a * self.0.x + b * self.1.x + c * self.2.x) / perimeter,
+            (a * self.0.y + b * self.1.y + c * self.2.y) / perimeter,
+        )
+    }
+
+    /// Returns the ratio of the circumradius to twice the inradius, which is
+    /// 1 for the equilateral triangle and grows as the triangle degenerates.
+    ///
+    /// Returns infinity for triangles with zero area.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Triangle, Point};
+    /// let t: Triangle = Triangle(
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(0.0, 3.0),
+    ///     Point::new(4.0, 0.0)
+    /// );
+    /// assert!((t.aspect_ratio() - 1.25).abs() < 1e-5);
+    /// ```
+    #[inline]
+    pub fn aspect_ratio(self) -> T {
+        let inradius = self.inradius();
+
+        if inradius.is_nan() || inradius <= T::ZERO {
+            return T::INFINITY;
+        }
+
+        self.circumradius_sq().sqrt() / (T::from_f64(2.0) * inradius)
+    }
+
+    /// Returns the interior angles at the three vertices in radians
+    #[inline]
+    fn angles(self) -> [T; 3] {
+        let angle = |a: Point<T>, b: Point<T>, c: Point<T>| {
+            let (ux, uy) = (b.x - a.x, b.y - a.y);
+            let (vx, vy) = (c.x - a.x, c.y - a.y);
+            (ux * vy - uy * vx).abs().atan2(ux * vx + uy * vy)
+        };
+
+        [
+            angle(self.0, self.1, self.2),
+            angle(self.1, self.2, self.0),
+            angle(self.2, self.0, self.1),
+        ]
+    }
+
+    /// Returns the smallest interior angle in radians.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Triangle, Point};
+    /// use std::f32::consts::PI;
+    ///
+    /// let t: Triangle = Triangle(
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(1.0, 0.0),
+    ///     Point::new(0.5, 3.0_f32.sqrt() / 2.0)
+    /// );
+    /// assert!((t.min_angle() - PI / 3.0).abs() < 1e-5);
+    /// assert!((t.max_angle() - PI / 3.0).abs() < 1e-5);
+    /// ```
+    #[inline]
+    pub fn min_angle(self) -> T {
+        let [a, b, c] = self.angles();
+        a.min(b).min(c)
+    }
+
+    /// Returns the largest interior angle in radians.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Triangle, Point};
+    /// use std::f32::consts::PI;
+    ///
+    /// let t = Triangle(
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(0.0, 3.0),
+    ///     Point::new(4.0, 0.0)
+    /// );
+    /// assert!((t.max_angle() - PI / 2.0).abs() < 1e-6);
+    /// ```
+    #[inline]
+    pub fn max_angle(self) -> T {
+        let [a, b, c] = self.angles();
+        a.max(b).max(c)
+    }
+
+    /// Returns the dot products of the two sides meeting at each vertex,
+    /// their signs tell whether the angles are acute, right or obtuse
+    #[inline]
+    fn vertex_dots(self) -> [T; 3] {
+        let dot = |a: Point<T>, b: Point<T>, c: Point<T>| {
+            (b.x - a.x) * (c.x - a.x) + (b.y - a.y) * (c.y - a.y)
+        };
+
+        [
+            dot(self.0, self.1, self.2),
+            dot(self.1, self.2, self.0),
+            dot(self.2, self.0, self.1),
+        ]
+    }
+
+    /// Returns true if all angles are smaller than a right angle, which is
+    /// when the circumcenter lies inside of the triangle.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Triangle, Point};
+    /// let t = Triangle(Point::new(0.0, 0.0), Point::new(4.0, 0.0), Point::new(2.0, 3.0));
+    /// assert!(t.is_acute());
+    /// assert!(!t.is_right() && !t.is_obtuse());
+    /// ```
+    #[inline]
+    pub fn is_acute(self) -> bool {
+        self.vertex_dots().iter().all(|&d| d > T::ZERO)
+    }
+
+    /// Returns true if one angle is larger than a right angle, which is when
+    /// the circumcenter lies outside of the triangle.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Triangle, Point};
+    /// let t = Triangle(Point::new(0.0, 0.0), Point::new(4.0, 0.0), Point::new(-1.0, 3.0));
+    /// assert!(t.is_obtuse());
+    /// ```
+    #[inline]
+    pub fn is_obtuse(self) -> bool {
+        self.vertex_dots().iter().any(|&d| d < T::ZERO)
+    }
+
+    /// Returns true if one angle is exactly a right angle, which puts the
+    /// circumcenter in the middle of the opposite side.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Triangle, Point};
+    /// let t = Triangle(Point::new(0.0, 0.0), Point::new(4.0, 0.0), Point::new(0.0, 3.0));
+    /// assert!(t.is_right());
+    /// assert!(!t.is_acute() && !t.is_obtuse());
+    /// ```
+    #[inline]
+    pub fn is_right(self) -> bool {
+        let dots = self.vertex_dots();
+        dots.contains(&T::ZERO) && dots.iter().all(|&d| d >= T::ZERO)
+    }
+
+    /// Returns the signed area, half of the [`orientation`](Triangle::orientation).
+    ///
+    /// It is positive for right-handed (counter-clockwise) triangles and
+    /// negative for left-handed ones.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Triangle, Point};
+    /// let (a, b, c) = (Point::new(0.0, 0.0), Point::new(0.0, 4.0), Point::new(3.0, 0.0));
+    ///
+    /// assert!(Triangle(a, b, c).is_right_handed());
+    /// assert_eq!(Triangle(a, b, c).signed_area(), 6.0);
+    /// assert_eq!(Triangle(a, c, b).signed_area(), -6.0);
+    /// ```
+    #[inline]
+    pub fn signed_area(self) -> T {
+        self.orientation() * T::from_f64(0.5)
+    }
+
+    /// Returns the area.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Triangle, Point};
+    /// let t = Triangle(Point::new(0.0, 0.0), Point::new(3.0, 0.0), Point::new(0.0, 4.0));
+    /// assert_eq!(t.area(), 6.0);
+    /// ```
+    #[inline]
+    pub fn area(self) -> T {
+        self.signed_area().abs()
+    }
+
+    /// Returns the barycentric coordinates of the point, the weights of the
+    /// three vertices which sum up to one.
+    ///
+    /// The weights are computed from the same sub-triangles as
+    /// [`contains_point`](Triangle::contains_point) uses, so none of them is
+    /// negative exactly when the point is contained, on the edges included.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Triangle, Point};
+    /// let t: Triangle = Triangle(
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(0.0, 10.0),
+    ///     Point::new(10.0, 0.0)
+    /// );
+    /// assert_eq!(t.barycentric_coords(Point::new(5.0, 0.0)), [0.5, 0.0, 0.5]);
+    ///
+    /// assert_eq!(t.barycentric_coords(t.0), [1.0, 0.0, 0.0]);
+    /// assert_eq!(t.barycentric_coords(t.1), [0.0, 1.0, 0.0]);
+    /// assert_eq!(t.barycentric_coords(t.2), [0.0, 0.0, 1.0]);
+    /// assert!(t.contains_point(t.1));
+    ///
+    /// for w in &t.barycentric_coords(t.centroid()) {
+    ///     assert!((w - 1.0 / 3.0).abs() < 1e-6);
+    /// }
+    ///
+    /// let outside = Point::new(10.0, 10.0);
+    /// assert_eq!(t.barycentric_coords(outside), [-1.0, 1.0, 1.0]);
+    /// assert!(!t.contains_point(outside));
+    /// ```
+    #[inline]
+    pub fn barycentric_coords(self, point: Point<T>) -> [T; 3] {
+        let area = self.orientation();
+        let [c, a, b] = self.edge_orientations(point);
+
+        [a / area, b / area, c / area]
+    }
+
+    /// Interpolates the values at the vertices with the given barycentric
+    /// coordinates.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Triangle, Point};
+    /// let t = Triangle(
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(0.0, 10.0),
+    ///     Point::new(10.0, 0.0)
+    /// );
+    /// let coords = t.barycentric_coords(Point::new(5.0, 0.0));
+    /// assert_eq!(Triangle::interpolate(coords, [2.0, 7.0, 4.0]), 3.0);
+    /// ```
+    #[inline]
+    pub fn interpolate(coords: [T; 3], values: [T; 3]) -> T {
+        coords[0] * values[0] + coords[1] * values[1] + coords[2] * values[2]
+    }
+
+    /// Returns true if the point lies inside of the triangle or on its
+    /// boundary, for either winding.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Triangle, Point};
+    /// let t = Triangle(
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(0.0, 10.0),
+    ///     Point::new(10.0, 0.0)
+    /// );
+    /// assert!(t.contains_point(Point::new(2.0, 3.0)));
+    /// assert!(t.contains_point(Point::new(5.0, 5.0)));
+    /// assert!(t.contains_point(Point::new(0.0, 10.0)));
+    /// assert!(!t.contains_point(Point::new(6.0, 6.0)));
+    /// ```
+    #[inline]
+    pub fn contains_point(self, point: Point<T>) -> bool {
+        let [a, b, c] = self.edge_orientations(point);
+        (a >= T::ZERO && b >= T::ZERO && c >= T::ZERO)
+            || (a <= T::ZERO && b <= T::ZERO && c <= T::ZERO)
+    }
+
+    /// Returns true if the point lies strictly inside of the triangle, for
+    /// either winding.
+    ///
+    /// Unlike [`contains_point`](Triangle::contains_point), points on a
+    /// shared edge belong to neither triangle.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Triangle, Point};
+    /// let t = Triangle(
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(0.0, 10.0),
+    ///     Point::new(10.0, 0.0)
+    /// );
+    /// assert!(t.contains_point_exclusive(Point::new(2.0, 3.0)));
+    /// assert!(!t.contains_point_exclusive(Point::new(5.0, 5.0)));
+    /// ```
+    #[inline]
+    pub fn contains_point_exclusive(self, point: Point<T>) -> bool {
+        let [a, b, c] = self.edge_orientations(point);
+        (a > T::ZERO && b > T::ZERO && c > T::ZERO) || (a < T::ZERO && b < T::ZERO && c < T::ZERO)
+    }
+
+    /// Returns true if the triangle and the rectangle overlap, including
+    /// when they only touch, for either winding.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Triangle, Point, Rect};
+    /// let t = Triangle(
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(0.0, 10.0),
+    ///     Point::new(10.0, 0.0)
+    /// );
+    /// assert!(t.intersects_rect(Rect::new(Point::new(4.0, 4.0), Point::new(8.0, 8.0))));
+    /// assert!(t.intersects_rect(Rect::new(Point::new(5.0, 5.0), Point::new(8.0, 8.0))));
+    /// assert!(!t.intersects_rect(Rect::new(Point::new(6.0, 6.0), Point::new(8.0, 8.0))));
+    /// ```
+    #[inline]
+    pub fn intersects_rect(self, rect: Rect<T>) -> bool {
+        let bbox = self.bounding_box();
+
+        if bbox.min.x > rect.max.x
+            || bbox.max.x < rect.min.x
+            || bbox.min.y > rect.max.y
+            || bbox.max.y < rect.min.y
+        {
+            return false;
+        }
+
+        let corners = [
+            rect.min,
+            Point::new(rect.max.x, rect.min.y),
+            rect.max,
+            Point::new(rect.min.x, rect.max.y),
+        ];
+
+        // the bounding boxes overlap, so only the lines of the edges are
+        // left to separate the two
+        let edges = [
+            (self.0, self.1, self.2),
+            (self.1, self.2, self.0),
+            (self.2, self.0, self.1),
+        ];
+
+        !edges.iter().any(|&(a, b, c)| {
+            let inside = Triangle(a, b, c).orientation();
+            let outside = |sign: T| {
+                corners
+                    .iter()
+                    .all(|&p| Triangle(a, b, p).orientation() * sign > T::ZERO)
+            };
+
+            // a flat triangle is separated by its line like a segment is
+            if inside == T::ZERO {
+                outside(T::ONE) || outside(-T::ONE)
+            } else {
+                outside(-inside)
+            }
+        })
+    }
+
+    /// Returns true if the triangle and the circle overlap, including when
+    /// they only touch, for either winding.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Triangle, Point};
+    /// let t = Triangle(
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(0.0, 10.0),
+    ///     Point::new(10.0, 0.0)
+    /// );
+    /// assert!(t.intersects_circle(Point::new(2.0, 2.0), 1.0));
+    /// assert!(t.intersects_circle(Point::new(-3.0, 5.0), 3.0));
+    /// assert!(!t.intersects_circle(Point::new(10.0, 10.0), 7.0));
+    /// ```
+    #[inline]
+    pub fn intersects_circle(self, center: Point<T>, radius: T) -> bool {
+        if self.contains_point(center) {
+            return true;
+        }
+
+        let radius_sq = radius * radius;
+
+        [(self.0, self.1), (self.1, self.2), (self.2, self.0)]
+            .iter()
+            .any(|&(a, b)| segment_distance_sq(a, b, center) <= radius_sq)
+    }
+
+    /// Returns the orientations of the point relative to the three edges
+    #[inline]
+    fn edge_orientations(self, point: Point<T>) -> [T; 3] {
+        [
+            Triangle(self.0, self.1, point).orientation(),
+            Triangle(self.1, self.2, point).orientation(),
+            Triangle(self.2, self.0, point).orientation(),
+        ]
+    }
+}
+
+/// Returns the squared distance from the point to the closest point of the
+/// segment from `a` to `b`
+#[inline]
+fn segment_distance_sq<T: Scalar>(a: Point<T>, b: Point<T>, point: Point<T>) -> T {
+    let (dx, dy) = (b.x - a.x, b.y - a.y);
+    let length_sq = dx * dx + dy * dy;
+
+    let t = if length_sq > T::ZERO {
+        (((point.x - a.x) * dx + (point.y - a.y) * dy) / length_sq)
+            .max(T::ZERO)
+            .min(T::ONE)
+    } else {
+        T::ZERO
+    };
+
+    a.lerp(b, t).distance_sq(point)
+}
+
+/// A line segment between two points.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Segment<T = f32>(pub Point<T>, pub Point<T>);
+
+impl<T: Scalar> Segment<T> {
+    /// Returns the distance between the endpoints.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Point, Segment};
+    /// let s = Segment(Point::new(0.0, 0.0), Point::new(3.0, 4.0));
+    /// assert_eq!(s.length(), 5.0);
+    /// ```
+    #[inline]
+    pub fn length(self) -> T {
+        self.0.distance(self.1)
+    }
+
+    /// Returns the point halfway between the endpoints.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Point, Segment};
+    /// let s = Segment(Point::new(0.0, 0.0), Point::new(3.0, 4.0));
+    /// assert_eq!(s.midpoint(), Point::new(1.5, 2.0));
+    /// ```
+    #[inline]
+    pub fn midpoint(self) -> Point<T> {
+        self.0.lerp(self.1, T::from_f64(0.5))
+    }
+
+    /// Returns true if the point lies on the segment, including its
+    /// endpoints.
+    ///
+    /// The point has to be exactly collinear, which with the `robust` feature
+    /// is decided exactly.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Point, Segment};
+    /// let s = Segment(Point::new(0.0, 0.0), Point::new(10.0, 10.0));
+    /// assert!(s.contains_point(Point::new(5.0, 5.0)));
+    /// assert!(s.contains_point(Point::new(10.0, 10.0)));
+    /// assert!(!s.contains_point(Point::new(11.0, 11.0)));
+    /// assert!(!s.contains_point(Point::new(5.0, 6.0)));
+    /// ```
+    #[inline]
+    pub fn contains_point(self, p: Point<T>) -> bool {
+        Triangle(self.0, self.1, p).orientation() == T::ZERO && self.spans(p)
+    }
+
+    /// Returns true if the segments share at least one point, including when
+    /// they only touch or overlap along a line.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Point, Segment};
+    /// let s = Segment(Point::new(0.0, 0.0), Point::new(10.0, 10.0));
+    /// assert!(s.intersects(Segment(Point::new(0.0, 10.0), Point::new(10.0, 0.0))));
+    /// assert!(s.intersects(Segment(Point::new(10.0, 10.0), Point::new(20.0, 0.0))));
+    /// assert!(!s.intersects(Segment(Point::new(0.0, 1.0), Point::new(10.0, 11.0))));
+    /// ```
+    pub fn intersects(self, other: Segment<T>) -> bool {
+        self.crosses(other) || self.touching_points(other).next().is_some()
+    }
+
+    /// Returns a point shared by both segments, if there is one.
+    ///
+    /// Collinear segments overlapping along a line share more than one
+    /// point, then the end of the overlap closest to `self.0` is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Point, Segment};
+    /// let s = Segment(Point::new(0.0, 0.0), Point::new(10.0, 10.0));
+    /// let crossing = Segment(Point::new(0.0, 10.0), Point::new(10.0, 0.0));
+    /// assert_eq!(s.intersection_point(crossing), Some(Point::new(5.0, 5.0)));
+    ///
+    /// let overlapping = Segment(Point::new(15.0, 15.0), Point::new(5.0, 5.0));
+    /// assert_eq!(s.intersection_point(overlapping), Some(Point::new(5.0, 5.0)));
+    /// ```
+    pub fn intersection_point(self, other: Segment<T>) -> Option<Point<T>> {
+        if self.crosses(other) {
+            let (r, q) = (self.1 - self.0, other.1 - other.0);
+            let t = (other.0 - self.0).cross(q) / r.cross(q);
+            return Some(self.0.lerp(self.1, t.max(T::ZERO).min(T::ONE)));
+        }
+
+        self.touching_points(other).min_by(|&a, &b| {
+            let (a, b) = (a.distance_sq(self.0), b.distance_sq(self.0));
+            a.partial_cmp(&b).unwrap()
+        })
+    }
+
+    /// Returns true if the segments cross at a single point inside both
+    #[inline]
+    fn crosses(self, other: Segment<T>) -> bool {
+        let opposite = |a: T, b: T| (a > T::ZERO && b < T::ZERO) || (a < T::ZERO && b > T::ZERO);
+
+        opposite(
+            Triangle(other.0, other.1, self.0).orientation(),
+            Triangle(other.0, other.1, self.1).orientation(),
+        ) && opposite(
+            Triangle(self.0, self.1, other.0).orientation(),
+            Triangle(self.0, self.1, other.1).orientation(),
+        )
+    }
+
+    /// Returns the endpoints of either segment lying on the other one
+    #[inline]
+    fn touching_points(self, other: Segment<T>) -> impl Iterator<Item = Point<T>> {
+        let on_other = [self.0, self.1].map(|p| (p, other.contains_point(p)));
+        let on_self = [other.0, other.1].map(|p| (p, self.contains_point(p)));
+
+        IntoIterator::into_iter(on_other)
+            .chain(on_self)
+            .filter_map(|(p, touching)| if touching { Some(p) } else { None })
+    }
+
+    /// Returns true if the point lies within the bounding box of the segment
+    #[inline]
+    fn spans(self, p: Point<T>) -> bool {
+        p.x >= self.0.x.min(self.1.x)
+            && p.x <= self.0.x.max(self.1.x)
+            && p.y >= self.0.y.min(self.1.y)
+            && p.y <= self.0.y.max(self.1.y)
+    }
+}
+
+/// Error-free transformation of `a + b` into the rounded sum and its error
+#[inline]
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let sum = a + b;
+    let bv = sum - a;
+    let av = sum - bv;
+    (sum, (a - av) + (b - bv))
+}
+
+/// Sums the values into a nonoverlapping expansion and returns its most
+/// significant component, which has the sign of the exact sum.
+///
+/// The expansion never has more components than there are values, so it
+/// lives on the stack.
+fn exact_sum<const N: usize>(values: [f64; N]) -> f64 {
+    let mut expansion = [0.0; N];
+    let mut len = 0;
+
+    for &value in &values {
+        let mut q = value;
+
+        for component in &mut expansion[..len] {
+            let (sum, err) = two_sum(q, *component);
+            *component = err;
+            q = sum;
+        }
+
+        expansion[len] = q;
+        len += 1;
+    }
+
+    expansion[..len]
+        .iter()
+        .rev()
+        .cloned()
+        .find(|&c| c != 0.0)
+        .unwrap_or(0.0)
+}
+
+/// Returns a cheap substitute for the angle of the vector `(dx, dy)`, for
+/// sorting and hashing directions.
+///
+/// The result grows monotonically with `atan2(dy, dx) / 2π` taken in
+/// `[0, 1)`: it is `0.0` along the positive x axis, `0.25`, `0.5` and `0.75`
+/// along the other axes, and approaches `1.0` when going around back to the
+/// positive x axis. Directions so close to the positive x axis that the
+/// result rounds up to `1.0` map to `0.0` instead. In between it isn't
+/// proportional to the real angle. The zero vector has no direction and maps
+/// to `0.0`.
+///
+/// # Examples
+/// ```
+/// # use triangulation::geom::pseudo_angle;
+/// let a = pseudo_angle(1.0, 1.0);  // 45 degrees
+/// let b = pseudo_angle(2.0, 1.0);  // 26 degrees
+/// assert!(a > b);
+///
+/// assert_eq!(pseudo_angle(-1.0, 0.0), 0.5);
+/// assert!(pseudo_angle(1.0, -1e-6) < 1.0);
+/// assert_eq!(pseudo_angle(1.0f32, -1e-8), 0.0);
+/// assert_eq!(pseudo_angle(0.0, 0.0), 0.0);
+/// ```
+#[inline]
+pub fn pseudo_angle<T: Scalar>(dx: T, dy: T) -> T {
+    let sum = dx.abs() + dy.abs();
+
+    if sum == T::ZERO {
+        return T::ZERO;
+    }
+
+    let p = dx / sum;
+    let quarter = T::from_f64(0.25);
+
+    if dy >= T::ZERO {
+        (T::ONE - p) * quarter
+    } else {
+        let angle = (T::from_f64(3.0) + p) * quarter;
+        if angle < T::ONE {
+            angle
+        } else {
+            T::ZERO
+        }
+    }
+}
+
+/// Rounds coordinates of the points to the nearest multiples of `cell`.
+///
+/// Points closer than half a cell to the same grid node end up exactly
+/// equal, so near-duplicates become true duplicates, which are skipped by
+/// [`Delaunay::new`](crate::Delaunay::new).
+///
+/// # Examples
+/// ```
+/// # use triangulation::geom::{snap_to_grid, Point};
+/// let mut points = [Point::new(10.2, 19.9), Point::new(9.8, 20.3)];
+/// snap_to_grid(&mut points, 1.0);
+/// assert_eq!(points[0], Point::new(10.0, 20.0));
+/// assert_eq!(points[0], points[1]);
+/// ```
+pub fn snap_to_grid<T: Scalar>(points: &mut [Point<T>], cell: T) {
+    for point in points {
+        point.x = (point.x / cell).round() * cell;
+        point.y = (point.y / cell).round() * cell;
+    }
+}
+
+/// Splits the points into separate slices of x and y coordinates.
+///
+/// # Examples
+/// ```
+/// # use triangulation::{geom, Point};
+/// let points = vec![Point::new(1.0, 2.0), Point::new(3.0, 4.0)];
+/// let (xs, ys) = geom::to_soa(&points);
+///
+/// assert_eq!(xs, vec![1.0, 3.0]);
+/// assert_eq!(ys, vec![2.0, 4.0]);
+/// assert_eq!(geom::from_soa(&xs, &ys), points);
+/// ```
+pub fn to_soa<T: Scalar>(points: &[Point<T>]) -> (Vec<T>, Vec<T>) {
+    points.iter().map(|p| (p.x, p.y)).unzip()
+}
+
+/// Builds points from separate slices of x and y coordinates.
+///
+/// # Panics
+/// Panics if the slices have different lengths.
+pub fn from_soa<T: Scalar>(xs: &[T], ys: &[T]) -> Vec<Point<T>> {
+    assert_eq!(xs.len(), ys.len(), "coordinate slices differ in length");
+
+    xs.iter().zip(ys).map(|(&x, &y)| Point::new(x, y)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+
+    fn random_triangles() -> impl Iterator<Item = Triangle> {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(13);
+        let mut point =
+            move || Point::new(rng.gen_range(-100.0, 100.0), rng.gen_range(-100.0, 100.0));
+
+        (0..1000)
+            .map(move |_| Triangle(point(), point(), point()))
+            .filter(|t| t.area() > 1.0)
+    }
+
+    #[test]
+    fn point_arithmetic() {
+        let (a, b) = (Point::new(1.0, 2.0), Point::new(-3.0, 0.5));
+
+        assert_eq!(a + b, Point::new(-2.0, 2.5));
+        assert_eq!(a - b, Point::new(4.0, 1.5));
+        assert_eq!(-a, Point::new(-1.0, -2.0));
+        assert_eq!(a * 2.0, Point::new(2.0, 4.0));
+        assert_eq!(b / 2.0, Point::new(-1.5, 0.25));
+        assert_eq!(a + Point::zero(), a);
+
+        let mut c = a;
+        c += b;
+        assert_eq!(c, a + b);
+        c -= b;
+        assert_eq!(c, a);
+
+        // the cross product is twice the signed area
+        let t = Triangle(
+            Point::new(0.0, 0.0),
+            Point::new(0.0, 4.0),
+            Point::new(3.0, 0.0),
+        );
+        assert_eq!((t.0 - t.1).cross(t.2 - t.1), t.orientation());
+        assert_eq!((t.1 - t.0).dot(t.2 - t.0), 0.0);
+
+        let d = Point::new(1.0f64, 2.0) * 3.0;
+        assert_eq!(d.dot(d), 45.0);
+    }
+
+    #[test]
+    fn robust_orientation_sign() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(17);
+
+        // integer coordinates, so the determinant is exact in i128; the
+        // third point is within a unit of the line through the others
+        for _ in 0..1000 {
+            let a = (
+                rng.gen_range(-1 << 23, 1 << 23),
+                rng.gen_range(-1 << 23, 1 << 23),
+            );
+            let b = (
+                rng.gen_range(-1 << 23, 1 << 23),
+                rng.gen_range(-1 << 23, 1 << 23),
+            );
+            let k: i64 = rng.gen_range(-2, 3);
+            let c = (
+                a.0 + k * (b.0 - a.0) / 2 + rng.gen_range(-1, 2),
+                a.1 + k * (b.1 - a.1) / 2 + rng.gen_range(-1, 2),
+            );
+            if c.0.abs() >= 1 << 24 || c.1.abs() >= 1 << 24 {
+                continue;
+            }
+
+            let exact = i128::from(a.0 - b.0) * i128::from(c.1 - b.1)
+                - i128::from(a.1 - b.1) * i128::from(c.0 - b.0);
+
+            let point = |(x, y): (i64, i64)| Point::new(x as f32, y as f32);
+            let t = Triangle(point(a), point(b), point(c));
+
+            assert_eq!(
+                t.robust_orientation().partial_cmp(&0.0),
+                exact.partial_cmp(&0)
+            );
+            #[cfg(feature = "robust")]
+            assert_eq!(t.orientation().partial_cmp(&0.0), exact.partial_cmp(&0));
+        }
+    }
+
+    #[test]
+    fn conversions() {
+        let p = Point::new(1.5, -2.0);
+
+        assert_eq!(Point::from((1.5, -2.0)), p);
+        assert_eq!(Point::from([1.5, -2.0]), p);
+        assert_eq!(<(f32, f32)>::from(p), (1.5, -2.0));
+        assert_eq!(<[f32; 2]>::from(p), [1.5, -2.0]);
+        assert_eq!(<(i32, i32)>::from(p), (1, -2));
+
+        // round trips
+        let tuple: (f32, f32) = p.into();
+        let array: [f32; 2] = p.into();
+        assert_eq!(Point::from(tuple), p);
+        assert_eq!(Point::from(array), p);
+
+        let coords = [0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
+        let points: Vec<Point> = coords.chunks(2).map(|c| [c[0], c[1]].into()).collect();
+        assert_eq!(
+            points,
+            vec![
+                Point::new(0.0, 1.0),
+                Point::new(2.0, 3.0),
+                Point::new(4.0, 5.0)
+            ]
+        );
+
+        let flat = points
+            .iter()
+            .flat_map(|&p| <[f32; 2]>::from(p).to_vec())
+            .collect::<Vec<_>>();
+        assert_eq!(flat, coords);
+
+        let d: Point<f64> = (0.25, 8.0).into();
+        assert_eq!(<[f64; 2]>::from(d), [0.25, 8.0]);
+    }
+
+    #[test]
+    fn f64_triangles() {
+        // a unit right triangle far from the origin, where f32 can't even
+        // represent the coordinates
+        let (x, y) = (500_000.25, 4_649_776.25);
+        let t: Triangle64 = Triangle(
+            Point64::new(x, y),
+            Point64::new(x, y + 1.0),
+            Point64::new(x + 1.0, y),
+        );
+
+        assert_eq!(t.area(), 0.5);
+        assert_eq!(t.perimeter(), 2.0 + 2f64.sqrt());
+        assert!(t.is_right() && !t.is_acute() && !t.is_obtuse());
+        assert!(t.circumcenter().approx_eq(Point64::new(x + 0.5, y + 0.5)));
+        assert_eq!(t.circumradius_sq(), 0.5);
+        assert!(t.in_circumcircle(Point64::new(x + 0.5, y + 0.5)));
+        assert!(!t.in_circumcircle(Point64::new(x + 1.25, y + 1.25)));
+        assert!(
+            t.centroid()
+                .distance(Point64::new(x + 1.0 / 3.0, y + 1.0 / 3.0))
+                < 1e-9
+        );
+        assert_eq!(
+            t.barycentric_coords(Point64::new(x + 0.5, y)),
+            [0.5, 0.0, 0.5]
+        );
+        assert!(t.contains_point(Point64::new(x + 0.25, y + 0.25)));
+        assert!(!t.contains_point(Point64::new(x + 0.75, y + 0.75)));
+        assert!((t.max_angle() - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+        assert!(t.intersects_circle(Point64::new(x - 1.0, y), 1.0));
+        assert!(!t.intersects_rect(Rect::new(
+            Point64::new(x + 0.75, y + 0.75),
+            Point64::new(x + 2.0, y + 2.0)
+        )));
+
+        assert_ne!(t.0.cast::<f32>().cast::<f64>(), t.0);
+    }
+
+    #[test]
+    fn f64_snap_and_soa() {
+        let (x, y) = (500_000.0, 4_649_776.0);
+        let mut points = [
+            Point64::new(x + 0.26, y - 0.24),
+            Point64::new(x - 0.24, y + 0.74),
+        ];
+        snap_to_grid(&mut points, 0.5);
+        assert_eq!(points, [Point64::new(x + 0.5, y), Point64::new(x, y + 0.5)]);
+
+        let (xs, ys) = to_soa(&points);
+        assert_eq!(xs, vec![x + 0.5, x]);
+        assert_eq!(from_soa(&xs, &ys), points);
+    }
+
+    #[test]
+    fn hash() {
+        use std::collections::{HashMap, HashSet};
+
+        let points = [
+            Point::new(1.0, 2.0),
+            Point::new(2.0, 1.0),
+            Point::new(1.0, 2.0),
+            Point::new(0.0, -0.0),
+            Point::new(-0.0, 0.0),
+        ];
+
+        let set = points.iter().cloned().collect::<HashSet<_>>();
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(&Point::new(0.0, 0.0)));
+
+        let mut counts = HashMap::new();
+        for &p in &points {
+            *counts.entry(p).or_insert(0) += 1;
+        }
+        assert_eq!(counts[&Point::new(1.0, 2.0)], 2);
+        assert_eq!(counts[&Point::new(0.0, 0.0)], 2);
+
+        let points = [Point::new(0.5f64, 0.25), Point::new(0.5, 0.25)];
+        let set = points.iter().collect::<HashSet<_>>();
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn hash_nan() {
+        let mut set = std::collections::HashSet::new();
+        set.insert(Point::new(0.0, f32::NAN));
+    }
+
+    #[test]
+    fn display() {
+        let p = Point::new(10.5, 20.25);
+        assert_eq!(p.to_string(), "(10.5, 20.25)");
+        assert_eq!(format!("{:.1}", p), "(10.5, 20.2)");
+        assert_eq!(
+            format!("{:.3}", Point::new(1.0f64, -2.0)),
+            "(1.000, -2.000)"
+        );
+
+        let t = Triangle(Point::new(0.0, 0.0), Point::new(0.0, 1.5), p);
+        assert_eq!(t.to_string(), "(0, 0) -> (0, 1.5) -> (10.5, 20.25)");
+        assert_eq!(
+            format!("{:.1}", t),
+            "(0.0, 0.0) -> (0.0, 1.5) -> (10.5, 20.2)"
+        );
+    }
+
+    #[test]
+    fn distance_and_length() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(19);
+
+        for _ in 0..1000 {
+            let a = Point::new(rng.gen_range(-100.0, 100.0), rng.gen_range(-100.0, 100.0));
+            let b = Point::new(rng.gen_range(-100.0, 100.0), rng.gen_range(-100.0, 100.0));
+            let d = Point::new(b.x - a.x, b.y - a.y);
+
+            assert_eq!(a.distance(b), b.distance(a));
+            assert!((a.distance(b) - d.length()).abs() < 1e-4);
+
+            let unit = d.normalize();
+            assert!((unit.length() - 1.0).abs() < 1e-6);
+            assert!(Triangle(Point::new(0.0, 0.0), d, unit).orientation().abs() < 1e-3);
+            assert!(unit.x * d.x + unit.y * d.y > 0.0);
+        }
+    }
+
+    fn is_acute(t: Triangle) -> bool {
+        let (a, b, c) = (
+            t.1.distance_sq(t.2),
+            t.2.distance_sq(t.0),
+            t.0.distance_sq(t.1),
+        );
+        a + b > c && b + c > a && c + a > b
+    }
+
+    #[test]
+    fn midpoints() {
+        let t = Triangle(
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(0.0, 1.0),
+        );
+
+        assert_eq!(
+            t.midpoints(),
+            [
+                Point::new(0.5, 0.5),
+                Point::new(0.0, 0.5),
+                Point::new(0.5, 0.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn subdivide_4() {
+        for t in random_triangles() {
+            let children = t.subdivide_4();
+
+            let area: f32 = children.iter().map(|c| c.area()).sum();
+            assert!((area - t.area()).abs() <= 1e-4 * t.area());
+
+            for c in &children {
+                assert_eq!(c.is_right_handed(), t.is_right_handed());
+
+                let ratio = c.aspect_ratio() / t.aspect_ratio();
+                assert!((ratio - 1.0).abs() < 1e-3, "{:?} {:?}", t, c);
+            }
+        }
+    }
+
+    #[test]
+    fn edge_indices() {
+        for t in random_triangles() {
+            let sides = t.side_lengths();
+            let (longest, shortest) = (t.longest_edge_index(), t.shortest_edge_index());
+
+            assert_ne!(longest, shortest);
+            assert_eq!(
+                sides[(longest + 1) % 3],
+                sides.iter().cloned().fold(0.0, f32::max)
+            );
+            assert_eq!(
+                sides[(shortest + 1) % 3],
+                sides.iter().cloned().fold(f32::INFINITY, f32::min)
+            );
+
+            let (a, b) = t.longest_edge_endpoints();
+            assert_eq!(a.distance_sq(b).sqrt(), sides[(longest + 1) % 3]);
+        }
+    }
+
+    #[test]
+    fn contains_vertices() {
+        for t in random_triangles() {
+            assert!(t.contains_point(t.0) && t.contains_point(t.1) && t.contains_point(t.2));
+            assert!(!t.contains_point_exclusive(t.0));
+            assert!(t.contains_point_exclusive(t.centroid()));
+        }
+    }
+
+    #[test]
+    fn contains_circumcenter_of_acute() {
+        for t in random_triangles().filter(|&t| is_acute(t)) {
+            assert!(t.contains_point(t.circumcenter()), "{:?}", t);
+        }
+    }
+
+    #[test]
+    fn outside() {
+        for t in random_triangles() {
+            let c = t.centroid();
+
+            // reflecting the centroid over a vertex always leaves the triangle
+            for v in &[t.0, t.1, t.2] {
+                let outside = Point::new(2.0 * v.x - c.x, 2.0 * v.y - c.y);
+                assert!(!t.contains_point(outside));
+                assert!(!t.contains_point_exclusive(outside));
+            }
+        }
+    }
+
+    fn equilateral(side: f32) -> Triangle {
+        Triangle(
+            Point::new(10.0, 20.0),
+            Point::new(10.0 + side, 20.0),
+            Point::new(10.0 + side / 2.0, 20.0 + side * 3.0_f32.sqrt() / 2.0),
+        )
+    }
+
+    #[test]
+    fn intersects_rect() {
+        let t = Triangle(
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(0.0, 10.0),
+        );
+        let rect = |x0, y0, x1, y1| Rect::new(Point::new(x0, y0), Point::new(x1, y1));
+
+        // inside, around, through an edge and touching a vertex
+        assert!(t.intersects_rect(rect(1.0, 1.0, 2.0, 2.0)));
+        assert!(t.intersects_rect(rect(-5.0, -5.0, 20.0, 20.0)));
+        assert!(t.intersects_rect(rect(4.0, -5.0, 6.0, 20.0)));
+        assert!(t.intersects_rect(rect(10.0, 0.0, 12.0, 2.0)));
+
+        // beyond the hypotenuse, within the bounding box
+        assert!(!t.intersects_rect(rect(6.0, 6.0, 9.0, 9.0)));
+        assert!(!t.intersects_rect(rect(11.0, 0.0, 12.0, 2.0)));
+
+        let flat = Triangle(t.0, t.1, Point::new(5.0, 0.0));
+        assert!(flat.intersects_rect(rect(4.0, -1.0, 6.0, 1.0)));
+        assert!(!flat.intersects_rect(rect(4.0, 1.0, 6.0, 2.0)));
+
+        // any point of the rectangle inside of the triangle is an overlap
+        let mut rng = rand::rngs::StdRng::seed_from_u64(18);
+        for t in random_triangles() {
+            let min = Point::new(rng.gen_range(-110.0, 90.0), rng.gen_range(-110.0, 90.0));
+            let r = rect(min.x, min.y, min.x + 20.0, min.y + 20.0);
+
+            let sampled = (0..=10)
+                .flat_map(|i| (0..=10).map(move |j| (i, j)))
+                .map(|(i, j)| Point::new(min.x + i as f32 * 2.0, min.y + j as f32 * 2.0))
+                .any(|p| t.contains_point(p));
+
+            if sampled {
+                assert!(t.intersects_rect(r), "{:?} {:?}", t, r);
+            }
+        }
+    }
+
+    #[test]
+    fn barycentric_matches_contains() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(20);
+
+        for t in random_triangles() {
+            // vertices and points on the edges as well as random ones
+            let mut probes = vec![t.0, t.1, t.2, t.0.lerp(t.1, 0.5), t.1.lerp(t.2, 0.25)];
+            probes.extend(
+                (0..10).map(|_| {
+                    Point::new(rng.gen_range(-100.0, 100.0), rng.gen_range(-100.0, 100.0))
+                }),
+            );
+
+            for &p in &probes {
+                let coords = t.barycentric_coords(p);
+                assert_eq!(
+                    coords.iter().all(|&w| w >= 0.0),
+                    t.contains_point(p),
+                    "{:?} {:?}",
+                    t,
+                    p
+                );
+                assert!((coords.iter().sum::<f32>() - 1.0).abs() < 1e-3);
+            }
+        }
+    }
+
+    #[test]
+    fn intersects_circle() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(21);
+
+        for t in random_triangles() {
+            let center = Point::new(rng.gen_range(-150.0, 150.0), rng.gen_range(-150.0, 150.0));
+            let radius = rng.gen_range(0.0, 50.0);
+
+            // sampled points of the triangle inside of the circle overlap it
+            let sampled = (0..=20)
+                .flat_map(|i| (0..=20 - i).map(move |j| (i, j)))
+                .map(|(i, j)| {
+                    let (u, v) = (i as f32 / 20.0, j as f32 / 20.0);
+                    Point::new(
+                        t.0.x + (t.1.x - t.0.x) * u + (t.2.x - t.0.x) * v,
+                        t.0.y + (t.1.y - t.0.y) * u + (t.2.y - t.0.y) * v,
+                    )
+                })
+                .any(|p| p.distance(center) < radius);
+
+            if sampled {
+                assert!(t.intersects_circle(center, radius), "{:?}", t);
+            }
+
+            assert!(t.intersects_circle(t.centroid(), 0.0));
+            assert!(t.intersects_circle(t.0, 0.0));
+        }
+    }
+
+    #[test]
+    fn inscribed_circle_of_equilateral() {
+        for &side in &[0.5, 1.0, 7.0, 100.0] {
+            let t = equilateral(side);
+            let expected = side / (2.0 * 3.0_f32.sqrt());
+            assert!((t.inradius() - expected).abs() <= 1e-4 * side, "{}", side);
+
+            let (incenter, centroid) = (t.incenter(), t.centroid());
+            assert!(incenter.distance_sq(centroid).sqrt() <= 1e-4 * side);
+        }
+    }
+
+    #[test]
+    fn incenter_inside() {
+        for t in random_triangles() {
+            let c = t.incenter();
+            assert!(t.contains_point_exclusive(c), "{:?}", t);
+
+            // the inscribed circle fits inside the circumscribed one
+            assert!(t.inradius() <= t.circumradius_sq().sqrt() / 2.0 * (1.0 + 1e-4));
+        }
+    }
+
+    #[test]
+    fn aspect_ratio() {
+        for &side in &[0.5, 1.0, 7.0, 100.0] {
+            assert!((equilateral(side).aspect_ratio() - 1.0).abs() < 1e-4);
+        }
+
+        for t in random_triangles() {
+            assert!(t.aspect_ratio() >= 1.0 - 1e-4, "{:?}", t);
+        }
+
+        let p = Point::new(1.0, 2.0);
+        assert_eq!(Triangle(p, p, p).aspect_ratio(), f32::INFINITY);
+
+        let collinear = Triangle(p, Point::new(2.0, 4.0), Point::new(3.0, 6.0));
+        assert_eq!(collinear.aspect_ratio(), f32::INFINITY);
+    }
+
+    #[test]
+    fn angles() {
+        use std::f32::consts::PI;
+
+        for t in random_triangles() {
+            let [a, b, c] = t.angles();
+            assert!((a + b + c - PI).abs() < 1e-4, "{:?}", t);
+
+            assert!(t.min_angle() <= PI / 3.0 + 1e-5);
+            assert!(t.max_angle() >= PI / 3.0 - 1e-5);
+            assert!(t.max_angle() < PI);
+
+            // acute triangles have no angle of at least a right angle
+            if is_acute(t) {
+                assert!(t.max_angle() < PI / 2.0 + 1e-5);
+            }
+        }
+    }
+
+    #[test]
+    fn angle_kinds() {
+        let right = Triangle(
+            Point::new(0.0, 0.0),
+            Point::new(4.0, 0.0),
+            Point::new(0.0, 3.0),
+        );
+        for t in &[
+            right,
+            Triangle(right.1, right.2, right.0),
+            Triangle(right.2, right.1, right.0),
+        ] {
+            assert!(t.is_right() && !t.is_acute() && !t.is_obtuse(), "{:?}", t);
+        }
+
+        let angle = 100f32.to_radians();
+        let obtuse = Triangle(
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(10.0 * angle.cos(), 10.0 * angle.sin()),
+        );
+        assert!(obtuse.is_obtuse() && !obtuse.is_acute() && !obtuse.is_right());
+
+        let equilateral = Triangle(
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(0.5, 3.0_f32.sqrt() / 2.0),
+        );
+        assert!(equilateral.is_acute() && !equilateral.is_obtuse() && !equilateral.is_right());
+
+        for t in random_triangles() {
+            let kinds = [t.is_acute(), t.is_right(), t.is_obtuse()];
+            assert_eq!(kinds.iter().filter(|&&k| k).count(), 1, "{:?}", t);
+            assert_eq!(t.is_acute(), is_acute(t), "{:?}", t);
+        }
+    }
+
+    #[test]
+    fn bounding_box() {
+        for t in random_triangles() {
+            let rect = t.bounding_box();
+            assert_eq!(Some(rect), Rect::from_points(&[t.0, t.1, t.2]));
+
+            for p in &[t.0, t.1, t.2, t.centroid(), t.incenter()] {
+                assert!(p.x >= rect.min.x && p.x <= rect.max.x);
+                assert!(p.y >= rect.min.y && p.y <= rect.max.y);
+            }
+        }
+    }
+
+    fn segment(ax: f32, ay: f32, bx: f32, by: f32) -> Segment {
+        Segment(Point::new(ax, ay), Point::new(bx, by))
+    }
+
+    #[test]
+    fn segment_crossing() {
+        let s = segment(0.0, 0.0, 4.0, 2.0);
+        let other = segment(0.0, 2.0, 4.0, 0.0);
+
+        assert!(s.intersects(other));
+        assert_eq!(s.intersection_point(other), Some(Point::new(2.0, 1.0)));
+        assert_eq!(other.intersection_point(s), Some(Point::new(2.0, 1.0)));
+
+        // the lines cross, but outside of the second segment
+        let short = segment(0.0, 2.0, 1.0, 1.5);
+        assert!(!s.intersects(short));
+        assert_eq!(s.intersection_point(short), None);
+    }
+
+    #[test]
+    fn segment_parallel() {
+        let s = segment(0.0, 0.0, 4.0, 2.0);
+
+        for &other in &[segment(0.0, 1.0, 4.0, 3.0), segment(4.0, 3.0, 0.0, 1.0)] {
+            assert!(!s.intersects(other));
+            assert!(!other.intersects(s));
+            assert_eq!(s.intersection_point(other), None);
+        }
+    }
+
+    #[test]
+    fn segment_collinear() {
+        let s = segment(0.0, 0.0, 4.0, 2.0);
+
+        let disjoint = segment(6.0, 3.0, 8.0, 4.0);
+        assert!(!s.intersects(disjoint));
+        assert_eq!(s.intersection_point(disjoint), None);
+
+        let overlapping = segment(6.0, 3.0, 2.0, 1.0);
+        assert!(s.intersects(overlapping));
+        assert!(overlapping.intersects(s));
+        assert_eq!(
+            s.intersection_point(overlapping),
+            Some(Point::new(2.0, 1.0))
+        );
+        assert_eq!(
+            overlapping.intersection_point(s),
+            Some(Point::new(4.0, 2.0))
+        );
+
+        let inner = segment(3.0, 1.5, 1.0, 0.5);
+        assert_eq!(s.intersection_point(inner), Some(Point::new(1.0, 0.5)));
+        assert_eq!(inner.intersection_point(s), Some(Point::new(3.0, 1.5)));
+
+        assert_eq!(s.intersection_point(s), Some(s.0));
+    }
+
+    #[test]
+    fn segment_touching() {
+        let s = segment(0.0, 0.0, 4.0, 2.0);
+
+        // shared endpoint, either collinear or at an angle
+        for &other in &[segment(4.0, 2.0, 8.0, 4.0), segment(4.0, 2.0, 4.0, 8.0)] {
+            assert!(s.intersects(other));
+            assert_eq!(s.intersection_point(other), Some(Point::new(4.0, 2.0)));
+            assert_eq!(other.intersection_point(s), Some(Point::new(4.0, 2.0)));
+        }
+
+        // endpoint in the middle of the other segment
+        let t_junction = segment(2.0, 1.0, 2.0, 5.0);
+        assert!(s.intersects(t_junction));
+        assert_eq!(s.intersection_point(t_junction), Some(Point::new(2.0, 1.0)));
+
+        // a degenerate segment is a point
+        let point = segment(2.0, 1.0, 2.0, 1.0);
+        assert!(s.intersects(point));
+        assert!(point.intersects(s));
+        assert_eq!(point.intersection_point(s), Some(Point::new(2.0, 1.0)));
+        assert!(!s.intersects(segment(2.0, 2.0, 2.0, 2.0)));
+    }
+
+    #[test]
+    fn segment_measures() {
+        let s = segment(1.0, 1.0, 4.0, 5.0);
+
+        assert_eq!(s.length(), 5.0);
+        assert_eq!(s.midpoint(), Point::new(2.5, 3.0));
+        assert!(s.contains_point(s.0));
+        assert!(s.contains_point(s.midpoint()));
+        assert!(!s.contains_point(Point::new(7.0, 9.0)));
+        assert!(!s.contains_point(Point::new(2.5, 3.5)));
+    }
+
+    #[test]
+    fn pseudo_angle_order() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(17);
+        let tau = 2.0 * std::f64::consts::PI;
+
+        for _ in 0..1000 {
+            let (a, b) = (rng.gen_range(0.0, tau), rng.gen_range(0.0, tau));
+            let (a, b) = if a < b { (a, b) } else { (b, a) };
+
+            let pa = pseudo_angle(a.cos(), a.sin());
+            let pb = pseudo_angle(b.cos(), b.sin());
+
+            assert!(pa < pb, "{} < {}, but {} >= {}", a, b, pa, pb);
+            assert!((0.0..1.0).contains(&pa) && (0.0..1.0).contains(&pb));
+
+            let pa = pseudo_angle(a.cos() as f32, a.sin() as f32);
+            let pb = pseudo_angle(b.cos() as f32, b.sin() as f32);
+
+            assert!(pa < pb, "{} < {}, but {} >= {} in f32", a, b, pa, pb);
+            assert!((0.0..1.0).contains(&pa) && (0.0..1.0).contains(&pb));
+        }
     }
 }
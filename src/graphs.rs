@@ -0,0 +1,276 @@
+//! Proximity graphs of the points, which are subgraphs of the triangulation
+
+use crate::dcel::{EdgeIndex, PointIndex};
+use crate::geom::{Point, Scalar};
+use crate::Triangulation;
+
+/// Returns the edges of the Euclidean minimum spanning tree of the points.
+///
+/// The tree is a subgraph of the triangulation, so only its edges are
+/// considered, shortest first, joining them unless both ends are already
+/// connected (Kruskal's algorithm). A connected triangulation of `n` points
+/// results in `n - 1` edges, while points skipped by the triangulation, like
+/// duplicates, stay out of the tree. Edges of points with a NaN coordinate
+/// have no length and are considered last.
+///
+/// # Examples
+/// ```
+/// # use triangulation::{graphs::euclidean_mst, Delaunay, Point};
+/// let points = &[
+///     Point::new(0.0, 0.0),
+///     Point::new(10.0, 0.0),
+///     Point::new(5.0, 1.0),
+/// ];
+/// let t = Delaunay::new(points).unwrap();
+/// let tree = euclidean_mst(&t, points);
+///
+/// assert_eq!(tree.len(), 2);
+/// assert!(tree.iter().all(|&(a, b)| a.as_usize() == 2 || b.as_usize() == 2));
+/// ```
+pub fn euclidean_mst<T: Triangulation + ?Sized>(
+    triangulation: &T,
+    points: &[Point<T::Scalar>],
+) -> Vec<(PointIndex, PointIndex)> {
+    let mut edges = triangulation
+        .dcel()
+        .wireframe_edges()
+        .map(|(a, b, _)| (points[a].distance_sq(points[b]), a, b))
+        .collect::<Vec<_>>();
+    edges.sort_unstable_by(|a, b| Scalar::total_cmp(&a.0, &b.0));
+
+    let mut sets = DisjointSets::new(points.len());
+    let mut tree = Vec::with_capacity(points.len().saturating_sub(1));
+
+    for (_, a, b) in edges {
+        if sets.union(a.as_usize(), b.as_usize()) {
+            tree.push((a, b));
+
+            if tree.len() + 1 == points.len() {
+                break;
+            }
+        }
+    }
+
+    tree
+}
+
+/// Returns the boundary edges of the alpha shape of the points, a concave
+/// outline of the point cloud.
+///
+/// The triangles with a circumradius of at most `alpha` are kept, and the
+/// edges bordering exactly one kept triangle are returned, oriented like in
+/// their kept triangle. A small `alpha` splits the shape apart at gaps
+/// wider than about `2 * alpha`, an infinite one gives the convex hull.
+///
+/// # Examples
+/// ```
+/// # use triangulation::{graphs::alpha_shape, Delaunay, Point};
+/// let points = &[
+///     Point::new(0.0, 0.0),
+///     Point::new(10.0, 0.0),
+///     Point::new(10.0, 10.0),
+///     Point::new(0.0, 10.0),
+///     Point::new(5.0, 9.0),
+/// ];
+/// let t = Delaunay::new(points).unwrap();
+///
+/// assert_eq!(alpha_shape(&t, points, std::f32::INFINITY).len(), 4);
+/// // the flat triangle along the top edge has a circumradius of 13
+/// assert_eq!(alpha_shape(&t, points, 6.0).len(), 5);
+/// ```
+pub fn alpha_shape<T: Triangulation + ?Sized>(
+    triangulation: &T,
+    points: &[Point<T::Scalar>],
+    alpha: T::Scalar,
+) -> Vec<(PointIndex, PointIndex)> {
+    let dcel = triangulation.dcel();
+    let alpha_sq = alpha * alpha;
+
+    let kept = dcel
+        .triangles(points)
+        .map(|t| t.circumradius_sq() <= alpha_sq)
+        .collect::<Vec<_>>();
+
+    (0..dcel.vertices.len())
+        .map(EdgeIndex::from)
+        .filter(|&e| kept[e.as_usize() / 3])
+        .filter(|&e| dcel.twin(e).map_or(true, |twin| !kept[twin.as_usize() / 3]))
+        .map(|e| (dcel.vertices[e], dcel.edge_endpoint(e)))
+        .collect()
+}
+
+/// Union-find over indices with path halving and union by size
+struct DisjointSets {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+}
+
+impl DisjointSets {
+    fn new(len: usize) -> DisjointSets {
+        DisjointSets {
+            parents: (0..len).collect(),
+            sizes: vec![1; len],
+        }
+    }
+
+    fn find(&mut self, mut i: usize) -> usize {
+        while self.parents[i] != i {
+            self.parents[i] = self.parents[self.parents[i]];
+            i = self.parents[i];
+        }
+
+        i
+    }
+
+    /// Joins the sets of both indices, returns `false` if they were the same
+    fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+
+        if a == b {
+            return false;
+        }
+
+        if self.sizes[a] < self.sizes[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+
+        self.parents[b] = a;
+        self.sizes[a] += self.sizes[b];
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::testing::random_points;
+    use crate::Delaunay;
+    use rand::{Rng, SeedableRng};
+
+    fn total_length(points: &[Point], tree: &[(PointIndex, PointIndex)]) -> f64 {
+        tree.iter()
+            .map(|&(a, b)| f64::from(points[a].distance(points[b])))
+            .sum()
+    }
+
+    #[test]
+    fn square_with_center() {
+        let points: &[Point] = &[
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(10.0, 10.0),
+            Point::new(0.0, 10.0),
+            Point::new(5.0, 5.0),
+        ];
+        let t = Delaunay::new(points).unwrap();
+        let tree = euclidean_mst(&t, points);
+
+        assert_eq!(tree.len(), 4);
+
+        let mut corners = tree
+            .iter()
+            .map(|&(a, b)| {
+                assert!(a.as_usize() == 4 || b.as_usize() == 4);
+                a.as_usize().min(b.as_usize())
+            })
+            .collect::<Vec<_>>();
+        corners.sort_unstable();
+        assert_eq!(corners, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn matches_prim() {
+        let points = random_points(300, 7);
+
+        let t = Delaunay::new(&points).unwrap();
+        let tree = euclidean_mst(&t, &points);
+        assert_eq!(tree.len(), points.len() - 1);
+
+        // quadratic Prim's algorithm over the complete graph
+        let mut in_tree = vec![false; points.len()];
+        let mut dist = vec![f32::INFINITY; points.len()];
+        let mut expected = 0.0;
+        dist[0] = 0.0;
+
+        for _ in 0..points.len() {
+            let next = (0..points.len())
+                .filter(|&i| !in_tree[i])
+                .min_by(|&a, &b| Scalar::total_cmp(&dist[a], &dist[b]))
+                .unwrap();
+            in_tree[next] = true;
+            expected += f64::from(dist[next].sqrt());
+
+            for i in 0..points.len() {
+                dist[i] = dist[i].min(points[i].distance_sq(points[next]));
+            }
+        }
+
+        assert!((total_length(&points, &tree) - expected).abs() < 1e-3);
+    }
+
+    #[test]
+    fn nan_point() {
+        let mut points = random_points(50, 8);
+
+        let t = Delaunay::new(&points).unwrap();
+        points[7].x = f32::NAN;
+
+        let tree = euclidean_mst(&t, &points);
+        assert_eq!(tree.len(), points.len() - 1);
+        assert!(tree
+            .iter()
+            .any(|&(a, b)| a.as_usize() == 7 || b.as_usize() == 7));
+    }
+
+    fn components(edges: &[(PointIndex, PointIndex)], len: usize) -> usize {
+        let mut sets = DisjointSets::new(len);
+        let joins = edges
+            .iter()
+            .filter(|&&(a, b)| sets.union(a.as_usize(), b.as_usize()))
+            .count();
+
+        let mut used = edges
+            .iter()
+            .flat_map(|&(a, b)| vec![a.as_usize(), b.as_usize()])
+            .collect::<Vec<_>>();
+        used.sort_unstable();
+        used.dedup();
+
+        used.len() - joins
+    }
+
+    #[test]
+    fn alpha_clusters() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(11);
+        let mut points = Vec::new();
+
+        for &offset in &[0.0, 30.0] {
+            for i in 0..121 {
+                let jitter = Point::new(rng.gen_range(-0.1, 0.1), rng.gen_range(-0.1, 0.1));
+                points.push(Point::new(offset + (i % 11) as f32, (i / 11) as f32) + jitter);
+            }
+        }
+
+        let t = Delaunay::new(&points).unwrap();
+
+        let blob = alpha_shape(&t, &points, 100.0);
+        assert_eq!(components(&blob, points.len()), 1);
+        assert!(blob
+            .iter()
+            .any(|&(a, b)| (a.as_usize() < 121) != (b.as_usize() < 121)));
+
+        let apart = alpha_shape(&t, &points, 2.0);
+        assert_eq!(components(&apart, points.len()), 2);
+        assert_eq!(apart.len(), 2 * 40);
+
+        // every boundary point has one edge in and one out
+        for edges in &[blob, apart] {
+            let starts = edges.iter().map(|e| e.0).collect::<HashSet<_>>();
+            let ends = edges.iter().map(|e| e.1).collect::<HashSet<_>>();
+            assert_eq!((starts.len(), ends.len()), (edges.len(), edges.len()));
+            assert_eq!(starts, ends);
+        }
+    }
+}
@@ -0,0 +1,341 @@
+//! Interpolation of scalar fields defined at the points of any
+//! [`Triangulation`]
+
+use std::cell::Cell;
+
+use crate::dcel::{EdgeIndex, TrianglesDCEL};
+use crate::geom::{Point, Rect, Scalar, Triangle};
+use crate::{locate_from, locate_with_hint, Delaunay, Triangulation};
+
+/// Piecewise-linear interpolation of values given at the points of a
+/// triangulation, like heights of terrain samples.
+///
+/// # Examples
+/// ```
+/// # use triangulation::{Delaunay, Interpolator, Point};
+/// let points = &[
+///     Point::new(0.0, 0.0),
+///     Point::new(10.0, 0.0),
+///     Point::new(0.0, 10.0),
+/// ];
+/// let t = Delaunay::new(points).unwrap();
+/// let heights = Interpolator::new(&t, &[0.0, 10.0, 20.0]);
+///
+/// assert_eq!(heights.interpolate(Point::new(0.0, 0.0)), Some(0.0));
+/// assert_eq!(heights.interpolate(Point::new(5.0, 5.0)), Some(15.0));
+/// assert_eq!(heights.interpolate(Point::new(10.0, 10.0)), None);
+/// ```
+pub struct Interpolator<'a, T: Triangulation + ?Sized = Delaunay> {
+    triangulation: &'a T,
+    points: &'a [Point<T::Scalar>],
+    values: &'a [T::Scalar],
+
+    /// Triangle of the last query, the walk to nearby queries starts there
+    hint: Cell<EdgeIndex>,
+}
+
+impl<'a, T: Triangulation + ?Sized> Interpolator<'a, T> {
+    /// Creates an interpolator of the values at the points with the same
+    /// indices.
+    ///
+    /// # Panics
+    /// Panics if there are fewer values than points.
+    pub fn new(triangulation: &'a T, values: &'a [T::Scalar]) -> Interpolator<'a, T> {
+        Interpolator::with_points(triangulation, triangulation.points(), values)
+    }
+
+    /// Like [`new`](Interpolator::new), but blends over `points` instead of
+    /// the points of the triangulation, for example the same points after a
+    /// deformation. They have to be indexed like the triangulation, with its
+    /// triangles counter-clockwise.
+    pub fn with_points(
+        triangulation: &'a T,
+        points: &'a [Point<T::Scalar>],
+        values: &'a [T::Scalar],
+    ) -> Interpolator<'a, T> {
+        assert!(values.len() >= points.len(), "every point needs a value");
+
+        Interpolator {
+            triangulation,
+            points,
+            values,
+            hint: Cell::new(0.into()),
+        }
+    }
+
+    /// Returns the value at the query blended from the three vertices of the
+    /// triangle containing it, or `None` if it lies outside of the hull.
+    ///
+    /// Queries close to the previous one, like samples of a grid taken in
+    /// order, locate their triangle in a few steps.
+    pub fn interpolate(&self, query: Point<T::Scalar>) -> Option<T::Scalar> {
+        let dcel = self.triangulation.dcel();
+        let t = locate_with_hint(dcel, query, self.hint.get(), self.points)?;
+        self.hint.set(t);
+
+        Some(blend(dcel, t, query, self.points, self.values))
+    }
+}
+
+/// Interpolates the values at the vertices of the triangle `t` at the point.
+///
+/// Points on a vertex get its value exactly, and points on an edge are
+/// interpolated along it alone, so both triangles of the edge agree.
+fn blend<T: Scalar>(
+    dcel: &TrianglesDCEL,
+    t: EdgeIndex,
+    point: Point<T>,
+    points: &[Point<T>],
+    values: &[T],
+) -> T {
+    let vertices = dcel.triangle_points(t);
+
+    if let Some(&v) = vertices.iter().find(|&&v| points[v] == point) {
+        return values[v.as_usize()];
+    }
+
+    let coords = dcel.triangle(t, points).barycentric_coords(point);
+
+    if let Some(i) = coords.iter().position(|&w| w == T::ZERO) {
+        let (a, b) = (vertices[(i + 1) % 3], vertices[(i + 2) % 3]);
+        // the same order from either side of the edge
+        let (a, b) = if a.as_usize() < b.as_usize() {
+            (a, b)
+        } else {
+            (b, a)
+        };
+
+        let (pa, pb) = (points[a], points[b]);
+        let along = (point - pa).dot(pb - pa) / pb.distance_sq(pa);
+        let (va, vb) = (values[a.as_usize()], values[b.as_usize()]);
+
+        return va + (vb - va) * along;
+    }
+
+    Triangle::interpolate(
+        coords,
+        [
+            values[vertices[0].as_usize()],
+            values[vertices[1].as_usize()],
+            values[vertices[2].as_usize()],
+        ],
+    )
+}
+
+impl<T: Scalar> Delaunay<T> {
+    /// Returns the value at `query` of the piecewise-linear function with the
+    /// given values at the points, or `None` if it lies outside of the hull.
+    ///
+    /// Queries on a point get its value exactly, and queries on an edge get
+    /// the same value whichever of its triangles is found. For many queries
+    /// an [`Interpolator`] reuses the previous triangle to find the next one.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Delaunay, Point};
+    /// let points = &[
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(10.0, 0.0),
+    ///     Point::new(10.0, 10.0),
+    ///     Point::new(0.0, 10.0),
+    /// ];
+    /// let values = &[1.0, 2.0, 4.0, 3.0];
+    /// let t = Delaunay::new(points).unwrap();
+    ///
+    /// assert_eq!(t.interpolate_linear(Point::new(10.0, 10.0), values), Some(4.0));
+    /// assert_eq!(t.interpolate_linear(Point::new(5.0, 0.0), values), Some(1.5));
+    /// assert_eq!(t.interpolate_linear(Point::new(5.0, 5.0), values), Some(2.5));
+    /// assert_eq!(t.interpolate_linear(Point::new(5.0, 15.0), values), None);
+    /// ```
+    pub fn interpolate_linear(&self, query: Point<T>, values: &[T]) -> Option<T> {
+        interpolate_linear(self, query, &self.points, values)
+    }
+
+    /// Samples the piecewise-linear function with the given values at the
+    /// points onto a `width` × `height` grid covering the rectangle.
+    ///
+    /// Every cell is sampled at its center by barycentric interpolation in
+    /// the triangle containing it. Cells are stored row by row starting at
+    /// the minimum corner of the rectangle, cells outside of the hull are
+    /// `None`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Delaunay, Point, Rect};
+    /// let points = &[
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(10.0, 0.0),
+    ///     Point::new(0.0, 10.0),
+    /// ];
+    /// let t = Delaunay::new(points).unwrap();
+    /// let rect = Rect::new(Point::new(0.0, 0.0), Point::new(10.0, 10.0));
+    /// let grid = t.rasterize(&[0.0, 10.0, 0.0], 2, 2, rect);
+    ///
+    /// assert_eq!(grid, vec![Some(2.5), Some(7.5), Some(2.5), None]);
+    /// ```
+    pub fn rasterize(
+        &self,
+        values: &[T],
+        width: usize,
+        height: usize,
+        rect: Rect<T>,
+    ) -> Vec<Option<T>> {
+        rasterize(self, &self.points, values, width, height, rect)
+    }
+}
+
+/// Like [`Delaunay::interpolate_linear`], for any [`Triangulation`] with the
+/// points passed in. They have to be indexed like the triangulation, with its
+/// triangles counter-clockwise.
+pub fn interpolate_linear<T: Triangulation + ?Sized>(
+    triangulation: &T,
+    query: Point<T::Scalar>,
+    points: &[Point<T::Scalar>],
+    values: &[T::Scalar],
+) -> Option<T::Scalar> {
+    let dcel = triangulation.dcel();
+    let t = locate_from(dcel, 0.into(), query, points)?;
+    Some(blend(dcel, t, query, points, values))
+}
+
+/// Like [`Delaunay::rasterize`], for any [`Triangulation`] with the points
+/// passed in, see [`interpolate_linear`].
+pub fn rasterize<T: Triangulation + ?Sized>(
+    triangulation: &T,
+    points: &[Point<T::Scalar>],
+    values: &[T::Scalar],
+    width: usize,
+    height: usize,
+    rect: Rect<T::Scalar>,
+) -> Vec<Option<T::Scalar>> {
+    let dcel = triangulation.dcel();
+    let min = rect.min;
+    let cell_w = rect.width() / T::Scalar::from_f64(width as f64);
+    let cell_h = rect.height() / T::Scalar::from_f64(height as f64);
+
+    let mut grid = Vec::with_capacity(width * height);
+
+    // neighboring cells mostly fall into the same or adjacent triangles,
+    // so every walk starts at the last triangle found
+    let mut hint = EdgeIndex::from(0);
+
+    for row in 0..height {
+        for col in 0..width {
+            let p = Point::new(
+                min.x + T::Scalar::from_f64(col as f64 + 0.5) * cell_w,
+                min.y + T::Scalar::from_f64(row as f64 + 0.5) * cell_h,
+            );
+
+            let value = locate_from(dcel, hint, p, points).map(|t| {
+                hint = t;
+                blend(dcel, t, p, points, values)
+            });
+
+            grid.push(value);
+        }
+    }
+
+    grid
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+    use crate::testing::random_points;
+
+    #[test]
+    fn linear_field() {
+        let mut points = vec![
+            Point::new(0.0, 0.0),
+            Point::new(100.0, 0.0),
+            Point::new(100.0, 100.0),
+            Point::new(0.0, 100.0),
+        ];
+        points.extend(random_points(300, 17));
+
+        let field = |p: Point| 3.0 * p.x - 2.0 * p.y + 7.0;
+        let values = points.iter().map(|&p| field(p)).collect::<Vec<_>>();
+
+        let t = Delaunay::new(&points).unwrap();
+        let rect = Rect::new(Point::new(-20.0, 0.0), Point::new(120.0, 100.0));
+        let grid = t.rasterize(&values, 70, 40, rect);
+        assert_eq!(grid.len(), 70 * 40);
+
+        for (i, value) in grid.iter().enumerate() {
+            let p = Point::new(
+                -20.0 + (i % 70) as f32 * 2.0 + 1.0,
+                (i / 70) as f32 * 2.5 + 1.25,
+            );
+
+            match value {
+                Some(v) => assert!((v - field(p)).abs() < 1e-2, "{} != {}", v, field(p)),
+                None => assert!(p.x < 0.0 || p.x > 100.0),
+            }
+        }
+
+        assert_eq!(grid.iter().filter(|v| v.is_some()).count(), 50 * 40);
+    }
+
+    #[test]
+    fn interpolator() {
+        let points = random_points(500, 23);
+        let mut rng = rand::rngs::StdRng::seed_from_u64(24);
+
+        let field = |p: Point| 0.5 * p.x + 2.0 * p.y - 30.0;
+        let values = points.iter().map(|&p| field(p)).collect::<Vec<_>>();
+
+        let t = Delaunay::new(&points).unwrap();
+        let interpolator = Interpolator::new(&t, &values);
+
+        for &p in &points {
+            let v = interpolator.interpolate(p).unwrap();
+            assert!((v - field(p)).abs() < 1e-2);
+        }
+
+        for _ in 0..1000 {
+            let q = Point::new(rng.gen_range(-10.0, 110.0), rng.gen_range(-10.0, 110.0));
+
+            match interpolator.interpolate(q) {
+                Some(v) => assert!((v - field(q)).abs() < 1e-2, "{} != {}", v, field(q)),
+                None => assert!(t.locate(q).is_none()),
+            }
+        }
+    }
+
+    #[test]
+    fn interpolate_linear() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(24);
+        let size = 12;
+        let points = (0..size * size)
+            .map(|i| Point::new((i % size) as f32 * 8.0, (i / size) as f32 * 8.0))
+            .collect::<Vec<_>>();
+        let values = (0..points.len())
+            .map(|_| rng.gen_range(-10.0, 10.0))
+            .collect::<Vec<f32>>();
+
+        let t = Delaunay::new(&points).unwrap();
+
+        for (i, &p) in points.iter().enumerate() {
+            assert_eq!(t.interpolate_linear(p, &values), Some(values[i]));
+        }
+
+        // both triangles of an edge give the same value anywhere along it
+        for (e, twin) in t.interior_edges() {
+            let (a, b) = (t.dcel.vertices[e], t.dcel.edge_endpoint(e));
+
+            for &along in &[0.25, 0.5, 0.75] {
+                let q = points[a].lerp(points[b], along);
+                assert_eq!(
+                    blend(&t.dcel, e, q, &points, &values),
+                    blend(&t.dcel, twin, q, &points, &values),
+                    "{:?}",
+                    q
+                );
+            }
+        }
+
+        assert_eq!(t.interpolate_linear(Point::new(-1.0, 4.0), &values), None);
+    }
+}
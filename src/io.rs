@@ -0,0 +1,115 @@
+//! Exporting triangulations
+
+use std::io::{self, Write};
+
+use crate::dcel::TrianglesDCEL;
+use crate::geom::{Point, Scalar};
+
+/// Writes every triangle as a JSON object on its own line.
+///
+/// Each line looks like `{"vertices":[0,1,2],"points":[[x,y],[x,y],[x,y]]}`.
+/// Nothing is buffered besides what `w` does itself, so memory usage stays
+/// constant regardless of the mesh size. Non-finite coordinates are written
+/// as `null`.
+///
+/// # Examples
+/// ```
+/// # use triangulation::{io::write_jsonl, Delaunay, Point};
+/// let points = &[Point::new(10.0, 10.0), Point::new(100.0, 20.0), Point::new(60.0, 120.0)];
+/// let t = Delaunay::new(points).unwrap();
+///
+/// let mut out = Vec::new();
+/// write_jsonl(&mut out, &t.dcel, points).unwrap();
+/// assert_eq!(String::from_utf8(out).unwrap().lines().count(), 1);
+/// ```
+pub fn write_jsonl<W: Write, T: Scalar>(
+    mut w: W,
+    dcel: &TrianglesDCEL,
+    points: &[Point<T>],
+) -> io::Result<()> {
+    for t in (0..dcel.vertices.len()).step_by(3) {
+        let vertices = dcel.triangle_points(t.into());
+
+        write!(
+            w,
+            "{{\"vertices\":[{},{},{}],\"points\":[",
+            vertices[0].as_usize(),
+            vertices[1].as_usize(),
+            vertices[2].as_usize()
+        )?;
+
+        for (i, &v) in vertices.iter().enumerate() {
+            if i > 0 {
+                w.write_all(b",")?;
+            }
+
+            let point = points[v];
+            w.write_all(b"[")?;
+            write_number(&mut w, point.x)?;
+            w.write_all(b",")?;
+            write_number(&mut w, point.y)?;
+            w.write_all(b"]")?;
+        }
+
+        w.write_all(b"]}\n")?;
+    }
+
+    w.flush()
+}
+
+fn write_number<W: Write, T: Scalar>(w: &mut W, value: T) -> io::Result<()> {
+    if value.is_finite() {
+        write!(w, "{}", value)
+    } else {
+        w.write_all(b"null")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::random_points;
+    use crate::Delaunay;
+
+    #[test]
+    fn jsonl() {
+        let points = random_points(300, 5);
+
+        let t = Delaunay::new(&points).unwrap();
+
+        let mut out = Vec::new();
+        write_jsonl(&mut out, &t.dcel, &points).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        assert_eq!(out.lines().count(), t.dcel.num_triangles());
+
+        for (line, tri) in out.lines().zip(t.dcel.triangles(&points)) {
+            let value: serde_json::Value = serde_json::from_str(line).unwrap();
+            let x = value["points"][1][0].as_f64().unwrap() as f32;
+            assert_eq!(x, tri.1.x);
+        }
+    }
+
+    #[test]
+    fn jsonl_f64() {
+        // a coordinate `f32` can't represent
+        let points = [
+            Point::new(500_000.25, 4_649_776.125),
+            Point::new(500_010.0, 4_649_776.0),
+            Point::new(500_000.0, 4_649_786.0),
+        ];
+        let t = crate::Delaunay64::new(&points).unwrap();
+
+        let mut out = Vec::new();
+        write_jsonl(&mut out, &t.dcel, &points).unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&out).unwrap();
+
+        let written = value["points"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|p| Point::new(p[0].as_f64().unwrap(), p[1].as_f64().unwrap()))
+            .collect::<Vec<_>>();
+        assert!(written.contains(&points[0]));
+    }
+}
@@ -0,0 +1,158 @@
+//! Cotangent Laplacian of the triangulation
+
+use crate::dcel::{EdgeIndex, PointIndex};
+use crate::geom::Scalar;
+use crate::{Delaunay, Triangulation};
+
+impl<T: Scalar> Delaunay<T> {
+    /// Returns the cotangent weight of every edge.
+    ///
+    /// The weight of an edge is `(cot α + cot β) / 2`, where `α` and `β` are
+    /// the angles opposite to it. Hull edges have a single opposite angle, so
+    /// their weight is `cot α / 2`. Negative weights are clamped to zero if
+    /// requested with [`DelaunayBuilder::clamp_negative_weights`](crate::DelaunayBuilder::clamp_negative_weights).
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Delaunay, Point};
+    /// let points: &[Point] = &[
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(10.0, 0.0),
+    ///     Point::new(10.0, 10.0),
+    ///     Point::new(0.0, 10.0),
+    /// ];
+    /// let t = Delaunay::new(points).unwrap();
+    /// let weights = t.cotan_weights();
+    ///
+    /// // the diagonal has two right angles in front of it
+    /// assert_eq!(weights.len(), 5);
+    /// assert!(weights.iter().all(|&(_, _, w)| w.abs() < 1e-6 || (w - 0.5).abs() < 1e-6));
+    /// ```
+    pub fn cotan_weights(&self) -> Vec<(PointIndex, PointIndex, T)> {
+        let dcel = self.dcel();
+        let two = T::from_f64(2.0);
+        let mut weights = Vec::new();
+
+        for e in 0..dcel.vertices.len() {
+            let e = EdgeIndex::from(e);
+            let twin = dcel.twin(e);
+
+            if twin.filter(|&twin| twin < e).is_some() {
+                continue;
+            }
+
+            let mut weight = self.opposite_cotan(e) / two;
+
+            if let Some(twin) = twin {
+                weight = weight + self.opposite_cotan(twin) / two;
+            }
+
+            if self.clamp_weights {
+                weight = weight.max(T::ZERO);
+            }
+
+            weights.push((dcel.vertices[e], dcel.edge_endpoint(e), weight));
+        }
+
+        weights
+    }
+
+    /// Returns the cotangent Laplacian as `(row, column, value)` triplets.
+    ///
+    /// Off-diagonal entries are the [cotangent weights](Delaunay::cotan_weights)
+    /// of the edges, stored in both directions, and every point gets
+    /// a diagonal entry equal to minus the sum of its row.
+    pub fn laplacian_triplets(&self) -> Vec<(u32, u32, T)> {
+        let weights = self.cotan_weights();
+        let mut diagonal = vec![T::ZERO; self.points.len()];
+        let mut triplets = Vec::with_capacity(2 * weights.len() + diagonal.len());
+
+        for &(a, b, w) in &weights {
+            let (a, b) = (a.as_usize(), b.as_usize());
+
+            triplets.push((a as u32, b as u32, w));
+            triplets.push((b as u32, a as u32, w));
+
+            diagonal[a] = diagonal[a] - w;
+            diagonal[b] = diagonal[b] - w;
+        }
+
+        triplets.extend(
+            diagonal
+                .iter()
+                .enumerate()
+                .map(|(i, &d)| (i as u32, i as u32, d)),
+        );
+
+        triplets
+    }
+
+    /// Returns the cotangent of the angle opposite to the edge
+    fn opposite_cotan(&self, edge: EdgeIndex) -> T {
+        let (dcel, points) = (self.dcel(), self.points());
+        let a = points[dcel.vertices[edge]];
+        let b = points[dcel.edge_endpoint(edge)];
+        let o = points[dcel.vertices[dcel.prev_edge(edge)]];
+
+        let (u, v) = ((a.x - o.x, a.y - o.y), (b.x - o.x, b.y - o.y));
+        let dot = u.0 * v.0 + u.1 * v.1;
+        let cross = (u.0 * v.1 - u.1 * v.0).abs();
+
+        dot / cross
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geom::Point;
+    use crate::testing::random_points;
+    use crate::DelaunayBuilder;
+
+    #[test]
+    fn row_sums() {
+        let points = random_points(300, 5);
+        let t = Delaunay::new(&points).unwrap();
+
+        let mut sums = vec![0.0f64; points.len()];
+        for (row, _, value) in t.laplacian_triplets() {
+            sums[row as usize] += f64::from(value);
+        }
+
+        assert!(sums.iter().all(|s| s.abs() < 1e-3));
+    }
+
+    #[test]
+    fn linear_function() {
+        let points = random_points(300, 5);
+        let t = Delaunay::new(&points).unwrap();
+        let hull = t.hull();
+
+        let f = |p: Point| 2.0 * f64::from(p.x) - 3.0 * f64::from(p.y) + 1.0;
+
+        let mut result = vec![0.0f64; points.len()];
+        for (row, column, value) in t.laplacian_triplets() {
+            result[row as usize] += f64::from(value) * f(points[column as usize]);
+        }
+
+        for (i, &r) in result.iter().enumerate() {
+            if !hull.contains(&PointIndex::from(i)) {
+                assert!(r.abs() < 1e-2, "point {}: {}", i, r);
+            }
+        }
+    }
+
+    #[test]
+    fn clamped() {
+        let points = random_points(300, 5);
+
+        let t = Delaunay::new(&points).unwrap();
+        assert!(t.cotan_weights().iter().any(|&(_, _, w)| w < 0.0));
+
+        let t = DelaunayBuilder::new()
+            .clamp_negative_weights(true)
+            .build(&points)
+            .unwrap();
+        assert!(t.cotan_weights().iter().all(|&(_, _, w)| w >= 0.0));
+    }
+}
@@ -3,18 +3,44 @@ use core::marker::PhantomData;
 #[cfg(feature = "rayon")]
 use rayon::prelude::*;
 
+mod builder;
+mod clip;
+pub mod contour;
 pub mod dcel;
+mod divconq;
+pub mod dual;
 pub mod geom;
-
+pub mod graphs;
+pub mod interpolate;
+pub mod io;
+mod laplacian;
+pub mod preprocess;
+pub mod query;
+mod remove;
+mod repair;
+pub mod sphere;
+#[cfg(test)]
+mod testing;
+mod tiled;
+pub mod voronoi;
+
+pub use builder::DelaunayBuilder;
 pub use dcel::{EdgeIndex, PointIndex, TrianglesDCEL};
-pub use geom::{Point, Triangle};
+pub use geom::{
+    Circumcircle, Normalization, Point, Point64, Rect, Scalar, Segment, Triangle, Triangle64,
+};
+pub use interpolate::Interpolator;
+pub use remove::RemoveError;
 
 const STACK_CAPACITY: usize = 512;
 
+/// Number of alternative seed edges tried when the first seed is degenerate
+const SEED_ATTEMPTS: usize = 16;
+
 /// Option<usize>, where None is represented by usize::MAX.
 ///
 /// Takes 8 bytes instead of 16.
-#[derive(Clone, Copy, Eq, Hash, Ord)]
+#[derive(Clone, Copy)]
 pub struct OptionIndex<T: Into<usize> + From<usize>>(usize, PhantomData<T>);
 
 impl<T: Into<usize> + From<usize>> OptionIndex<T> {
@@ -22,14 +48,14 @@ impl<T: Into<usize> + From<usize>> OptionIndex<T> {
     #[inline]
     pub fn some(idx: T) -> OptionIndex<T> {
         let idx = idx.into();
-        debug_assert!(idx < std::usize::MAX);
+        debug_assert!(idx < usize::MAX);
         OptionIndex(idx, PhantomData)
     }
 
     /// Returns None value
     #[inline]
     pub fn none() -> OptionIndex<T> {
-        OptionIndex(std::usize::MAX, PhantomData)
+        OptionIndex(usize::MAX, PhantomData)
     }
 
     /// Returns true if it is a `Some` value
@@ -61,9 +87,23 @@ impl<T: Into<usize> + From<usize>> PartialEq for OptionIndex<T> {
     }
 }
 
+impl<T: Into<usize> + From<usize>> Eq for OptionIndex<T> {}
+
 impl<T: Into<usize> + From<usize>> PartialOrd for OptionIndex<T> {
     fn partial_cmp(&self, rhs: &Self) -> Option<core::cmp::Ordering> {
-        self.0.partial_cmp(&rhs.0)
+        Some(self.cmp(rhs))
+    }
+}
+
+impl<T: Into<usize> + From<usize>> Ord for OptionIndex<T> {
+    fn cmp(&self, rhs: &Self) -> core::cmp::Ordering {
+        self.0.cmp(&rhs.0)
+    }
+}
+
+impl<T: Into<usize> + From<usize>> core::hash::Hash for OptionIndex<T> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
     }
 }
 
@@ -73,10 +113,31 @@ impl<T: Into<usize> + From<usize> + std::fmt::Debug> std::fmt::Debug for OptionI
     }
 }
 
+/// Serialized as a plain `Option`
+#[cfg(feature = "serde")]
+impl<T: Into<usize> + From<usize> + serde::Serialize> serde::Serialize for OptionIndex<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.get().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: Into<usize> + From<usize> + serde::Deserialize<'de>> serde::Deserialize<'de>
+    for OptionIndex<T>
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match Option::<T>::deserialize(deserializer)? {
+            Some(idx) => OptionIndex::some(idx),
+            None => OptionIndex::none(),
+        })
+    }
+}
+
 /// Maps angle between `point` and `center` to index in the hash table
-fn angular_hash(point: Point, center: Point, size: usize) -> usize {
+fn angular_hash<T: Scalar>(point: Point<T>, center: Point<f64>, size: usize) -> usize {
+    let center = center.cast::<T>();
     let angle = geom::pseudo_angle(point.x - center.x, point.y - center.y);
-    (angle * size as f32) as usize % size
+    (angle * T::from_f64(size as f64)).to_f64() as usize % size
 }
 
 /// Counter-clockwise convex hull
@@ -93,19 +154,22 @@ struct Hull {
     /// Boundary triangles
     triangles: Vec<OptionIndex<EdgeIndex>>,
 
-    /// Center point for calculating radial hash
-    center: Point,
+    /// Center point for calculating radial hash, in the precision of the
+    /// triangulated points
+    center: Point<f64>,
 
     /// Starting point index
     start: PointIndex,
 }
 
 impl Hull {
-    fn new(seed: [PointIndex; 3], points: &[Point]) -> Hull {
+    fn new<T: Scalar>(seed: [PointIndex; 3], points: &[Point<T>]) -> Hull {
         let capacity = points.len();
         let table_size = (capacity as f32).sqrt().ceil() as usize;
 
-        let center = Triangle(points[seed[0]], points[seed[1]], points[seed[2]]).circumcenter();
+        let center = Triangle(points[seed[0]], points[seed[1]], points[seed[2]])
+            .circumcenter()
+            .cast();
 
         let mut hull = Hull {
             next: vec![0.into(); capacity],
@@ -135,8 +199,71 @@ impl Hull {
         hull
     }
 
+    /// Builds the hull of an already finished triangulation from the edges
+    /// without twins
+    fn from_dcel<T: Scalar>(dcel: &TrianglesDCEL, points: &[Point<T>]) -> Hull {
+        let capacity = points.len();
+        let table_size = (capacity as f32).sqrt().ceil() as usize;
+
+        let mut hull = Hull {
+            next: (0..capacity).map(PointIndex::from).collect(),
+            prev: (0..capacity).map(PointIndex::from).collect(),
+            hash_table: vec![OptionIndex::none(); table_size],
+            triangles: vec![OptionIndex::none(); capacity],
+            start: 0.into(),
+            center: find_center(points).cast(),
+        };
+
+        for e in (0..dcel.vertices.len()).map(EdgeIndex::from) {
+            if dcel.twin(e).is_some() {
+                continue;
+            }
+
+            let (p, q) = (dcel.vertices[e], dcel.edge_endpoint(e));
+            hull.next[p.as_usize()] = q;
+            hull.prev[q.as_usize()] = p;
+            hull.triangles[p.as_usize()] = OptionIndex::some(e);
+            hull.start = p;
+            hull.add_hash(p, points[p]);
+        }
+
+        hull
+    }
+
+    /// Rewrites point indices through `map`, growing the arrays to `len` points
+    fn remap_points(&mut self, map: &[usize], len: usize) {
+        let mut next = (0..len).map(PointIndex::from).collect::<Vec<_>>();
+        let mut prev = next.clone();
+        let mut triangles = vec![OptionIndex::none(); len];
+
+        for (i, &to) in map.iter().enumerate() {
+            next[to] = map[self.next[i].as_usize()].into();
+            prev[to] = map[self.prev[i].as_usize()].into();
+            triangles[to] = self.triangles[i];
+        }
+
+        for entry in &mut self.hash_table {
+            if let Some(p) = entry.get() {
+                *entry = OptionIndex::some(map[p.as_usize()].into());
+            }
+        }
+
+        self.next = next;
+        self.prev = prev;
+        self.triangles = triangles;
+        self.start = map[self.start.as_usize()].into();
+    }
+
+    /// Grows the arrays to `len` points, the new points are not on the hull
+    fn grow(&mut self, len: usize) {
+        let old = self.next.len();
+        self.next.extend((old..len).map(PointIndex::from));
+        self.prev.extend((old..len).map(PointIndex::from));
+        self.triangles.resize(len, OptionIndex::none());
+    }
+
     /// Adds a new point in the hash table
-    fn add_hash(&mut self, index: PointIndex, point: Point) {
+    fn add_hash<T: Scalar>(&mut self, index: PointIndex, point: Point<T>) {
         let table_size = self.hash_table.len();
         self.hash_table[angular_hash(point, self.center, table_size)] = OptionIndex::some(index);
     }
@@ -144,7 +271,11 @@ impl Hull {
     /// Returns the index of the ending point of first convex hull edge visible
     /// from the point and a boolean indicating whether the previous edge may be
     /// visible too
-    fn find_visible_edge(&self, point: Point, points: &[Point]) -> Option<(PointIndex, bool)> {
+    fn find_visible_edge<T: Scalar>(
+        &self,
+        point: Point<T>,
+        points: &[Point<T>],
+    ) -> Option<(PointIndex, bool)> {
         let table_size = self.hash_table.len();
         let hash = angular_hash(point, self.center, table_size);
 
@@ -155,7 +286,11 @@ impl Hull {
             start = self.hash_table[(hash + i) % table_size];
 
             // if e == self.next[e] then it is an empty hash table entry; skip it
-            if start.get().filter(|&e| e != self.next[e.as_usize()]).is_some() {
+            if start
+                .get()
+                .filter(|&e| e != self.next[e.as_usize()])
+                .is_some()
+            {
                 break;
             }
         }
@@ -170,7 +305,8 @@ impl Hull {
             let next = self.next[edge.as_usize()];
             let tri = Triangle(point, points[edge], points[next]);
 
-            if tri.is_left_handed() {
+            // points lying exactly on the line of an edge don't see it
+            if tri.robust_orientation() < 0.0 {
                 // edge is visible, breakin' outta hell
                 break;
             }
@@ -187,19 +323,139 @@ impl Hull {
 
         Some((edge, edge == start))
     }
+
+    /// Finds the hull edge having the point strictly between its endpoints
+    fn find_containing_edge<T: Scalar>(
+        &self,
+        point: Point<T>,
+        points: &[Point<T>],
+    ) -> Option<PointIndex> {
+        let mut edge = self.start;
+
+        loop {
+            let next = self.next[edge.as_usize()];
+            let (a, b) = (points[edge], points[next]);
+
+            if Triangle(point, a, b).robust_orientation() == 0.0 {
+                let along = (point.x - a.x) * (b.x - a.x) + (point.y - a.y) * (b.y - a.y);
+                if along > T::ZERO && along < a.distance_sq(b) {
+                    return Some(edge);
+                }
+            }
+
+            edge = next;
+            if edge == self.start {
+                return None;
+            }
+        }
+    }
+}
+
+/// Sorts the point indices along rows of the bounding box of the points,
+/// going right in even rows and left in odd ones, so consecutive points are
+/// close to each other.
+///
+/// The construction sorts by the distance from the seed circumcenter
+/// instead, which suits adding points outside of the hull. A batch inserted
+/// into an existing triangulation mostly lands inside of it, where the
+/// points of a distance ring lie all around the center, so locating each
+/// one from the previous one would walk across the whole mesh.
+fn sort_in_rows<T: Scalar>(indices: &mut [PointIndex], points: &[Point<T>]) {
+    let subset = indices.iter().map(|&i| points[i]).collect::<Vec<_>>();
+    let rect = match Rect::from_points(&subset) {
+        Some(rect) => rect,
+        None => return,
+    };
+
+    // about two points per row and column on average
+    let rows = ((indices.len() as f64).sqrt() / 2.0).ceil().max(1.0);
+    let height = rect.height().to_f64() / rows;
+
+    let key = |i: PointIndex| {
+        let p = points[i];
+        let row = if height > 0.0 {
+            ((p.y - rect.min.y).to_f64() / height) as i64
+        } else {
+            0
+        };
+        let x = (p.x - rect.min.x).to_f64();
+        (row, if row % 2 == 0 { x } else { -x })
+    };
+
+    indices.sort_by(|&a, &b| key(a).partial_cmp(&key(b)).unwrap());
 }
 
 /// Calculates the median point (arithmetic mean of the coordinates)
-fn find_center(points: &[Point]) -> Point {
-    let (x_sum, y_sum) = points
-        .iter()
-        .fold((0.0, 0.0), |(x, y), point| (x + point.x, y + point.y));
+fn find_center<T: Scalar>(points: &[Point<T>]) -> Point<T> {
+    let (x_sum, y_sum) = points.iter().fold((T::ZERO, T::ZERO), |(x, y), point| {
+        (x + point.x, y + point.y)
+    });
+
+    let len = T::from_f64(points.len() as f64);
+    Point::new(x_sum / len, y_sum / len)
+}
+
+/// Finds a right-handed seed triangle near the center of the point set
+fn find_seed_triangle<T: Scalar>(points: &[Point<T>]) -> Option<(Triangle<T>, [PointIndex; 3])> {
+    let (tri, indices) = find_central_seed_triangle(points)?;
+
+    if tri.robust_orientation() > 0.0 && tri.circumradius_sq().is_finite() {
+        Some((tri, indices))
+    } else {
+        find_fallback_seed_triangle(points, indices[0].as_usize())
+    }
+}
+
+/// Pairs the seed point with its closest neighbours one by one, skipping
+/// degenerate third points, until a proper triangle is found
+fn find_fallback_seed_triangle<T: Scalar>(
+    points: &[Point<T>],
+    seed_idx: usize,
+) -> Option<(Triangle<T>, [PointIndex; 3])> {
+    let seed = points[seed_idx];
+
+    let mut partners = (0..points.len())
+        .filter(|&i| i != seed_idx && points[i].distance_sq(seed) > T::EPSILON)
+        .collect::<Vec<_>>();
+
+    partners.sort_by(|&a, &b| {
+        points[a]
+            .distance_sq(seed)
+            .partial_cmp(&points[b].distance_sq(seed))
+            .unwrap()
+    });
+
+    partners.iter().take(SEED_ATTEMPTS).find_map(|&partner| {
+        let oriented = |i: usize| {
+            let forward = Triangle(seed, points[partner], points[i]);
+            let orientation = forward.robust_orientation();
+
+            if orientation > 0.0 {
+                Some((forward, [seed_idx.into(), partner.into(), i.into()]))
+            } else if orientation < 0.0 {
+                let backward = Triangle(seed, points[i], points[partner]);
+                Some((backward, [seed_idx.into(), i.into(), partner.into()]))
+            } else {
+                None
+            }
+        };
 
-    Point::new(x_sum / points.len() as f32, y_sum / points.len() as f32)
+        (0..points.len())
+            .filter(|&i| i != seed_idx && i != partner)
+            .filter_map(oriented)
+            .filter(|(tri, _)| tri.circumradius_sq().is_finite())
+            .min_by(|(a, _), (b, _)| {
+                a.circumradius_sq()
+                    .partial_cmp(&b.circumradius_sq())
+                    .unwrap()
+            })
+    })
 }
 
-fn find_seed_triangle(points: &[Point]) -> Option<(Triangle, [PointIndex; 3])> {
-    let center = find_center(&points);
+fn find_central_seed_triangle<T: Scalar>(
+    points: &[Point<T>],
+) -> Option<(Triangle<T>, [PointIndex; 3])> {
+    let center = find_center(points);
 
     #[cfg(feature = "rayon")]
     let iter = points.par_iter();
@@ -219,8 +475,8 @@ fn find_seed_triangle(points: &[Point]) -> Option<(Triangle, [PointIndex; 3])> {
         .enumerate()
         .filter(|&(i, _)| i != seed_idx)
         .map(|(i, p)| (i, p, p.distance_sq(seed)))
-        .filter(|(_, _, d)| d.abs() > std::f32::EPSILON)
-        .min_by(|(_, _, a), (_, _, b)| a.partial_cmp(&b).unwrap())?;
+        .filter(|(_, _, d)| d.abs() > T::EPSILON)
+        .min_by(|(_, _, a), (_, _, b)| a.partial_cmp(b).unwrap())?;
 
     let (third_idx, third) = iter
         .cloned()
@@ -245,21 +501,441 @@ fn find_seed_triangle(points: &[Point]) -> Option<(Triangle, [PointIndex; 3])> {
     }
 }
 
-/// Delaunay triangulation
-pub struct Delaunay {
+/// Common interface of the triangulation backends.
+///
+/// Query and analysis code should be written against this trait, so it
+/// doesn't matter which algorithm produced the mesh, like the functions in
+/// [`query`], [`interpolate`] and
+/// [`Voronoi::new`](voronoi::Voronoi::new). The divide and conquer
+/// construction needs no implementation of its own, as
+/// [`Delaunay::new_divconq`] returns a [`Delaunay`] too.
+pub trait Triangulation {
+    /// Coordinate type of the points
+    type Scalar: Scalar;
+
+    /// Returns the underlying DCEL
+    fn dcel(&self) -> &TrianglesDCEL;
+
+    /// Returns the points the DCEL refers to, in the input space
+    fn points(&self) -> &[Point<Self::Scalar>];
+
+    /// Returns the boundary points in counter-clockwise order.
+    fn hull(&self) -> &[PointIndex];
+
+    /// Returns the synthetic points added around the input, see
+    /// [`DelaunayBuilder::bounding_frame`]. There are none by default.
+    fn frame_points(&self) -> &[PointIndex] {
+        &[]
+    }
+
+    /// Returns the triangle containing the given point, or `None` if the point
+    /// lies outside of the triangulation.
+    ///
+    /// Points lying exactly on an edge are considered to be inside.
+    fn locate(&self, point: Point<Self::Scalar>) -> Option<EdgeIndex> {
+        self.locate_with_hint(point, 0.into())
+    }
+
+    /// Like [`Triangulation::locate`], but starts walking from the triangle
+    /// of the `hint` edge.
+    ///
+    /// The walk takes a few steps when the hint is close to the point, so
+    /// passing the previous result makes coherent sequences of queries, like
+    /// points along a path, almost constant time each. A hint which isn't an
+    /// edge of the triangulation is ignored.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Delaunay, Point, Triangulation};
+    /// let points = &[
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(100.0, 0.0),
+    ///     Point::new(100.0, 100.0),
+    ///     Point::new(0.0, 100.0),
+    /// ];
+    /// let t = Delaunay::new(points).unwrap();
+    ///
+    /// let mut hint = t.locate(Point::new(1.0, 1.0)).unwrap();
+    /// for i in 1..100 {
+    ///     let p = Point::new(i as f32, i as f32);
+    ///     hint = t.locate_with_hint(p, hint).unwrap();
+    /// }
+    ///
+    /// assert_eq!(t.locate_with_hint(Point::new(150.0, 50.0), hint), None);
+    /// ```
+    fn locate_with_hint(&self, point: Point<Self::Scalar>, hint: EdgeIndex) -> Option<EdgeIndex> {
+        locate_with_hint(self.dcel(), point, hint, self.points())
+    }
+}
+
+/// Like [`Triangulation::locate_with_hint`], but with the points passed in
+pub(crate) fn locate_with_hint<T: Scalar>(
+    dcel: &TrianglesDCEL,
+    point: Point<T>,
+    hint: EdgeIndex,
+    points: &[Point<T>],
+) -> Option<EdgeIndex> {
+    let start = if hint.as_usize() < dcel.vertices.len() {
+        hint
+    } else {
+        0.into()
+    };
+
+    locate_from(dcel, start, point, points)
+}
+
+/// Returns the boundary edges in counter-clockwise order, beginning at the
+/// first one in the DCEL.
+fn boundary_edges(dcel: &TrianglesDCEL) -> Vec<EdgeIndex> {
+    let start = match (0..dcel.vertices.len())
+        .map(EdgeIndex::from)
+        .find(|&e| dcel.twin(e).is_none())
+    {
+        Some(e) => e,
+        None => return Vec::new(),
+    };
+
+    let mut edges = Vec::new();
+    let mut edge = start;
+
+    loop {
+        edges.push(edge);
+
+        edge = dcel.next_boundary_edge(edge);
+
+        if edge == start || edges.len() > dcel.vertices.len() {
+            break;
+        }
+    }
+
+    edges
+}
+
+/// Walks from the triangle `start` to the triangle containing the point, see
+/// [`Triangulation::locate`].
+fn locate_from<T: Scalar>(
+    dcel: &TrianglesDCEL,
+    start: EdgeIndex,
+    point: Point<T>,
+    points: &[Point<T>],
+) -> Option<EdgeIndex> {
+    if dcel.num_triangles() == 0 {
+        return None;
+    }
+
+    let mut t = dcel.triangle_first_edge(start);
+    let mut came_from = None;
+
+    // visibility walk; the number of steps is bounded to stay safe on
+    // meshes which aren't Delaunay
+    for _ in 0..dcel.num_triangles() {
+        let exit = dcel.triangle_edges(t).iter().cloned().find(|&e| {
+            Some(e) != came_from
+                && Triangle(
+                    points[dcel.vertices[e]],
+                    points[dcel.edge_endpoint(e)],
+                    point,
+                )
+                .is_left_handed()
+        });
+
+        let exit = match exit {
+            Some(e) => e,
+            None => return Some(t),
+        };
+
+        let twin = dcel.twin(exit)?;
+        t = dcel.triangle_first_edge(twin);
+        came_from = Some(twin);
+    }
+
+    dcel.triangles(points)
+        .position(|tri| {
+            !Triangle(tri.0, tri.1, point).is_left_handed()
+                && !Triangle(tri.1, tri.2, point).is_left_handed()
+                && !Triangle(tri.2, tri.0, point).is_left_handed()
+        })
+        .map(|t| EdgeIndex::from(3 * t))
+}
+
+/// Read-only view of the internal hull state of a [`Delaunay`], for
+/// debugging.
+///
+/// It reflects the state at the end of the construction; the hull is not
+/// updated by operations modifying the triangulation afterwards.
+#[derive(Clone, Copy)]
+pub struct HullSnapshot<'a> {
+    hull: &'a Hull,
+}
+
+/// Occupancy of the radial hash table of the hull
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HashTableStats {
+    /// Number of slots
+    pub size: usize,
+
+    /// Slots holding a point which is still on the hull
+    pub live_entries: usize,
+
+    /// Largest number of slots a lookup has to probe to find a live entry
+    pub max_probe_length: usize,
+}
+
+impl<'a> HullSnapshot<'a> {
+    /// Returns true if the point is linked into the hull chain
+    fn contains(&self, p: PointIndex) -> bool {
+        let hull = self.hull;
+        let p = p.as_usize();
+
+        p < hull.next.len()
+            && hull.next[p].as_usize() != p
+            && hull.prev[hull.next[p].as_usize()].as_usize() == p
+            && hull.next[hull.prev[p].as_usize()].as_usize() == p
+    }
+
+    /// Returns the next hull point in counter-clockwise order, or `None` if
+    /// the point is not on the hull.
+    pub fn next(&self, p: PointIndex) -> Option<PointIndex> {
+        if self.contains(p) {
+            Some(self.hull.next[p.as_usize()])
+        } else {
+            None
+        }
+    }
+
+    /// Returns the previous hull point in counter-clockwise order, or `None`
+    /// if the point is not on the hull.
+    pub fn prev(&self, p: PointIndex) -> Option<PointIndex> {
+        if self.contains(p) {
+            Some(self.hull.prev[p.as_usize()])
+        } else {
+            None
+        }
+    }
+
+    /// Returns the boundary edge going out of the hull point, or `None` if
+    /// the point is not on the hull.
+    pub fn boundary_triangle(&self, p: PointIndex) -> Option<EdgeIndex> {
+        if self.contains(p) {
+            self.hull.triangles[p.as_usize()].get()
+        } else {
+            None
+        }
+    }
+
+    /// Returns the point the hull chain starts at
+    pub fn start(&self) -> PointIndex {
+        self.hull.start
+    }
+
+    /// Returns the hull points in counter-clockwise order, beginning at
+    /// [`start`](HullSnapshot::start).
+    pub fn chain(&self) -> Vec<PointIndex> {
+        let mut chain = Vec::new();
+        let mut p = self.hull.start;
+
+        // the length bound keeps the walk finite on a corrupted chain
+        while self.contains(p) && chain.len() < self.hull.next.len() {
+            chain.push(p);
+            p = self.hull.next[p.as_usize()];
+
+            if p == self.hull.start {
+                break;
+            }
+        }
+
+        chain
+    }
+
+    /// Returns the occupancy of the radial hash table
+    pub fn hash_stats(&self) -> HashTableStats {
+        let table = &self.hull.hash_table;
+        let size = table.len();
+
+        // removed points stay in the table, lookups skip them like empty slots
+        let live = table
+            .iter()
+            .map(|entry| entry.get().map_or(false, |p| self.contains(p)))
+            .collect::<Vec<_>>();
+
+        let live_entries = live.iter().filter(|&&l| l).count();
+
+        let max_probe_length = if live_entries == 0 {
+            size
+        } else {
+            // a lookup probes the longest run of dead slots, wrapping around
+            // the end of the table, and then the live slot ending it
+            let last_live = live.iter().rposition(|&l| l).unwrap();
+            let mut run = size - 1 - last_live;
+            let mut max = 0;
+
+            for &l in live.iter() {
+                run = if l { 0 } else { run + 1 };
+                max = max.max(run);
+            }
+
+            max + 1
+        };
+
+        HashTableStats {
+            size,
+            live_entries,
+            max_probe_length,
+        }
+    }
+}
+
+/// Reason why a set of points can't be triangulated
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DelaunayError {
+    /// There are fewer than three points
+    TooFewPoints,
+    /// All points lie on a single line or coincide
+    AllCollinear,
+    /// A point has a NaN or infinite coordinate
+    InvalidCoordinate,
+}
+
+impl std::fmt::Display for DelaunayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            DelaunayError::TooFewPoints => write!(f, "at least three points are needed"),
+            DelaunayError::AllCollinear => write!(f, "all points are collinear"),
+            DelaunayError::InvalidCoordinate => write!(f, "a point has a non-finite coordinate"),
+        }
+    }
+}
+
+impl std::error::Error for DelaunayError {}
+
+/// Delaunay triangulation of points with `f32` or `f64` coordinates
+pub struct Delaunay<T = f32> {
     pub dcel: TrianglesDCEL,
     hull: Hull,
     stack: Vec<EdgeIndex>,
+    flips: usize,
+    normalization: Option<Normalization<T>>,
+    clamp_weights: bool,
+    frame: Vec<PointIndex>,
+    frame_positions: Vec<Point<T>>,
+
+    /// Points of the hull chain in counter-clockwise order, kept for
+    /// [`Triangulation::hull`]
+    hull_points: Vec<PointIndex>,
+
+    /// Triangulated points in the input space
+    points: Vec<Point<T>>,
+
+    /// Normalized copy of `points` if there is a normalization
+    normalized: Vec<Point<T>>,
 }
 
-impl Delaunay {
+/// Delaunay triangulation of points with `f64` coordinates
+///
+/// # Examples
+/// ```
+/// # use triangulation::{Delaunay64, Point64};
+/// // UTM coordinates a meter apart, which `f32` can't tell apart
+/// let points = &[
+///     Point64::new(500_000.0, 4_649_776.0),
+///     Point64::new(500_001.0, 4_649_776.0),
+///     Point64::new(500_000.0, 4_649_777.0),
+///     Point64::new(500_001.0, 4_649_777.5),
+/// ];
+/// let t = Delaunay64::new(points).unwrap();
+/// assert_eq!(t.dcel.num_triangles(), 2);
+/// ```
+pub type Delaunay64 = Delaunay<f64>;
+
+impl<T: Scalar> Delaunay<T> {
     /// Triangulates a set of given points, if it is possible.
-    pub fn new(points: &[Point]) -> Option<Delaunay> {
-        let (seed, seed_indices) = find_seed_triangle(points)?;
+    ///
+    /// Uses the default [`DelaunayBuilder`] settings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Delaunay, DelaunayError, Point};
+    /// let points = &[Point::new(0.0, 0.0), Point::new(1.0, 1.0), Point::new(2.0, 2.0)];
+    /// assert_eq!(Delaunay::new(points).err(), Some(DelaunayError::AllCollinear));
+    /// ```
+    pub fn new(points: &[Point<T>]) -> Result<Delaunay<T>, DelaunayError> {
+        DelaunayBuilder::new().build(points)
+    }
+
+    /// Triangulates a set of given points, returning `None` on failure like
+    /// [`new`](Delaunay::new) used to.
+    #[deprecated(note = "use `Delaunay::new`, which reports why triangulating failed")]
+    pub fn try_new(points: &[Point<T>]) -> Option<Delaunay<T>> {
+        Delaunay::new(points).ok()
+    }
+
+    /// Triangulates a set of given points, skipping points whose coordinates
+    /// both differ by at most `tolerance` from an already kept point.
+    ///
+    /// A shortcut for [`DelaunayBuilder::tolerance`] with otherwise default
+    /// settings, for input with a known precision like measured points.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Delaunay, Point};
+    /// let points = &[
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(10.0, 0.0),
+    ///     Point::new(0.0, 10.0),
+    ///     Point::new(10.01, 0.01),
+    /// ];
+    /// let t = Delaunay::new_with_tolerance(points, 0.1).unwrap();
+    /// assert_eq!(t.dcel.num_triangles(), 1);
+    ///
+    /// let t = Delaunay::new(points).unwrap();
+    /// assert_eq!(t.dcel.num_triangles(), 2);
+    /// ```
+    pub fn new_with_tolerance(
+        points: &[Point<T>],
+        tolerance: T,
+    ) -> Result<Delaunay<T>, DelaunayError> {
+        DelaunayBuilder::new()
+            .tolerance(tolerance.to_f64())
+            .build(points)
+    }
+
+    /// Triangulates points given as coordinate pairs, e.g. streamed from a
+    /// file, without the caller collecting them first.
+    ///
+    /// The triangulation keeps the points, available from
+    /// [`points`](Delaunay::points). Fails with
+    /// [`DelaunayError::InvalidCoordinate`] if a coordinate is NaN or
+    /// infinite.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Delaunay, DelaunayError, Point};
+    /// let coords = (0..10).map(|i| (i as f32, (i * i % 7) as f32));
+    /// let t = Delaunay::from_coords(coords).unwrap();
+    ///
+    /// assert_eq!(t.points().len(), 10);
+    /// assert_eq!(t.points()[3], Point::new(3.0, 2.0));
+    ///
+    /// let coords = vec![(0.0, 0.0), (1.0, 0.0), (0.0, std::f32::NAN)];
+    /// assert_eq!(Delaunay::from_coords(coords).err(), Some(DelaunayError::InvalidCoordinate));
+    /// ```
+    pub fn from_coords<I: IntoIterator<Item = (T, T)>>(
+        coords: I,
+    ) -> Result<Delaunay<T>, DelaunayError> {
+        let points = coords.into_iter().map(Point::from).collect::<Vec<_>>();
+        Delaunay::new(&points)
+    }
+
+    fn triangulate(points: &[Point<T>]) -> Result<Delaunay<T>, DelaunayError> {
+        if points.len() < 3 {
+            return Err(DelaunayError::TooFewPoints);
+        }
+
+        let (seed, seed_indices) = find_seed_triangle(points).ok_or(DelaunayError::AllCollinear)?;
         let seed_circumcenter = seed.circumcenter();
 
         let mut indices = (0..points.len())
-            .map(|i| PointIndex::from(i))
+            .map(PointIndex::from)
             .filter(|&i| i != seed_indices[0] && i != seed_indices[1] && i != seed_indices[2])
             .collect::<Vec<_>>();
 
@@ -282,11 +958,19 @@ impl Delaunay {
             dcel: TrianglesDCEL::with_capacity(max_triangles),
             hull: Hull::new(seed_indices, points),
             stack: Vec::with_capacity(STACK_CAPACITY),
+            flips: 0,
+            normalization: None,
+            clamp_weights: false,
+            frame: Vec::new(),
+            frame_positions: Vec::new(),
+            hull_points: Vec::new(),
+            points: Vec::new(),
+            normalized: Vec::new(),
         };
 
         delaunay.dcel.add_triangle(seed_indices);
 
-        let mut prev_point: Option<Point> = None;
+        let mut prev_point: Option<Point<T>> = None;
 
         for &i in &indices {
             let point = points[i];
@@ -301,15 +985,486 @@ impl Delaunay {
             prev_point = Some(point);
         }
 
-        Some(delaunay)
+        delaunay.update_hull_points();
+        Ok(delaunay)
+    }
+
+    /// Wraps a finished triangulation of `points` built by other means
+    fn from_dcel(dcel: TrianglesDCEL, points: &[Point<T>]) -> Delaunay<T> {
+        let mut delaunay = Delaunay {
+            hull: Hull::from_dcel(&dcel, points),
+            dcel,
+            stack: Vec::with_capacity(STACK_CAPACITY),
+            flips: 0,
+            normalization: None,
+            clamp_weights: false,
+            frame: Vec::new(),
+            frame_positions: Vec::new(),
+            hull_points: Vec::new(),
+            points: Vec::new(),
+            normalized: Vec::new(),
+        };
+
+        delaunay.update_hull_points();
+        delaunay.set_points(points.to_vec());
+        delaunay
+    }
+
+    /// Returns the points of the triangulation: the input points, followed
+    /// by the frame points and the inserted ones.
+    pub fn points(&self) -> &[Point<T>] {
+        &self.points
+    }
+
+    /// Returns the triangle of the given edge, see [`TrianglesDCEL::triangle`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Delaunay, Point};
+    /// let points = &[Point::new(0.0, 0.0), Point::new(0.0, 10.0), Point::new(10.0, 0.0)];
+    /// let t = Delaunay::new(points).unwrap();
+    ///
+    /// assert_eq!(t.triangle(0.into()).area(), 50.0);
+    /// assert_eq!(t.triangles().count(), 1);
+    /// ```
+    pub fn triangle(&self, t: EdgeIndex) -> Triangle<T> {
+        self.dcel.triangle(t, &self.points)
+    }
+
+    /// Returns an iterator over all triangles, see
+    /// [`TrianglesDCEL::triangles`].
+    pub fn triangles(&self) -> impl Iterator<Item = Triangle<T>> + '_ {
+        self.dcel.triangles(&self.points)
+    }
+
+    /// Returns the triangle containing the given point, or `None` if the point
+    /// lies outside of the triangulation.
+    ///
+    /// Points lying exactly on an edge are considered to be inside.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Delaunay, Point};
+    /// let points = &[Point::new(0.0, 0.0), Point::new(0.0, 10.0), Point::new(10.0, 0.0)];
+    /// let t = Delaunay::new(points).unwrap();
+    ///
+    /// assert_eq!(t.locate(Point::new(1.0, 1.0)), Some(0.into()));
+    /// assert_eq!(t.locate(Point::new(10.0, 10.0)), None);
+    /// ```
+    pub fn locate(&self, point: Point<T>) -> Option<EdgeIndex> {
+        locate_from(&self.dcel, 0.into(), point, &self.points)
+    }
+
+    /// Sets the owned points, normalizing them if needed
+    fn set_points(&mut self, points: Vec<Point<T>>) {
+        self.normalized = match self.normalization {
+            Some(normalization) => points.iter().map(|&p| normalization.apply(p)).collect(),
+            None => Vec::new(),
+        };
+        self.points = points;
+    }
+
+    /// Inserts a new point into the triangulation, returning its index.
+    ///
+    /// The point is appended to [`points`](Delaunay::points). Points
+    /// coinciding with an already triangulated one are stored, but not
+    /// connected, the same way [`new`](Delaunay::new) skips duplicates.
+    /// Fails with [`DelaunayError::InvalidCoordinate`] if a coordinate is NaN
+    /// or infinite, leaving the triangulation unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Delaunay, Point};
+    /// let mut t = Delaunay::new(&[
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(100.0, 0.0),
+    ///     Point::new(0.0, 100.0),
+    /// ]).unwrap();
+    ///
+    /// assert_eq!(t.insert(Point::new(20.0, 20.0)), Ok(3.into()));
+    /// assert_eq!(t.insert(Point::new(100.0, 100.0)), Ok(4.into()));
+    /// assert_eq!(t.dcel.num_triangles(), 4);
+    /// ```
+    pub fn insert(&mut self, point: Point<T>) -> Result<PointIndex, DelaunayError> {
+        if !point.x.is_finite() || !point.y.is_finite() {
+            return Err(DelaunayError::InvalidCoordinate);
+        }
+
+        let index = PointIndex::from(self.points.len());
+
+        self.points.push(point);
+        if let Some(normalization) = self.normalization {
+            self.normalized.push(normalization.apply(point));
+        }
+
+        self.hull.grow(self.points.len());
+        self.dcel.invalidate_revmap();
+
+        self.with_points(|delaunay, points| delaunay.insert_point(index, 0.into(), points));
+        self.update_hull_points();
+
+        Ok(index)
+    }
+
+    /// Inserts a batch of points, like calling [`insert`](Delaunay::insert)
+    /// for each of them, but faster.
+    ///
+    /// The points are appended to [`points`](Delaunay::points) in the given
+    /// order, so they get consecutive indices. They are inserted in rows
+    /// across their bounding box instead, not by distance like in
+    /// [`new`](Delaunay::new), so that every point is found quickly by
+    /// walking from the triangle of the previous one, while `insert` walks
+    /// from the first triangle. Duplicates are skipped the same way as by
+    /// `insert`. Fails with [`DelaunayError::InvalidCoordinate`] if any
+    /// coordinate is NaN or infinite, before inserting any of the points.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Delaunay, Point};
+    /// let mut t = Delaunay::new(&[
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(100.0, 0.0),
+    ///     Point::new(0.0, 100.0),
+    /// ]).unwrap();
+    ///
+    /// t.insert_all(&[Point::new(20.0, 20.0), Point::new(100.0, 100.0), Point::new(20.0, 20.0)])
+    ///     .unwrap();
+    /// assert_eq!(t.points().len(), 6);
+    /// assert_eq!(t.dcel.num_triangles(), 4);
+    /// ```
+    pub fn insert_all(&mut self, new_points: &[Point<T>]) -> Result<(), DelaunayError> {
+        if new_points
+            .iter()
+            .any(|p| !p.x.is_finite() || !p.y.is_finite())
+        {
+            return Err(DelaunayError::InvalidCoordinate);
+        }
+
+        let first = self.points.len();
+
+        self.points.extend_from_slice(new_points);
+        if let Some(normalization) = self.normalization {
+            self.normalized
+                .extend(new_points.iter().map(|&p| normalization.apply(p)));
+        }
+
+        self.hull.grow(self.points.len());
+        self.dcel.invalidate_revmap();
+        self.dcel.vertices.reserve(6 * new_points.len());
+
+        self.with_points(|delaunay, points| {
+            let mut indices = (first..points.len())
+                .map(PointIndex::from)
+                .collect::<Vec<_>>();
+
+            sort_in_rows(&mut indices, points);
+
+            let mut hint = 0.into();
+            for i in indices {
+                hint = delaunay.insert_point(i, hint, points);
+            }
+        });
+        self.update_hull_points();
+
+        Ok(())
+    }
+
+    /// Runs `f` with the points the triangulation is computed on, which are
+    /// the normalized ones if there is a normalization.
+    ///
+    /// The points are moved out for the duration of the call, as every step
+    /// of changing the triangulation borrows it mutably.
+    fn with_points<R>(&mut self, f: impl FnOnce(&mut Delaunay<T>, &[Point<T>]) -> R) -> R {
+        let mut points = std::mem::take(match self.normalization {
+            Some(_) => &mut self.normalized,
+            None => &mut self.points,
+        });
+
+        let result = f(self, &points);
+
+        std::mem::swap(
+            match self.normalization {
+                Some(_) => &mut self.normalized,
+                None => &mut self.points,
+            },
+            &mut points,
+        );
+
+        result
+    }
+
+    /// Inserts a point at any position, inside or outside of the hull,
+    /// locating it starting from the triangle `hint`.
+    ///
+    /// Returns a triangle near the point, to be used as the next hint.
+    fn insert_point(
+        &mut self,
+        index: PointIndex,
+        hint: EdgeIndex,
+        points: &[Point<T>],
+    ) -> EdgeIndex {
+        let point = points[index];
+
+        let t = match locate_from(&self.dcel, hint, point, points) {
+            Some(t) => t,
+            None => {
+                self.add_point(index, points);
+                return self.hull.triangles[index.as_usize()].get().unwrap_or(hint);
+            }
+        };
+
+        if self
+            .dcel
+            .triangle_points(t)
+            .iter()
+            .any(|&v| points[v].approx_eq(point))
+        {
+            return t;
+        }
+
+        let on_edge = self.dcel.triangle_edges(t).iter().cloned().find(|&e| {
+            let (a, b) = (
+                points[self.dcel.vertices[e]],
+                points[self.dcel.edge_endpoint(e)],
+            );
+            Triangle(a, b, point).robust_orientation() == 0.0
+        });
+
+        let from_index = match on_edge {
+            Some(e) if self.dcel.twin(e).is_none() => {
+                self.split_hull_edge(self.dcel.vertices[e], index, points);
+                return t;
+            }
+            Some(e) => self.dcel.split_edge(e, index)[1],
+            None => self.dcel.split_triangle(t, index)[0] + 2,
+        };
+
+        let opposite = dcel::TrianglesAroundPoint::new(&self.dcel, from_index)
+            .map(|e| self.dcel.next_edge(e))
+            .collect::<Vec<_>>();
+
+        // splitting moves boundary edges into the new triangles
+        for &e in &opposite {
+            if self.dcel.twin(e).is_none() {
+                self.hull.triangles[self.dcel.vertices[e].as_usize()] = OptionIndex::some(e);
+            }
+        }
+
+        for e in opposite {
+            self.legalize(e, points);
+        }
+
+        t
+    }
+
+    /// Rewrites point indices through `map`, so that the triangulation of a
+    /// subset refers to the points of the original set of `len` points.
+    fn remap_points(&mut self, map: &[usize], len: usize) {
+        for v in &mut self.dcel.vertices {
+            *v = map[v.as_usize()].into();
+        }
+
+        self.hull.remap_points(map, len);
+        self.update_hull_points();
+    }
+
+    /// Returns the transform which was applied to the input points before
+    /// triangulating them, if any.
+    ///
+    /// Coordinates computed by the library are always returned in the input
+    /// space, so this is mostly useful for diagnostics.
+    pub fn normalization(&self) -> Option<Normalization<T>> {
+        self.normalization
+    }
+
+    /// Returns a read-only view of the hull state left by the construction.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Delaunay, Point};
+    /// let points = &[
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(100.0, 0.0),
+    ///     Point::new(100.0, 100.0),
+    ///     Point::new(0.0, 100.0),
+    ///     Point::new(40.0, 60.0),
+    /// ];
+    /// let t = Delaunay::new(points).unwrap();
+    /// let hull = t.hull_snapshot();
+    ///
+    /// assert_eq!(hull.chain().len(), 4);
+    /// assert_eq!(hull.next(4.into()), None);
+    /// ```
+    pub fn hull_snapshot(&self) -> HullSnapshot<'_> {
+        HullSnapshot { hull: &self.hull }
+    }
+
+    /// Rebuilds the hull chain and its boundary triangles from the DCEL after
+    /// the triangles were changed by something else than insertion.
+    pub(crate) fn resync_hull(&mut self) {
+        for p in self.hull_snapshot().chain() {
+            self.hull.next[p.as_usize()] = p;
+        }
+
+        let edges = boundary_edges(&self.dcel);
+
+        for (i, &e) in edges.iter().enumerate() {
+            let p = self.dcel.vertices[e];
+            let q = self.dcel.vertices[edges[(i + 1) % edges.len()]];
+
+            self.hull.next[p.as_usize()] = q;
+            self.hull.prev[q.as_usize()] = p;
+            self.hull.triangles[p.as_usize()] = OptionIndex::some(e);
+        }
+
+        let start = self.hull.start;
+        if !edges.iter().any(|&e| self.dcel.vertices[e] == start) {
+            if let Some(&e) = edges.first() {
+                self.hull.start = self.dcel.vertices[e];
+            }
+        }
+
+        self.update_hull_points();
+    }
+
+    /// Refreshes the hull points returned by [`Triangulation::hull`] after
+    /// the hull chain changed
+    fn update_hull_points(&mut self) {
+        self.hull_points = self.hull_snapshot().chain();
     }
 
-    fn add_point(&mut self, index: PointIndex, points: &[Point]) {
+    /// Returns the synthetic corner points added by
+    /// [`DelaunayBuilder::bounding_frame`], empty if there is no frame.
+    ///
+    /// The frame points are numbered right after the input points, and are
+    /// part of [`points`](Delaunay::points) until
+    /// [`strip_frame`](Delaunay::strip_frame) is called.
+    pub fn frame_points(&self) -> &[PointIndex] {
+        &self.frame
+    }
+
+    /// Returns the positions of the [`frame_points`](Delaunay::frame_points),
+    /// in the same order.
+    ///
+    /// These are copies of the entries of [`points`](Delaunay::points) at the
+    /// frame indices, which already contains them.
+    pub fn frame_positions(&self) -> &[Point<T>] {
+        &self.frame_positions
+    }
+
+    /// Removes the bounding frame, leaving the triangulation of the input
+    /// points alone.
+    ///
+    /// The frame points are removed from [`points`](Delaunay::points), so
+    /// points inserted after the frame move down to close the gap. The
+    /// points are triangulated again, as the frame points may be connected to
+    /// the input hull in ways which can't be undone locally. If the remaining
+    /// points are collinear the triangulation becomes empty.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Delaunay, DelaunayBuilder, Point};
+    /// let points = &[
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(100.0, 0.0),
+    ///     Point::new(100.0, 100.0),
+    ///     Point::new(0.0, 100.0),
+    ///     Point::new(40.0, 60.0),
+    /// ];
+    ///
+    /// let mut t = DelaunayBuilder::new().bounding_frame(0.5).build(points).unwrap();
+    /// assert_eq!(t.frame_points().len(), 4);
+    /// assert_eq!(t.dcel.num_triangles(), 2 * 9 - 4 - 2);
+    ///
+    /// t.insert(Point::new(60.0, 20.0)).unwrap();
+    /// t.strip_frame();
+    /// assert_eq!(t.points().len(), 6);
+    /// assert_eq!(t.points()[5], Point::new(60.0, 20.0));
+    /// assert_eq!(t.dcel.num_triangles(), 6);
+    /// ```
+    pub fn strip_frame(&mut self) {
+        if self.frame.is_empty() {
+            return;
+        }
+
+        let mut is_frame = vec![false; self.points.len()];
+        for &p in &self.frame {
+            is_frame[p.as_usize()] = true;
+        }
+
+        let mut used = vec![false; self.points.len()];
+        for &v in &self.dcel.vertices {
+            used[v.as_usize()] = true;
+        }
+
+        let mut points = Vec::with_capacity(self.points.len() - self.frame.len());
+        let mut indices = Vec::new();
+        let mut subset = Vec::new();
+
+        for (i, &point) in self.points.iter().enumerate() {
+            if is_frame[i] {
+                continue;
+            }
+
+            if used[i] {
+                indices.push(points.len());
+                subset.push(match self.normalization {
+                    Some(normalization) => normalization.apply(point),
+                    None => point,
+                });
+            }
+
+            points.push(point);
+        }
+
+        match Delaunay::triangulate(&subset) {
+            Ok(mut stripped) => {
+                stripped.remap_points(&indices, points.len());
+                stripped.normalization = self.normalization;
+                stripped.clamp_weights = self.clamp_weights;
+                stripped.set_points(points);
+                *self = stripped;
+            }
+            Err(_) => {
+                self.dcel = TrianglesDCEL::with_capacity(0);
+                self.hull_points.clear();
+                self.frame.clear();
+                self.frame_positions.clear();
+                self.set_points(points);
+            }
+        }
+    }
+
+    /// Returns the number of edge flips performed while legalizing the
+    /// triangulation.
+    ///
+    /// Flips done by later insertions are counted too. Edges between
+    /// cocircular points, like the diagonals of a regular grid, are never
+    /// flipped, as the circle test is strict, so a grid takes fewer flips
+    /// than as many uniformly random points.
+    pub fn flip_count(&self) -> usize {
+        self.flips
+    }
+
+    /// Returns true if the triangulation forms a single connected region.
+    ///
+    /// A disconnected result indicates a construction bug or degenerate input.
+    pub fn is_connected(&self) -> bool {
+        self.dcel.is_connected()
+    }
+
+    fn add_point(&mut self, index: PointIndex, points: &[Point<T>]) {
         let point = points[index];
 
         let (mut start, should_walk_back) = match self.hull.find_visible_edge(point, points) {
             Some(v) => v,
-            None => return,
+            None => {
+                if let Some(edge) = self.hull.find_containing_edge(point, points) {
+                    self.split_hull_edge(edge, index, points);
+                }
+
+                return;
+            }
         };
 
         let mut end = self.hull.next[start.as_usize()];
@@ -329,7 +1484,7 @@ impl Delaunay {
         loop {
             let next = self.hull.next[end.as_usize()];
             let tri = Triangle(point, points[next], points[end]);
-            if !tri.is_right_handed() {
+            if tri.robust_orientation() <= 0.0 {
                 break;
             }
 
@@ -351,7 +1506,7 @@ impl Delaunay {
             loop {
                 let prev = self.hull.prev[start.as_usize()];
                 let tri = Triangle(point, points[start], points[prev]);
-                if !tri.is_right_handed() {
+                if tri.robust_orientation() <= 0.0 {
                     break;
                 }
 
@@ -383,7 +1538,63 @@ impl Delaunay {
         self.hull.add_hash(start, points[start]);
     }
 
-    fn add_triangle(&mut self, vertices: [PointIndex; 3], halfedges: [OptionIndex<EdgeIndex>; 3]) -> EdgeIndex {
+    /// Inserts a point lying exactly on the hull edge going from `start`,
+    /// splitting the triangle inside of the edge in two.
+    ///
+    /// ```text
+    ///          o                     o
+    ///         / \                   /|\
+    ///        /   \       =>        / | \
+    ///       /  e  \               /  |  \
+    ///  start-------end      start--index--end
+    /// ```
+    fn split_hull_edge(&mut self, start: PointIndex, index: PointIndex, points: &[Point<T>]) {
+        let end = self.hull.next[start.as_usize()];
+        let e = match self.hull.triangles[start.as_usize()].get() {
+            Some(e) => e,
+            None => return,
+        };
+
+        let n = self.dcel.next_edge(e);
+        let p = self.dcel.prev_edge(e);
+        let o = self.dcel.vertices[p];
+        let twin_n = self.dcel.twin(n);
+
+        // the old triangle becomes [start, index, o], the new one is [index, end, o]
+        self.dcel.vertices[n] = index;
+        self.dcel.unlink(n);
+
+        let t = self.add_triangle(
+            [index, end, o],
+            [
+                OptionIndex::none(),
+                twin_n.map_or(OptionIndex::none(), OptionIndex::some),
+                OptionIndex::some(n),
+            ],
+        );
+
+        if twin_n.is_none() {
+            self.hull.triangles[end.as_usize()] = OptionIndex::some(t + 1);
+        }
+
+        self.hull.triangles[start.as_usize()] = OptionIndex::some(e);
+
+        self.hull.next[start.as_usize()] = index;
+        self.hull.next[index.as_usize()] = end;
+        self.hull.prev[end.as_usize()] = index;
+        self.hull.prev[index.as_usize()] = start;
+        self.hull.add_hash(index, points[index]);
+
+        // flips move the hull edge going out of the new point, like in add_point
+        self.hull.triangles[index.as_usize()] = OptionIndex::some(self.legalize(t + 1, points));
+        self.legalize(p, points);
+    }
+
+    fn add_triangle(
+        &mut self,
+        vertices: [PointIndex; 3],
+        halfedges: [OptionIndex<EdgeIndex>; 3],
+    ) -> EdgeIndex {
         let t = self.dcel.add_triangle(vertices);
 
         for (i, &halfedge) in halfedges.iter().enumerate() {
@@ -395,7 +1606,7 @@ impl Delaunay {
         t
     }
 
-    fn legalize(&mut self, index: EdgeIndex, points: &[Point]) -> EdgeIndex {
+    fn legalize(&mut self, index: EdgeIndex, points: &[Point<T>]) -> EdgeIndex {
         self.stack.push(index);
 
         let mut output = 0.into();
@@ -404,49 +1615,16 @@ impl Delaunay {
             let ar = self.dcel.prev_edge(a);
             output = ar;
 
-            let b = match self.dcel.twin(a) {
-                Some(v) => v,
+            let b = match self.flip_illegal(a, points) {
+                Some(b) => b,
                 None => continue,
             };
 
             let br = self.dcel.next_edge(b);
             let bl = self.dcel.prev_edge(b);
 
-            /* if the pair of triangles doesn't satisfy the Delaunay condition
-             * (p1 is inside the circumcircle of [p0, pl, pr]), flip them,
-             * then do the same check/flip recursively for the new pair of triangles
-             *
-             *           pl                    pl
-             *          /||\                  /  \
-             *       al/ || \bl            al/    \a
-             *        /  ||  \              /      \
-             *       /  a||b  \    flip    /___ar___\
-             *     p0\   ||   /p1   =>   p0\---bl---/p1
-             *        \  ||  /              \      /
-             *       ar\ || /br             b\    /br
-             *          \||/                  \  /
-             *           pr                    pr
-             */
-
-            let [p0, pr, pl] = self.dcel.triangle_points(ar);
-            let p1 = self.dcel.triangle_points(bl)[0];
-
-            let illegal = Triangle(points[p0], points[pr], points[pl]).in_circumcircle(points[p1]);
-
-            if !illegal {
-                continue;
-            }
-
-            self.dcel.vertices[a] = p1;
-            self.dcel.vertices[b] = p0;
-
-            let hbl = self.dcel.twin(bl);
-
-            self.dcel.link_option(a, hbl);
-            self.dcel.link_option(b, self.dcel.twin(ar));
-            self.dcel.link(ar, bl);
-
-            if hbl.is_none() {
+            // `a` took over the twin of `bl`, so it's on the hull if `bl` was
+            if self.dcel.twin(a).is_none() {
                 let mut edge: EdgeIndex = self.hull.start.as_usize().into();
 
                 loop {
@@ -457,7 +1635,9 @@ impl Delaunay {
 
                     edge = self.hull.next[edge].as_usize().into();
 
-                    if edge.as_usize() == self.hull.start.as_usize() || edge.as_usize() == self.hull.next[edge].as_usize() {
+                    if edge.as_usize() == self.hull.start.as_usize()
+                        || edge.as_usize() == self.hull.next[edge].as_usize()
+                    {
                         break;
                     }
                 }
@@ -473,4 +1653,635 @@ impl Delaunay {
 
         output
     }
+
+    /// Flips the given edges, and the ones around every flipped edge, until
+    /// they all satisfy the Delaunay condition.
+    ///
+    /// Unlike after an insertion, the flipped pairs of triangles don't share
+    /// a new point, so all four outer edges are checked again. The hull chain
+    /// isn't updated, see [`resync_hull`](Delaunay::resync_hull).
+    pub(crate) fn legalize_all(&mut self, mut stack: Vec<EdgeIndex>, points: &[Point<T>]) {
+        while let Some(a) = stack.pop() {
+            if let Some(b) = self.flip_illegal(a, points) {
+                stack.extend_from_slice(&[a, self.dcel.next_edge(a), b, self.dcel.next_edge(b)]);
+            }
+        }
+    }
+
+    /// Flips the edge `a` if the pair of triangles around it doesn't satisfy
+    /// the Delaunay condition.
+    ///
+    /// Returns the former twin of `a`, or `None` if nothing was flipped.
+    fn flip_illegal(&mut self, a: EdgeIndex, points: &[Point<T>]) -> Option<EdgeIndex> {
+        let b = self.dcel.twin(a)?;
+
+        let ar = self.dcel.prev_edge(a);
+        let bl = self.dcel.prev_edge(b);
+
+        /* if the pair of triangles doesn't satisfy the Delaunay condition
+         * (p1 is inside the circumcircle of [p0, pl, pr]), flip them
+         *
+         *           pl                    pl
+         *          /||\                  /  \
+         *       al/ || \bl            al/    \a
+         *        /  ||  \              /      \
+         *       /  a||b  \    flip    /___ar___\
+         *     p0\   ||   /p1   =>   p0\---bl---/p1
+         *        \  ||  /              \      /
+         *       ar\ || /br             b\    /br
+         *          \||/                  \  /
+         *           pr                    pr
+         */
+
+        let [p0, pr, pl] = self.dcel.triangle_points(ar);
+        let p1 = self.dcel.triangle_points(bl)[0];
+
+        let illegal = Triangle(points[p0], points[pr], points[pl]).in_circumcircle(points[p1]);
+
+        // the circle test isn't exact, so never flip into an inverted or
+        // degenerate pair of triangles
+        let convex = || {
+            Triangle(points[p0], points[pr], points[p1]).robust_orientation() > 0.0
+                && Triangle(points[p0], points[p1], points[pl]).robust_orientation() > 0.0
+        };
+
+        if !illegal || !convex() {
+            return None;
+        }
+
+        self.flips += 1;
+        self.dcel.flip(a);
+
+        Some(b)
+    }
+}
+
+impl<T: Scalar> Triangulation for Delaunay<T> {
+    type Scalar = T;
+
+    fn dcel(&self) -> &TrianglesDCEL {
+        &self.dcel
+    }
+
+    fn points(&self) -> &[Point<T>] {
+        &self.points
+    }
+
+    /// Returns the hull points in counter-clockwise order, kept up to date
+    /// with the hull chain of the construction, without scanning the
+    /// triangles.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Delaunay, Point, Triangulation};
+    /// let points = &[
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(100.0, 0.0),
+    ///     Point::new(100.0, 100.0),
+    ///     Point::new(100.0, 100.0),
+    ///     Point::new(0.0, 100.0),
+    ///     Point::new(40.0, 60.0),
+    /// ];
+    /// let t = Delaunay::new(points).unwrap();
+    /// let hull = t.hull();
+    ///
+    /// assert_eq!(hull.len(), 4);
+    /// assert!(!hull.contains(&5.into()));
+    /// ```
+    fn hull(&self) -> &[PointIndex] {
+        &self.hull_points
+    }
+
+    fn frame_points(&self) -> &[PointIndex] {
+        &self.frame
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{assert_delaunay, edge_set, random_points};
+    use std::collections::HashSet;
+
+    fn square() -> Vec<Point> {
+        vec![
+            Point::new(0.0, 0.0),
+            Point::new(100.0, 0.0),
+            Point::new(100.0, 100.0),
+            Point::new(0.0, 100.0),
+            Point::new(40.0, 60.0),
+        ]
+    }
+
+    #[test]
+    fn hull_snapshot() {
+        let points = random_points(1000, 11);
+
+        let t = Delaunay::new(&points).unwrap();
+        let snapshot = t.hull_snapshot();
+        let chain = snapshot.chain();
+
+        // the boundary walk of the DCEL, rotated to the same start
+        let mut walk = t.hull().to_vec();
+        let offset = walk.iter().position(|&p| p == snapshot.start()).unwrap();
+        walk.rotate_left(offset);
+        assert_eq!(chain, walk);
+
+        for (i, &p) in chain.iter().enumerate() {
+            assert_eq!(snapshot.next(p), Some(chain[(i + 1) % chain.len()]));
+            assert_eq!(snapshot.prev(chain[(i + 1) % chain.len()]), Some(p));
+
+            let e = snapshot.boundary_triangle(p).unwrap();
+            assert_eq!(t.dcel.vertices[e], p);
+            assert_eq!(t.dcel.twin(e), None);
+        }
+
+        let on_hull = chain.iter().collect::<HashSet<_>>();
+        for p in (0..points.len())
+            .map(PointIndex::from)
+            .filter(|p| !on_hull.contains(p))
+        {
+            assert_eq!(snapshot.next(p), None);
+            assert_eq!(snapshot.boundary_triangle(p), None);
+        }
+
+        let stats = snapshot.hash_stats();
+        assert_eq!(stats.size, 32);
+        assert!(stats.live_entries > 0 && stats.live_entries <= chain.len());
+        assert!(stats.max_probe_length >= 1 && stats.max_probe_length <= stats.size);
+    }
+
+    #[test]
+    fn insert() {
+        let points = random_points(300, 8);
+
+        let mut t = Delaunay::new(&points[..3]).unwrap();
+        for (i, &p) in points.iter().enumerate().skip(3) {
+            assert_eq!(t.insert(p), Ok(i.into()));
+        }
+
+        assert_eq!(t.points(), &points[..]);
+        assert_delaunay(&t, &points);
+        assert_eq!(edge_set(&t), edge_set(&Delaunay::new(&points).unwrap()));
+    }
+
+    #[test]
+    fn insert_on_edges() {
+        let points = (0..49)
+            .map(|i| Point::new((i % 7) as f32 * 10.0, (i / 7) as f32 * 10.0))
+            .collect::<Vec<_>>();
+
+        // the corners first, then every other point lies on an edge or is a duplicate
+        let mut t = Delaunay::new(&[points[0], points[6], points[48], points[42]]).unwrap();
+        for &p in &points {
+            t.insert(p).unwrap();
+        }
+
+        let used = t.dcel.vertices.iter().collect::<HashSet<_>>();
+        assert_eq!(used.len(), 49);
+        assert_eq!(t.dcel.num_triangles(), 2 * 49 - 2 - 24);
+        assert_delaunay(&t, t.points());
+    }
+
+    #[test]
+    fn insert_normalized() {
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(9);
+        let offset = 1_000_000.0;
+        let points = (0..200)
+            .map(|_| {
+                Point::new(
+                    offset + rng.gen_range(0.0, 1000.0),
+                    offset + rng.gen_range(0.0, 1000.0),
+                )
+            })
+            .collect::<Vec<Point>>();
+
+        let mut t = Delaunay::new(&points[..100]).unwrap();
+        assert!(t.normalization().is_some());
+
+        for &p in &points[100..] {
+            t.insert(p).unwrap();
+        }
+
+        assert_eq!(t.points(), &points[..]);
+        assert_eq!(edge_set(&t), edge_set(&Delaunay::new(&points).unwrap()));
+    }
+
+    #[test]
+    fn insert_all() {
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(10);
+
+        for &offset in &[0.0, 1_000_000.0] {
+            // the batch covers a larger area than the first points and
+            // repeats some of them
+            let mut points = (0..1000)
+                .map(|i| {
+                    let size = if i < 300 { 500.0 } else { 1000.0 };
+                    Point::new(
+                        offset + rng.gen_range(0.0, size),
+                        offset + rng.gen_range(0.0, size),
+                    )
+                })
+                .collect::<Vec<_>>();
+            points.copy_within(..100, 900);
+
+            let mut t = Delaunay::new(&points[..300]).unwrap();
+            t.insert_all(&points[300..]).unwrap();
+
+            assert_eq!(t.points(), &points[..]);
+            assert_eq!(edge_set(&t), edge_set(&Delaunay::new(&points).unwrap()));
+            assert_delaunay(&t, t.points());
+        }
+    }
+
+    #[test]
+    fn insert_all_matches_insert() {
+        use rand::{Rng, SeedableRng};
+
+        let points = random_points(500, 11);
+        let mut rng = rand::rngs::StdRng::seed_from_u64(12);
+
+        // new points inside and outside of the hull, mixed with copies of
+        // already triangulated vertices
+        let mut batch = (0..500)
+            .map(|_| Point::new(rng.gen_range(-50.0, 150.0), rng.gen_range(-50.0, 150.0)))
+            .collect::<Vec<_>>();
+        for i in 0..50 {
+            batch[10 * i] = points[7 * i];
+        }
+
+        let mut single = Delaunay::new(&points).unwrap();
+        for &p in &batch {
+            single.insert(p).unwrap();
+        }
+
+        let mut bulk = Delaunay::new(&points).unwrap();
+        bulk.insert_all(&batch).unwrap();
+
+        assert_eq!(bulk.points(), single.points());
+        assert_eq!(edge_set(&bulk), edge_set(&single));
+
+        // the copies are stored, but not connected
+        let used = bulk.dcel.vertices.iter().collect::<HashSet<_>>();
+        assert_eq!(used.len(), 950);
+        assert!((0..50).all(|i| !used.contains(&PointIndex::from(500 + 10 * i))));
+    }
+
+    #[test]
+    fn insert_non_finite() {
+        let points = random_points(100, 12);
+        let mut t = Delaunay::new(&points).unwrap();
+        let edges = edge_set(&t);
+
+        for &p in &[Point::new(f32::NAN, 50.0), Point::new(50.0, f32::INFINITY)] {
+            assert_eq!(t.insert(p), Err(DelaunayError::InvalidCoordinate));
+            assert_eq!(
+                t.insert_all(&[Point::new(50.0, 50.0), p]),
+                Err(DelaunayError::InvalidCoordinate)
+            );
+        }
+
+        assert_eq!(t.points(), &points[..]);
+        assert_eq!(edge_set(&t), edges);
+    }
+
+    #[test]
+    fn hull() {
+        let points = square();
+        let t = Delaunay::new(&points).unwrap();
+
+        let hull = t.hull();
+        assert_eq!(hull.len(), 4);
+        assert!(!hull.contains(&4.into()));
+
+        for i in 0..hull.len() {
+            let a = points[hull[i]];
+            let b = points[hull[(i + 1) % hull.len()]];
+            assert!(Triangle(a, b, points[4]).is_right_handed());
+        }
+    }
+
+    #[test]
+    fn hull_chain() {
+        let mut points = random_points(300, 10);
+
+        // duplicates of hull points are skipped
+        let copies = points.clone();
+        points.extend(copies);
+
+        let mut t = Delaunay::new(&points).unwrap();
+        for p in &[Point::new(-50.0, 50.0), Point::new(50.0, 150.0)] {
+            t.insert(*p).unwrap();
+        }
+
+        let hull = t.hull();
+        assert_eq!(hull[0], t.hull_snapshot().start());
+
+        // same cycle as the boundary of the DCEL
+        let mut boundary = boundary_edges(&t.dcel)
+            .into_iter()
+            .map(|e| t.dcel.vertices[e])
+            .collect::<Vec<_>>();
+        let first = boundary.iter().position(|&p| p == hull[0]).unwrap();
+        boundary.rotate_left(first);
+        assert_eq!(hull, boundary);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_roundtrip() {
+        let index = OptionIndex::some(EdgeIndex::from(3));
+        assert_eq!(serde_json::to_string(&index).unwrap(), "3");
+        assert_eq!(
+            serde_json::to_string(&OptionIndex::<EdgeIndex>::none()).unwrap(),
+            "null"
+        );
+
+        let halfedges: Vec<OptionIndex<EdgeIndex>> = serde_json::from_str("[null, 3]").unwrap();
+        assert_eq!(halfedges, vec![OptionIndex::none(), index]);
+
+        let tri = Triangle(
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.5),
+            Point::new(0.25, 2.0),
+        );
+        let json = serde_json::to_string(&tri).unwrap();
+        assert_eq!(serde_json::from_str::<Triangle>(&json).unwrap(), tri);
+    }
+
+    #[test]
+    fn locate() {
+        let points = square();
+        let t = Delaunay::new(&points).unwrap();
+
+        for &p in &[
+            Point::new(10.0, 10.0),
+            Point::new(90.0, 50.0),
+            Point::new(40.0, 60.0),
+        ] {
+            let tri = t.dcel.triangle(t.locate(p).unwrap(), &points);
+            assert!(!Triangle(tri.0, tri.1, p).is_left_handed());
+            assert!(!Triangle(tri.1, tri.2, p).is_left_handed());
+            assert!(!Triangle(tri.2, tri.0, p).is_left_handed());
+        }
+
+        assert_eq!(t.locate(Point::new(-10.0, 50.0)), None);
+        assert_eq!(t.locate(Point::new(50.0, 150.0)), None);
+    }
+
+    #[test]
+    fn locate_with_hint() {
+        let points = random_points(500, 21);
+        let t = Delaunay::new(&points).unwrap();
+
+        let contains = |e: EdgeIndex, p: Point| {
+            let tri = t.dcel.triangle(e, &points);
+            !Triangle(tri.0, tri.1, p).is_left_handed()
+                && !Triangle(tri.1, tri.2, p).is_left_handed()
+                && !Triangle(tri.2, tri.0, p).is_left_handed()
+        };
+
+        // a path across the square and out of it on the right
+        let mut hint = EdgeIndex::from(0);
+        for i in 0..200 {
+            let p = Point::new(i as f32 * 0.6, 50.0 + (i as f32 * 0.1).sin() * 20.0);
+
+            match t.locate_with_hint(p, hint) {
+                Some(e) => {
+                    assert!(contains(e, p));
+                    hint = e;
+                }
+                None => assert_eq!(t.locate(p), None),
+            }
+        }
+
+        // starting from a hull triangle
+        let snapshot = t.hull_snapshot();
+        let hull = snapshot.boundary_triangle(snapshot.start()).unwrap();
+        assert_eq!(t.locate_with_hint(Point::new(-5.0, 50.0), hull), None);
+        assert_eq!(t.locate_with_hint(Point::new(50.0, 105.0), hull), None);
+
+        let inside = Point::new(50.0, 50.0);
+        assert!(contains(t.locate_with_hint(inside, hull).unwrap(), inside));
+
+        let bogus = EdgeIndex::from(usize::MAX - 1);
+        assert!(contains(t.locate_with_hint(inside, bogus).unwrap(), inside));
+    }
+
+    #[test]
+    fn flip_count() {
+        let size = 100;
+        let uniform = random_points(size * size, 1337);
+        let grid = (0..size * size)
+            .map(|i| Point::new((i % size) as f32, (i / size) as f32))
+            .collect::<Vec<_>>();
+
+        let seed = &uniform[..3];
+        assert_eq!(Delaunay::new(seed).unwrap().flip_count(), 0);
+
+        let uniform_flips = Delaunay::new(&uniform).unwrap().flip_count();
+        let grid_flips = Delaunay::new(&grid).unwrap().flip_count();
+        assert!(grid_flips > 0);
+        assert!(grid_flips < uniform_flips);
+        assert_eq!(Delaunay::new(&grid).unwrap().flip_count(), grid_flips);
+
+        let mut t = Delaunay::new(&uniform).unwrap();
+        let before = t.flip_count();
+        t.insert(Point::new(50.0, 50.0)).unwrap();
+        assert!(t.flip_count() > before);
+    }
+
+    #[test]
+    fn double_precision() {
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(11);
+        let offset = 1e7;
+
+        // spacing of 1e-3 is far below f32 resolution at this offset
+        let points = (0..500)
+            .map(|_| {
+                Point::new(
+                    offset + rng.gen_range(0.0, 1.0),
+                    offset + rng.gen_range(0.0, 1.0),
+                )
+            })
+            .collect::<Vec<Point<f64>>>();
+
+        let t = Delaunay::new(&points).unwrap();
+        assert!(t.normalization().is_none());
+        assert!(t.is_connected());
+        assert_eq!(
+            t.dcel.num_triangles(),
+            2 * points.len() - 2 - t.hull().len()
+        );
+        assert!(t
+            .dcel
+            .triangles(&points)
+            .all(|t| t.robust_orientation() > 0.0));
+
+        for e in (0..t.dcel.vertices.len()).map(EdgeIndex::from) {
+            if let Some(twin) = t.dcel.twin(e) {
+                let opposite = points[t.dcel.vertices[t.dcel.prev_edge(twin)]];
+                assert!(!t.dcel.triangle(e, &points).in_circumcircle(opposite));
+            }
+        }
+    }
+
+    #[test]
+    fn grid_with_midpoints() {
+        // lots of collinear and cocircular quadruples, offset so that the
+        // coordinates aren't small integers
+        let offset = 1000.0;
+        let mut points: Vec<Point> = Vec::new();
+
+        for y in 0..40 {
+            for x in 0..40 {
+                let (x, y) = (offset + x as f32, offset + y as f32);
+                points.push(Point::new(x, y));
+
+                if x < offset + 39.0 && y < offset + 39.0 {
+                    points.push(Point::new(x + 0.5, y));
+                    points.push(Point::new(x, y + 0.5));
+                    points.push(Point::new(x + 0.5, y + 0.5));
+                }
+            }
+        }
+
+        let t = Delaunay::new(&points).unwrap();
+        assert!(t.is_connected());
+        assert!(t
+            .dcel
+            .triangles(&points)
+            .all(|t| t.robust_orientation() > 0.0));
+
+        // no gaps or overlaps, the triangles cover the square exactly
+        let area: f64 = t
+            .dcel
+            .triangles(&points)
+            .map(|t| t.robust_orientation() / 2.0)
+            .sum();
+        assert_eq!(area, 39.0 * 39.0);
+    }
+
+    #[test]
+    fn degenerate_seed_fallback() {
+        // nearly collinear points near the centroid, the triangle picked first
+        // has zero area unless the orientation is computed exactly
+        let points: [Point; 6] = [
+            Point::new(0.6491425, -0.60084885),
+            Point::new(0.6482928, -0.59966964),
+            Point::new(-0.33756948, 0.7684628),
+            Point::new(-100000.0, -100000.0),
+            Point::new(100000.0, -100000.0),
+            Point::new(0.0, 100000.0),
+        ];
+
+        // the exact predicates of the robust feature don't get this wrong
+        let (central, _) = find_central_seed_triangle(&points).unwrap();
+        #[cfg(not(feature = "robust"))]
+        assert!(!central.is_right_handed());
+        #[cfg(feature = "robust")]
+        assert!(central.is_right_handed());
+
+        let (seed, _) = find_seed_triangle(&points).unwrap();
+        assert!(seed.robust_orientation() > 0.0);
+
+        let t = Delaunay::new(&points).unwrap();
+        assert!(t.is_connected());
+        assert!(t
+            .dcel
+            .triangles(&points)
+            .all(|t| t.robust_orientation() > 0.0));
+    }
+
+    #[test]
+    fn collinear() {
+        let points = (0..10)
+            .map(|i| Point::new(i as f32, 2.0 * i as f32))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            Delaunay::new(&points).err(),
+            Some(DelaunayError::AllCollinear)
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let points = square();
+        assert_eq!(
+            Delaunay::new(&points[..2]).err(),
+            Some(DelaunayError::TooFewPoints)
+        );
+        assert_eq!(
+            Delaunay::new(&[points[0]; 5]).err(),
+            Some(DelaunayError::AllCollinear)
+        );
+
+        let mut points = points;
+        points[2].y = f32::NAN;
+        assert_eq!(
+            Delaunay::new(&points).err(),
+            Some(DelaunayError::InvalidCoordinate)
+        );
+
+        points[2].y = f32::INFINITY;
+        assert_eq!(
+            Delaunay::new(&points).err(),
+            Some(DelaunayError::InvalidCoordinate)
+        );
+    }
+
+    #[test]
+    fn collinear_with_hull_edge() {
+        // the first four points lie exactly on the line y = 3x, far enough
+        // apart in magnitude for the plain orientation test to round wrong
+        let points = [
+            Point::new(4263.926, 12791.777),
+            Point::new(60.325745, 180.97723),
+            Point::new(19344.281, 58032.844),
+            Point::new(4015.8125, 12047.4375),
+            Point::new(49.9, 88.0),
+            Point::new(66.5, 27.6),
+            Point::new(91.3, 21.9),
+        ];
+
+        let t = Delaunay::triangulate(&points).unwrap();
+
+        assert!(t
+            .dcel
+            .triangles(&points)
+            .all(|t| t.robust_orientation() > 0.0));
+        assert_eq!(
+            t.dcel.vertices.iter().collect::<HashSet<_>>().len(),
+            points.len()
+        );
+    }
+
+    #[test]
+    fn point_on_hull_edge() {
+        let mut points = square();
+        let mut t = Delaunay::triangulate(&points).unwrap();
+
+        let (a, b) = (points[0], points[1]);
+        points.push(Point::new((a.x + b.x) / 2.0, (a.y + b.y) / 2.0));
+
+        // grow the hull to fit the new point
+        let map = (0..5).collect::<Vec<_>>();
+        t.remap_points(&map, points.len());
+        t.add_point(5.into(), &points);
+        t.update_hull_points();
+
+        assert_eq!(t.dcel.num_triangles(), 5);
+        assert!(t
+            .dcel
+            .triangles(&points)
+            .all(|t| t.robust_orientation() > 0.0));
+        assert_eq!(t.dcel.halfedges.iter().filter(|e| e.is_some()).count(), 10);
+        assert_eq!(t.hull().len(), 5);
+    }
 }
@@ -0,0 +1,144 @@
+//! Input preprocessing utilities
+
+use std::collections::HashMap;
+
+use crate::geom::{Point, Scalar};
+
+/// Keeps at most one point per `min_spacing`-radius neighborhood.
+///
+/// Points are visited in input order and kept only if no already kept point
+/// lies closer than `min_spacing`. Returns the kept points and their indices
+/// in the original slice. A `min_spacing` which isn't positive keeps all
+/// points.
+///
+/// # Examples
+/// ```
+/// # use triangulation::{preprocess::thin, Point};
+/// let points = [Point::new(0.0, 0.0), Point::new(0.5, 0.0), Point::new(2.0, 0.0)];
+/// let (kept, indices) = thin(&points, 1.0);
+/// assert_eq!(kept, vec![points[0], points[2]]);
+/// assert_eq!(indices, vec![0, 2]);
+/// ```
+pub fn thin<T: Scalar>(points: &[Point<T>], min_spacing: T) -> (Vec<Point<T>>, Vec<usize>) {
+    thin_in_order(points, min_spacing, 0..points.len())
+}
+
+/// Like [`thin`], but prefers points with the highest `scores` in every
+/// neighborhood.
+///
+/// Ties are broken by input order. Points with a NaN score are preferred
+/// last. The result is ordered by index.
+///
+/// # Examples
+/// ```
+/// # use triangulation::{preprocess::thin_by_score, Point};
+/// let points = [Point::new(0.0, 0.0), Point::new(0.5, 0.0), Point::new(2.0, 0.0)];
+/// let (_, indices) = thin_by_score(&points, 1.0, &[0.1, 0.9, 0.5]);
+/// assert_eq!(indices, vec![1, 2]);
+/// ```
+pub fn thin_by_score<T: Scalar>(
+    points: &[Point<T>],
+    min_spacing: T,
+    scores: &[f32],
+) -> (Vec<Point<T>>, Vec<usize>) {
+    assert_eq!(points.len(), scores.len());
+
+    let mut order = (0..points.len()).collect::<Vec<_>>();
+    order.sort_by(|&a, &b| {
+        let (a, b) = (scores[a], scores[b]);
+        a.is_nan()
+            .cmp(&b.is_nan())
+            .then_with(|| Scalar::total_cmp(&b, &a))
+    });
+
+    let (_, mut indices) = thin_in_order(points, min_spacing, order);
+    indices.sort();
+
+    (indices.iter().map(|&i| points[i]).collect(), indices)
+}
+
+/// Keeps at most one of the points whose coordinates both differ by at most
+/// `epsilon`, like measurements of the same point.
+///
+/// Points are visited in input order and kept only if no already kept point
+/// is that close. Returns the kept points and their indices in the original
+/// slice. An `epsilon` which isn't positive keeps all points.
+///
+/// # Examples
+/// ```
+/// # use triangulation::{preprocess::dedup, Point};
+/// let points = [Point::new(0.0, 0.0), Point::new(0.1, -0.1), Point::new(0.1, 0.3)];
+/// let (kept, indices) = dedup(&points, 0.2);
+/// assert_eq!(kept, vec![points[0], points[2]]);
+/// assert_eq!(indices, vec![0, 2]);
+/// ```
+pub fn dedup<T: Scalar>(points: &[Point<T>], epsilon: T) -> (Vec<Point<T>>, Vec<usize>) {
+    keep_apart(points, epsilon, 0..points.len(), |a, b| {
+        a.approx_eq_eps(b, epsilon)
+    })
+}
+
+fn thin_in_order<T, I>(points: &[Point<T>], min_spacing: T, order: I) -> (Vec<Point<T>>, Vec<usize>)
+where
+    T: Scalar,
+    I: IntoIterator<Item = usize>,
+{
+    let spacing_sq = min_spacing * min_spacing;
+    keep_apart(points, min_spacing, order, |a, b| {
+        a.distance_sq(b) < spacing_sq
+    })
+}
+
+/// Keeps the points in the given order which are not `close` to a kept one,
+/// `close` points being at most `min_spacing` apart in both coordinates.
+///
+/// Keeps all points if `min_spacing` isn't positive.
+fn keep_apart<T, I, F>(
+    points: &[Point<T>],
+    min_spacing: T,
+    order: I,
+    close: F,
+) -> (Vec<Point<T>>, Vec<usize>)
+where
+    T: Scalar,
+    I: IntoIterator<Item = usize>,
+    F: Fn(Point<T>, Point<T>) -> bool,
+{
+    if min_spacing <= T::ZERO || min_spacing.is_nan() {
+        return order.into_iter().map(|i| (points[i], i)).unzip();
+    }
+
+    // the casts saturate for cells too far out to count, which merges them
+    // into the outermost cells, so neighbors stay within one cell
+    let cell = |p: Point<T>| {
+        (
+            (p.x / min_spacing).to_f64().floor() as i64,
+            (p.y / min_spacing).to_f64().floor() as i64,
+        )
+    };
+
+    let mut grid: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+    let mut kept = Vec::new();
+    let mut indices = Vec::new();
+
+    for i in order {
+        let point = points[i];
+        let (cx, cy) = cell(point);
+
+        let crowded = (cx.saturating_sub(1)..=cx.saturating_add(1))
+            .flat_map(|x| (cy.saturating_sub(1)..=cy.saturating_add(1)).map(move |y| (x, y)))
+            .filter_map(|c| grid.get(&c))
+            .flatten()
+            .any(|&j| close(points[j], point));
+
+        if crowded {
+            continue;
+        }
+
+        grid.entry((cx, cy)).or_default().push(i);
+        kept.push(point);
+        indices.push(i);
+    }
+
+    (kept, indices)
+}
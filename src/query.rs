@@ -0,0 +1,1431 @@
+//! Spatial queries and measures on any [`Triangulation`]
+//!
+//! The [`Delaunay`] methods use the owned points. The free functions take the
+//! points separately, so they also work on points stored elsewhere: `points`
+//! has to be indexed like the triangulation, with its triangles
+//! counter-clockwise. [`conflict_triangles`], [`nearest_point`],
+//! [`k_nearest`] and [`vertices_within`] also rely on the triangulation being
+//! Delaunay in them, which holds for the input points but not necessarily for
+//! points moved afterwards.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+
+use crate::dcel::{EdgeIndex, PointIndex, TrianglesAroundPoint, TrianglesDCEL};
+use crate::geom::{Point, Rect, Scalar, Triangle};
+use crate::{locate_from, Delaunay, Triangulation};
+
+impl<T: Scalar> Delaunay<T> {
+    /// Returns all triangles whose circumcircle contains the given point.
+    ///
+    /// This is the cavity which would be retriangulated when inserting the
+    /// point with the Bowyer-Watson algorithm. The triangles are found by
+    /// locating the point and flood-filling over the neighbors in conflict.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Delaunay, Point};
+    /// let points = &[
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(100.0, 0.0),
+    ///     Point::new(100.0, 100.0),
+    ///     Point::new(0.0, 100.0),
+    /// ];
+    /// let t = Delaunay::new(points).unwrap();
+    /// assert_eq!(t.conflict_triangles(Point::new(50.0, 50.0)).len(), 2);
+    /// assert_eq!(t.conflict_triangles(Point::new(50.0, 500.0)).len(), 0);
+    /// ```
+    pub fn conflict_triangles(&self, point: Point<T>) -> Vec<EdgeIndex> {
+        conflict_triangles(self, point, &self.points)
+    }
+
+    /// Returns the triangles overlapping or touching the rectangle.
+    ///
+    /// Floods the triangulation from the triangle containing the center of
+    /// the rectangle, or from the hull if the center is outside of it, so the
+    /// cost depends on the number of triangles found rather than on the size
+    /// of the triangulation.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Delaunay, Point, Rect};
+    /// let points = &[
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(100.0, 0.0),
+    ///     Point::new(100.0, 100.0),
+    ///     Point::new(0.0, 100.0),
+    ///     Point::new(50.0, 50.0),
+    /// ];
+    /// let t = Delaunay::new(points).unwrap();
+    ///
+    /// let rect = Rect::new(Point::new(10.0, 40.0), Point::new(20.0, 60.0));
+    /// assert_eq!(t.triangles_in_rect(rect).len(), 1);
+    ///
+    /// let rect = Rect::new(Point::new(-10.0, -10.0), Point::new(200.0, 5.0));
+    /// assert_eq!(t.triangles_in_rect(rect).len(), 3);
+    /// ```
+    pub fn triangles_in_rect(&self, rect: Rect<T>) -> Vec<EdgeIndex> {
+        triangles_in_rect(self, rect, &self.points)
+    }
+
+    /// Returns the triangles overlapping or touching the circle.
+    ///
+    /// Floods the triangulation from the triangle containing the center the
+    /// same way [`triangles_in_rect`](Delaunay::triangles_in_rect) does.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Delaunay, Point};
+    /// let points = &[
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(100.0, 0.0),
+    ///     Point::new(100.0, 100.0),
+    ///     Point::new(0.0, 100.0),
+    ///     Point::new(50.0, 50.0),
+    /// ];
+    /// let t = Delaunay::new(points).unwrap();
+    ///
+    /// assert_eq!(t.triangles_within(Point::new(15.0, 50.0), 5.0).len(), 1);
+    /// assert_eq!(t.triangles_within(Point::new(50.0, 50.0), 5.0).len(), 4);
+    /// assert_eq!(t.triangles_within(Point::new(50.0, -10.0), 5.0).len(), 0);
+    /// ```
+    pub fn triangles_within(&self, center: Point<T>, radius: T) -> Vec<EdgeIndex> {
+        triangles_within(self, center, radius, &self.points)
+    }
+
+    /// Returns the input points within `radius` of the location, boundary
+    /// included, in no particular order.
+    ///
+    /// Expands over the Delaunay graph from the nearest point, which works
+    /// for locations outside of the hull too. Every point in the circle has
+    /// a neighbor closer to the center, so the points in it are connected
+    /// and only they and their neighbors are visited.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Delaunay, Point};
+    /// let points = &[
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(100.0, 0.0),
+    ///     Point::new(100.0, 100.0),
+    ///     Point::new(0.0, 100.0),
+    ///     Point::new(40.0, 60.0),
+    /// ];
+    /// let t = Delaunay::new(points).unwrap();
+    ///
+    /// let mut near = t.vertices_within(Point::new(10.0, 90.0), 60.0);
+    /// near.sort_by_key(|p| p.as_usize());
+    /// assert_eq!(near, vec![3.into(), 4.into()]);
+    ///
+    /// assert_eq!(t.vertices_within(Point::new(-10.0, -10.0), 20.0), vec![0.into()]);
+    /// ```
+    pub fn vertices_within(&self, center: Point<T>, radius: T) -> Vec<PointIndex> {
+        vertices_within(self, center, radius, &self.points)
+    }
+
+    /// Returns the input point closest to the given location.
+    ///
+    /// Starts at the triangle containing the location, or at the hull for
+    /// locations outside of it, and walks greedily to neighbors closer to
+    /// the location. The walk can't get stuck in the Delaunay graph, so the
+    /// result is exact. A location with a NaN coordinate has no nearest
+    /// point.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Delaunay, Point};
+    /// let points = &[
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(100.0, 0.0),
+    ///     Point::new(100.0, 100.0),
+    ///     Point::new(0.0, 100.0),
+    ///     Point::new(40.0, 60.0),
+    /// ];
+    /// let t = Delaunay::new(points).unwrap();
+    /// assert_eq!(t.nearest_point(Point::new(45.0, 50.0)), Some(4.into()));
+    /// assert_eq!(t.nearest_point(Point::new(150.0, -20.0)), Some(1.into()));
+    /// ```
+    pub fn nearest_point(&self, point: Point<T>) -> Option<PointIndex> {
+        nearest_point(self, point, &self.points)
+    }
+
+    /// Returns the `k` input points closest to the given location, sorted by
+    /// distance.
+    ///
+    /// Expands a best-first search over the Delaunay graph from the nearest
+    /// point. Every other point has a neighbor closer to the location, so
+    /// no point is skipped.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Delaunay, Point};
+    /// let points = &[
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(100.0, 0.0),
+    ///     Point::new(100.0, 100.0),
+    ///     Point::new(0.0, 100.0),
+    ///     Point::new(40.0, 60.0),
+    /// ];
+    /// let t = Delaunay::new(points).unwrap();
+    /// let nearest = t.k_nearest(Point::new(10.0, 90.0), 3);
+    /// assert_eq!(nearest, vec![3.into(), 4.into(), 0.into()]);
+    /// ```
+    pub fn k_nearest(&self, point: Point<T>, k: usize) -> Vec<PointIndex> {
+        k_nearest(self, point, k, &self.points)
+    }
+
+    /// Returns the triangles crossed by the segment from `a` to `b`, in the
+    /// order they are crossed.
+    ///
+    /// The walk starts at the triangle containing `a` and steps through the
+    /// edge the segment leaves by, or around the vertex it passes through,
+    /// until the triangle containing `b` is reached or the segment leaves
+    /// the hull. Where the segment runs along an edge only the triangle on
+    /// one side of it is returned. The iterator is empty if `a` lies outside
+    /// of the hull.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Delaunay, Point};
+    /// let points = &[
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(100.0, 0.0),
+    ///     Point::new(100.0, 100.0),
+    ///     Point::new(0.0, 100.0),
+    /// ];
+    /// let t = Delaunay::new(points).unwrap();
+    ///
+    /// let (a, b) = (Point::new(10.0, 50.0), Point::new(90.0, 50.0));
+    /// assert_eq!(t.triangles_along_segment(a, b).count(), 2);
+    /// assert_eq!(t.triangles_along_segment(a, a).count(), 1);
+    /// ```
+    pub fn triangles_along_segment(
+        &self,
+        a: Point<T>,
+        b: Point<T>,
+    ) -> impl Iterator<Item = EdgeIndex> + '_ {
+        triangles_along_segment(self, a, b, &self.points)
+    }
+
+    /// Returns the edges which are shared by two triangles as pairs of twin
+    /// half-edges, each edge once, see
+    /// [`TrianglesDCEL::interior_edges`](crate::dcel::TrianglesDCEL::interior_edges).
+    ///
+    /// Edges on the hull, or on the boundary of holes left by removed
+    /// triangles, are skipped. The crate doesn't build constrained
+    /// triangulations, so there are no constrained edges to exclude.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Delaunay, Point};
+    /// let points = &[
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(100.0, 0.0),
+    ///     Point::new(100.0, 100.0),
+    ///     Point::new(0.0, 100.0),
+    /// ];
+    /// let t = Delaunay::new(points).unwrap();
+    /// assert_eq!(t.interior_edges().count(), 1);
+    /// ```
+    pub fn interior_edges(&self) -> impl Iterator<Item = (EdgeIndex, EdgeIndex)> + '_ {
+        self.dcel.interior_edges()
+    }
+
+    /// Returns the outward unit normal of a boundary edge, or `None` for
+    /// interior edges.
+    ///
+    /// Triangles are right-handed, so the outside of a hull edge is always on
+    /// the same side of its direction.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Delaunay, Point};
+    /// let points = &[
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(10.0, 0.0),
+    ///     Point::new(0.0, 10.0),
+    /// ];
+    /// let mut t = Delaunay::new(points).unwrap();
+    /// t.dcel.init_revmap();
+    ///
+    /// let e = t.dcel.edge_between(0.into(), 1.into())
+    ///     .or_else(|| t.dcel.edge_between(1.into(), 0.into()))
+    ///     .unwrap();
+    ///
+    /// assert_eq!(t.hull_edge_normal(e), Some(Point::new(0.0, -1.0)));
+    /// ```
+    pub fn hull_edge_normal(&self, e: EdgeIndex) -> Option<Point<T>> {
+        hull_edge_normal(self, e, &self.points)
+    }
+
+    /// Returns the bounding box of the triangulated points, or `None` if there
+    /// are no triangles.
+    ///
+    /// It's the bounding box of the hull, so points which were skipped
+    /// during triangulation are ignored.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Delaunay, Point, Rect};
+    /// let points = &[
+    ///     Point::new(0.0, 5.0),
+    ///     Point::new(10.0, 0.0),
+    ///     Point::new(10.0, 10.0),
+    ///     Point::new(4.0, 6.0),
+    /// ];
+    /// let t = Delaunay::new(points).unwrap();
+    /// let rect = Rect::new(Point::new(0.0, 0.0), Point::new(10.0, 10.0));
+    /// assert_eq!(t.bounding_box(), Some(rect));
+    /// ```
+    pub fn bounding_box(&self) -> Option<Rect<T>> {
+        bounding_box(self, &self.points)
+    }
+
+    /// Returns the area enclosed by the hull.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Delaunay, Point};
+    /// let points = &[
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(10.0, 0.0),
+    ///     Point::new(10.0, 10.0),
+    ///     Point::new(0.0, 10.0),
+    ///     Point::new(4.0, 6.0),
+    /// ];
+    /// let t = Delaunay::new(points).unwrap();
+    /// assert_eq!(t.hull_area(), 100.0);
+    /// ```
+    pub fn hull_area(&self) -> T {
+        hull_area(self, &self.points)
+    }
+
+    /// Returns the length of the hull.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Delaunay, Point};
+    /// let points = &[
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(10.0, 0.0),
+    ///     Point::new(10.0, 10.0),
+    ///     Point::new(0.0, 10.0),
+    ///     Point::new(4.0, 6.0),
+    /// ];
+    /// let t = Delaunay::new(points).unwrap();
+    /// assert_eq!(t.hull_perimeter(), 40.0);
+    /// ```
+    pub fn hull_perimeter(&self) -> T {
+        hull_perimeter(self, &self.points)
+    }
+
+    /// Returns the triangle with the largest inscribed circle, along with
+    /// the radius of the circle, or `None` if there are no triangles.
+    ///
+    /// The inradius is computed as the area divided by the semiperimeter.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Delaunay, Point};
+    /// let points: &[Point] = &[
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(100.0, 0.0),
+    ///     Point::new(0.0, 100.0),
+    /// ];
+    /// let t = Delaunay::new(points).unwrap();
+    /// let (_, radius) = t.max_inscribed_circle_triangle().unwrap();
+    /// assert!((radius - 29.289).abs() < 1e-3);
+    /// ```
+    pub fn max_inscribed_circle_triangle(&self) -> Option<(EdgeIndex, T)> {
+        max_inscribed_circle_triangle(self, &self.points)
+    }
+
+    /// Returns the gradient of the piecewise-linear function with the given
+    /// values at the points, for every triangle.
+    ///
+    /// The gradient of triangle `t` is at index `t / 3`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Delaunay, Point};
+    /// let points = &[
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(10.0, 0.0),
+    ///     Point::new(0.0, 10.0),
+    /// ];
+    /// let t = Delaunay::new(points).unwrap();
+    /// assert_eq!(t.gradients(&[1.0, 3.0, 0.0]), vec![(0.2, -0.1)]);
+    /// ```
+    pub fn gradients(&self, values: &[T]) -> Vec<(T, T)> {
+        gradients(self, &self.points, values)
+    }
+
+    /// Returns the mixed area of every point.
+    ///
+    /// Each triangle is split between its corners following Meyer et al.:
+    /// corners of non-obtuse triangles get their Voronoi region, while in
+    /// obtuse triangles the obtuse corner gets half of the area and the other
+    /// two a quarter each. The areas therefore sum up to the area of the
+    /// triangulation, and points on the hull get only the part inside of it.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Delaunay, Point};
+    /// let points = &[
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(10.0, 0.0),
+    ///     Point::new(10.0, 10.0),
+    ///     Point::new(0.0, 10.0),
+    /// ];
+    /// let t = Delaunay::new(points).unwrap();
+    /// assert_eq!(t.vertex_areas(), vec![25.0; 4]);
+    /// ```
+    pub fn vertex_areas(&self) -> Vec<T> {
+        vertex_areas(self, &self.points)
+    }
+}
+
+/// Like [`Delaunay::conflict_triangles`], for any [`Triangulation`] with the
+/// points passed in.
+pub fn conflict_triangles<T: Triangulation + ?Sized>(
+    triangulation: &T,
+    point: Point<T::Scalar>,
+    points: &[Point<T::Scalar>],
+) -> Vec<EdgeIndex> {
+    let dcel = triangulation.dcel();
+    let in_conflict = |t: EdgeIndex| dcel.triangle(t, points).in_circumcircle(point);
+
+    let mut stack = match locate_from(dcel, 0.into(), point, points) {
+        Some(t) => vec![t],
+        // outside of the hull only boundary triangles can start the cavity
+        None => (0..dcel.vertices.len())
+            .map(EdgeIndex::from)
+            .filter(|&e| dcel.twin(e).is_none())
+            .map(|e| dcel.triangle_first_edge(e))
+            .collect(),
+    };
+
+    stack.retain(|&t| in_conflict(t));
+
+    let mut visited = vec![false; dcel.num_triangles()];
+    let mut cavity = Vec::new();
+
+    while let Some(t) = stack.pop() {
+        if visited[t.as_usize() / 3] {
+            continue;
+        }
+
+        visited[t.as_usize() / 3] = true;
+        cavity.push(t);
+
+        for &neighbor in dcel.triangle_neighbours(t).iter().flatten() {
+            if !visited[neighbor.as_usize() / 3] && in_conflict(neighbor) {
+                stack.push(neighbor);
+            }
+        }
+    }
+
+    cavity
+}
+
+/// Like [`Delaunay::triangles_in_rect`], for any [`Triangulation`] with the
+/// points passed in.
+pub fn triangles_in_rect<T: Triangulation + ?Sized>(
+    triangulation: &T,
+    rect: Rect<T::Scalar>,
+    points: &[Point<T::Scalar>],
+) -> Vec<EdgeIndex> {
+    let two = T::Scalar::from_f64(2.0);
+    let center = Point::new(
+        (rect.min.x + rect.max.x) / two,
+        (rect.min.y + rect.max.y) / two,
+    );
+
+    flood_triangles(triangulation, center, points, |t| t.intersects_rect(rect))
+}
+
+/// Like [`Delaunay::triangles_within`], for any [`Triangulation`] with the
+/// points passed in.
+pub fn triangles_within<T: Triangulation + ?Sized>(
+    triangulation: &T,
+    center: Point<T::Scalar>,
+    radius: T::Scalar,
+    points: &[Point<T::Scalar>],
+) -> Vec<EdgeIndex> {
+    flood_triangles(triangulation, center, points, |t| {
+        t.intersects_circle(center, radius)
+    })
+}
+
+/// Returns the triangles passing `overlaps`, which must hold for a convex
+/// region, flooding from the one containing `start` or from the hull
+fn flood_triangles<T: Triangulation + ?Sized, F>(
+    triangulation: &T,
+    start: Point<T::Scalar>,
+    points: &[Point<T::Scalar>],
+    overlaps: F,
+) -> Vec<EdgeIndex>
+where
+    F: Fn(Triangle<T::Scalar>) -> bool,
+{
+    let dcel = triangulation.dcel();
+    let overlaps = |t: EdgeIndex| overlaps(dcel.triangle(t, points));
+
+    let mut stack = match locate_from(dcel, 0.into(), start, points) {
+        Some(t) => vec![t],
+        // the overlap of a convex region with the hull is convex, so it
+        // reaches the boundary if it doesn't contain the start
+        None => (0..dcel.vertices.len())
+            .map(EdgeIndex::from)
+            .filter(|&e| dcel.twin(e).is_none())
+            .map(|e| dcel.triangle_first_edge(e))
+            .collect(),
+    };
+
+    stack.retain(|&t| overlaps(t));
+
+    // a set keeps small queries from touching every triangle
+    let mut visited = HashSet::new();
+    let mut found = Vec::new();
+
+    while let Some(t) = stack.pop() {
+        if !visited.insert(t.as_usize()) {
+            continue;
+        }
+
+        found.push(t);
+
+        for &neighbor in dcel.triangle_neighbours(t).iter().flatten() {
+            if !visited.contains(&neighbor.as_usize()) && overlaps(neighbor) {
+                stack.push(neighbor);
+            }
+        }
+    }
+
+    found
+}
+
+/// Like [`Delaunay::vertices_within`], for any [`Triangulation`] with the
+/// points passed in.
+pub fn vertices_within<T: Triangulation + ?Sized>(
+    triangulation: &T,
+    center: Point<T::Scalar>,
+    radius: T::Scalar,
+    points: &[Point<T::Scalar>],
+) -> Vec<PointIndex> {
+    let dcel = triangulation.dcel();
+    let radius_sq = radius * radius;
+    let inside = |e: EdgeIndex| points[dcel.vertices[e]].distance_sq(center) <= radius_sq;
+
+    let mut stack = match nearest_edge(triangulation, center, points) {
+        Some(e) if inside(e) => vec![e],
+        _ => return Vec::new(),
+    };
+
+    let mut queued = HashSet::new();
+    queued.insert(dcel.vertices[stack[0]].as_usize());
+
+    let mut found = Vec::new();
+
+    while let Some(edge) = stack.pop() {
+        found.push(dcel.vertices[edge]);
+
+        for e in TrianglesAroundPoint::new(dcel, edge) {
+            for &neighbor in &[dcel.next_edge(e), dcel.prev_edge(e)] {
+                if inside(neighbor) && queued.insert(dcel.vertices[neighbor].as_usize()) {
+                    stack.push(neighbor);
+                }
+            }
+        }
+    }
+
+    found
+}
+
+/// Like [`Delaunay::nearest_point`], for any [`Triangulation`] with the points
+/// passed in.
+pub fn nearest_point<T: Triangulation + ?Sized>(
+    triangulation: &T,
+    point: Point<T::Scalar>,
+    points: &[Point<T::Scalar>],
+) -> Option<PointIndex> {
+    let dcel = triangulation.dcel();
+    nearest_edge(triangulation, point, points).map(|e| dcel.vertices[e])
+}
+
+/// Like [`Delaunay::k_nearest`], for any [`Triangulation`] with the points
+/// passed in.
+pub fn k_nearest<T: Triangulation + ?Sized>(
+    triangulation: &T,
+    point: Point<T::Scalar>,
+    k: usize,
+    points: &[Point<T::Scalar>],
+) -> Vec<PointIndex> {
+    let dcel = triangulation.dcel();
+    let mut nearest = Vec::with_capacity(k);
+
+    let start = match nearest_edge(triangulation, point, points) {
+        Some(e) if k > 0 => e,
+        _ => return nearest,
+    };
+
+    // distances are never negative, so their bits order like them
+    let key = |e: EdgeIndex| {
+        let distance = points[dcel.vertices[e]].distance_sq(point);
+        Reverse((distance.to_f64().to_bits(), e.as_usize()))
+    };
+
+    let mut queued = vec![false; points.len()];
+    queued[dcel.vertices[start].as_usize()] = true;
+
+    let mut heap = BinaryHeap::new();
+    heap.push(key(start));
+
+    while let Some(Reverse((_, edge))) = heap.pop() {
+        let edge = EdgeIndex::from(edge);
+        nearest.push(dcel.vertices[edge]);
+
+        if nearest.len() == k {
+            break;
+        }
+
+        for e in TrianglesAroundPoint::new(dcel, edge) {
+            for &neighbor in &[dcel.next_edge(e), dcel.prev_edge(e)] {
+                let queued = &mut queued[dcel.vertices[neighbor].as_usize()];
+
+                if !*queued {
+                    *queued = true;
+                    heap.push(key(neighbor));
+                }
+            }
+        }
+    }
+
+    nearest
+}
+
+/// Returns an edge going out of the input point closest to the given
+/// location
+fn nearest_edge<T: Triangulation + ?Sized>(
+    triangulation: &T,
+    point: Point<T::Scalar>,
+    points: &[Point<T::Scalar>],
+) -> Option<EdgeIndex> {
+    if point.x.is_nan() || point.y.is_nan() {
+        return None;
+    }
+
+    let dcel = triangulation.dcel();
+    let distance = |e: EdgeIndex| points[dcel.vertices[e]].distance_sq(point);
+
+    let mut edge = match locate_from(dcel, 0.into(), point, points) {
+        Some(t) => dcel
+            .triangle_edges(t)
+            .iter()
+            .cloned()
+            .min_by(|&a, &b| Scalar::total_cmp(&distance(a), &distance(b)))?,
+        // the greedy walk is exact from any point of the Delaunay graph
+        None if !dcel.vertices.is_empty() => 0.into(),
+        None => return None,
+    };
+
+    let mut best = distance(edge);
+
+    loop {
+        let mut closer = None;
+
+        // both other corners of every triangle around the point, so the
+        // neighbors across the boundary of hull points are covered too
+        for e in TrianglesAroundPoint::new(dcel, edge) {
+            for &candidate in &[dcel.next_edge(e), dcel.prev_edge(e)] {
+                let d = distance(candidate);
+
+                if d < best {
+                    best = d;
+                    closer = Some(candidate);
+                }
+            }
+        }
+
+        match closer {
+            Some(e) => edge = e,
+            None => return Some(edge),
+        }
+    }
+}
+
+/// Like [`Delaunay::triangles_along_segment`], for any [`Triangulation`] with
+/// the points passed in.
+pub fn triangles_along_segment<'a, T: Triangulation + ?Sized>(
+    triangulation: &'a T,
+    a: Point<T::Scalar>,
+    b: Point<T::Scalar>,
+    points: &'a [Point<T::Scalar>],
+) -> impl Iterator<Item = EdgeIndex> + 'a {
+    let dcel = triangulation.dcel();
+    SegmentWalk {
+        dcel,
+        points,
+        a,
+        b,
+        current: locate_from(dcel, 0.into(), a, points),
+        steps: 0,
+    }
+}
+
+/// Like [`Delaunay::hull_edge_normal`], for any [`Triangulation`] with the
+/// points passed in.
+pub fn hull_edge_normal<T: Triangulation + ?Sized>(
+    triangulation: &T,
+    e: EdgeIndex,
+    points: &[Point<T::Scalar>],
+) -> Option<Point<T::Scalar>> {
+    let dcel = triangulation.dcel();
+    if dcel.twin(e).is_some() {
+        return None;
+    }
+
+    let a = points[dcel.vertices[e].as_usize()];
+    let b = points[dcel.edge_endpoint(e).as_usize()];
+    let (dx, dy) = (b.x - a.x, b.y - a.y);
+    let len = (dx * dx + dy * dy).sqrt();
+
+    Some(Point::new(-dy / len, dx / len))
+}
+
+/// Like [`Delaunay::bounding_box`], for any [`Triangulation`] with the points
+/// passed in.
+pub fn bounding_box<T: Triangulation + ?Sized>(
+    triangulation: &T,
+    points: &[Point<T::Scalar>],
+) -> Option<Rect<T::Scalar>> {
+    let hull = triangulation
+        .hull()
+        .iter()
+        .map(|p| points[p.as_usize()])
+        .collect::<Vec<_>>();
+
+    Rect::from_points(&hull)
+}
+
+/// Like [`Delaunay::hull_area`], for any [`Triangulation`] with the points
+/// passed in.
+pub fn hull_area<T: Triangulation + ?Sized>(
+    triangulation: &T,
+    points: &[Point<T::Scalar>],
+) -> T::Scalar {
+    let hull = triangulation.hull();
+
+    if hull.len() < 3 {
+        return T::Scalar::ZERO;
+    }
+
+    let twice: f64 = hull
+        .iter()
+        .zip(hull.iter().cycle().skip(1))
+        .map(|(&a, &b)| {
+            let (a, b) = (points[a.as_usize()], points[b.as_usize()]);
+            a.x.to_f64() * b.y.to_f64() - b.x.to_f64() * a.y.to_f64()
+        })
+        .sum();
+
+    T::Scalar::from_f64((twice / 2.0).abs())
+}
+
+/// Like [`Delaunay::hull_perimeter`], for any [`Triangulation`] with the points
+/// passed in.
+pub fn hull_perimeter<T: Triangulation + ?Sized>(
+    triangulation: &T,
+    points: &[Point<T::Scalar>],
+) -> T::Scalar {
+    let hull = triangulation.hull();
+
+    if hull.len() < 2 {
+        return T::Scalar::ZERO;
+    }
+
+    hull.iter()
+        .zip(hull.iter().cycle().skip(1))
+        .map(|(&a, &b)| points[a.as_usize()].distance(points[b.as_usize()]))
+        .fold(T::Scalar::ZERO, |sum, length| sum + length)
+}
+
+/// Like [`Delaunay::max_inscribed_circle_triangle`], for any [`Triangulation`]
+/// with the points passed in.
+pub fn max_inscribed_circle_triangle<T: Triangulation + ?Sized>(
+    triangulation: &T,
+    points: &[Point<T::Scalar>],
+) -> Option<(EdgeIndex, T::Scalar)> {
+    let dcel = triangulation.dcel();
+    (0..dcel.num_triangles())
+        .map(|i| {
+            let t = EdgeIndex::from(3 * i);
+            let tri = dcel.triangle(t, points);
+
+            (t, tri.inradius())
+        })
+        .fold(None, |best, cur: (EdgeIndex, T::Scalar)| match best {
+            Some((_, radius)) if radius >= cur.1 => best,
+            _ => Some(cur),
+        })
+}
+
+/// Like [`Delaunay::gradients`], for any [`Triangulation`] with the points
+/// passed in.
+pub fn gradients<T: Triangulation + ?Sized>(
+    triangulation: &T,
+    points: &[Point<T::Scalar>],
+    values: &[T::Scalar],
+) -> Vec<(T::Scalar, T::Scalar)> {
+    let dcel = triangulation.dcel();
+    dcel.vertices
+        .chunks(3)
+        .map(|t| {
+            let (p0, p1, p2) = (points[t[0]], points[t[1]], points[t[2]]);
+            let (f0, f1, f2) = (
+                values[t[0].as_usize()],
+                values[t[1].as_usize()],
+                values[t[2].as_usize()],
+            );
+
+            let (dx1, dy1) = (p1.x - p0.x, p1.y - p0.y);
+            let (dx2, dy2) = (p2.x - p0.x, p2.y - p0.y);
+            let (df1, df2) = (f1 - f0, f2 - f0);
+
+            let det = dx1 * dy2 - dy1 * dx2;
+
+            ((df1 * dy2 - df2 * dy1) / det, (dx1 * df2 - dx2 * df1) / det)
+        })
+        .collect()
+}
+
+/// Like [`Delaunay::vertex_areas`], for any [`Triangulation`] with the points
+/// passed in.
+pub fn vertex_areas<T: Triangulation + ?Sized>(
+    triangulation: &T,
+    points: &[Point<T::Scalar>],
+) -> Vec<T::Scalar> {
+    let dcel = triangulation.dcel();
+    let mut areas = vec![T::Scalar::ZERO; points.len()];
+    let [two, four, eight] = [2.0, 4.0, 8.0].map(T::Scalar::from_f64);
+
+    for t in (0..dcel.vertices.len()).step_by(3) {
+        let corners = dcel.triangle_points(t.into());
+        let [a, b, c] = [points[corners[0]], points[corners[1]], points[corners[2]]];
+        let area = Triangle(a, b, c).area();
+
+        if area == T::Scalar::ZERO {
+            continue;
+        }
+
+        // corner points with the opposite corners, in triangle order
+        let around = [(a, b, c), (b, c, a), (c, a, b)];
+        let dots = around.map(|(p, q, r)| (q.x - p.x) * (r.x - p.x) + (q.y - p.y) * (r.y - p.y));
+
+        let obtuse = dots.iter().position(|&d| d < T::Scalar::ZERO);
+
+        for (i, &(p, q, r)) in around.iter().enumerate() {
+            let share = match obtuse {
+                Some(o) if o == i => area / two,
+                Some(_) => area / four,
+                None => {
+                    // cotangents of the angles at q and r
+                    let cot_q = dots[(i + 1) % 3] / (two * area);
+                    let cot_r = dots[(i + 2) % 3] / (two * area);
+                    (p.distance_sq(r) * cot_q + p.distance_sq(q) * cot_r) / eight
+                }
+            };
+
+            let area = &mut areas[corners[i].as_usize()];
+            *area = *area + share;
+        }
+    }
+
+    areas
+}
+
+/// Iterator of the triangles crossed by a segment, see
+/// [`Delaunay::triangles_along_segment`]
+struct SegmentWalk<'a, T> {
+    dcel: &'a TrianglesDCEL,
+    points: &'a [Point<T>],
+    a: Point<T>,
+    b: Point<T>,
+    current: Option<EdgeIndex>,
+    steps: usize,
+}
+
+impl<'a, T: Scalar> SegmentWalk<'a, T> {
+    fn point(&self, e: EdgeIndex) -> Point<T> {
+        self.points[self.dcel.vertices[e]]
+    }
+
+    /// Returns the side of the line through the segment the starting point of
+    /// `e` lies on, exactly
+    fn side(&self, e: EdgeIndex) -> f64 {
+        Triangle(self.a, self.b, self.point(e)).robust_orientation()
+    }
+
+    /// Returns how far along the segment the starting point of `e` lies
+    fn along(&self, e: EdgeIndex) -> T {
+        let p = self.point(e);
+        (p.x - self.a.x) * (self.b.x - self.a.x) + (p.y - self.a.y) * (self.b.y - self.a.y)
+    }
+
+    /// Returns the triangle the segment goes on to after leaving `t`
+    fn step(&mut self, t: EdgeIndex) -> Option<EdgeIndex> {
+        let dcel = self.dcel;
+        let edges = dcel.triangle_edges(t);
+
+        let contains_end = edges.iter().all(|&e| {
+            Triangle(self.point(e), self.point(dcel.next_edge(e)), self.b).robust_orientation()
+                >= 0.0
+        });
+
+        // the bound keeps the walk finite on meshes which aren't Delaunay
+        self.steps += 1;
+        if contains_end || self.steps > dcel.num_triangles() {
+            return None;
+        }
+
+        // leaving through the inside of an edge, from its right to its left
+        let exit = edges
+            .iter()
+            .cloned()
+            .find(|&e| self.side(e) < 0.0 && self.side(dcel.next_edge(e)) > 0.0);
+
+        if let Some(exit) = exit {
+            return dcel.twin(exit).map(|twin| dcel.triangle_first_edge(twin));
+        }
+
+        // otherwise through the vertex farthest along the segment, into the
+        // triangle around it whose corner the segment goes on through
+        let vertex = edges
+            .iter()
+            .cloned()
+            .filter(|&e| self.side(e) == 0.0)
+            .max_by(|&e, &f| self.along(e).partial_cmp(&self.along(f)).unwrap())?;
+
+        let ahead = |e: EdgeIndex| self.along(e) > self.along(vertex);
+
+        TrianglesAroundPoint::new(dcel, vertex)
+            .find(|&e| {
+                let (next, prev) = (dcel.next_edge(e), dcel.prev_edge(e));
+                let (x, y) = (self.side(next), self.side(prev));
+
+                dcel.triangle_first_edge(e) != t
+                    && x <= 0.0
+                    && y >= 0.0
+                    && (x < 0.0 || ahead(next))
+                    && (y > 0.0 || ahead(prev))
+            })
+            .map(|e| dcel.triangle_first_edge(e))
+    }
+}
+
+impl<'a, T: Scalar> Iterator for SegmentWalk<'a, T> {
+    type Item = EdgeIndex;
+
+    fn next(&mut self) -> Option<EdgeIndex> {
+        let t = self.current?;
+        self.current = self.step(t);
+        Some(t)
+    }
+}
+
+impl<'a, T: Scalar> std::iter::FusedIterator for SegmentWalk<'a, T> {}
+
+#[cfg(test)]
+mod tests {
+    use rand::{Rng, SeedableRng};
+
+    use super::*;
+    use crate::testing::random_points;
+    use crate::DelaunayBuilder;
+
+    #[test]
+    fn conflict_triangles() {
+        let points = random_points(500, 11);
+        let mut rng = rand::rngs::StdRng::seed_from_u64(12);
+
+        let t = Delaunay::new(&points).unwrap();
+
+        for _ in 0..100 {
+            let q = Point::new(rng.gen_range(-20.0, 120.0), rng.gen_range(-20.0, 120.0));
+
+            let mut cavity = t.conflict_triangles(q);
+            cavity.sort_by_key(|e| e.as_usize());
+
+            let expected = (0..t.dcel.num_triangles())
+                .map(|i| EdgeIndex::from(3 * i))
+                .filter(|&e| t.dcel.triangle(e, &points).in_circumcircle(q))
+                .collect::<Vec<_>>();
+
+            assert_eq!(cavity, expected);
+        }
+    }
+
+    #[test]
+    fn triangles_in_rect() {
+        let points = random_points(2000, 17);
+        let mut rng = rand::rngs::StdRng::seed_from_u64(18);
+
+        let mut t = Delaunay::new(&points).unwrap();
+        t.dcel.init_revmap();
+
+        for i in 0..200 {
+            // from tiny up to larger than the whole triangulation
+            let size = [0.0, 1.0, 10.0, 50.0, 200.0][i % 5];
+            let min = Point::new(rng.gen_range(-50.0, 120.0), rng.gen_range(-50.0, 120.0));
+            let max = Point::new(
+                min.x + rng.gen_range(0.0, size + 1.0),
+                min.y + rng.gen_range(0.0, size + 1.0),
+            );
+            let rect = Rect::new(min, max);
+
+            let mut found = t.triangles_in_rect(rect);
+            found.sort_by_key(|e| e.as_usize());
+
+            let expected = (0..t.dcel.num_triangles())
+                .map(|i| EdgeIndex::from(3 * i))
+                .filter(|&e| t.dcel.triangle(e, &points).intersects_rect(rect))
+                .collect::<Vec<_>>();
+
+            assert_eq!(found, expected, "{:?}", rect);
+        }
+
+        // a rectangle shrunk to a vertex
+        let p = points[100];
+        let found = t.triangles_in_rect(Rect::new(p, p));
+        assert_eq!(
+            found.len(),
+            t.dcel.triangles_around_point(100.into()).count()
+        );
+    }
+
+    #[test]
+    fn within_circle() {
+        let points = random_points(2000, 22);
+        let mut rng = rand::rngs::StdRng::seed_from_u64(23);
+
+        let t = Delaunay::new(&points).unwrap();
+
+        for i in 0..200 {
+            let radius = [0.0, 1.0, 5.0, 30.0, 200.0][i % 5];
+            let center = Point::new(rng.gen_range(-50.0, 150.0), rng.gen_range(-50.0, 150.0));
+
+            let mut vertices = t.vertices_within(center, radius);
+            vertices.sort_by_key(|p| p.as_usize());
+
+            let expected = (0..points.len())
+                .filter(|&p| points[p].distance_sq(center) <= radius * radius)
+                .map(PointIndex::from)
+                .collect::<Vec<_>>();
+
+            assert_eq!(vertices, expected, "{:?} {}", center, radius);
+
+            let mut triangles = t.triangles_within(center, radius);
+            triangles.sort_by_key(|e| e.as_usize());
+
+            let expected = (0..t.dcel.num_triangles())
+                .map(|i| EdgeIndex::from(3 * i))
+                .filter(|&e| {
+                    t.dcel
+                        .triangle(e, &points)
+                        .intersects_circle(center, radius)
+                })
+                .collect::<Vec<_>>();
+
+            assert_eq!(triangles, expected, "{:?} {}", center, radius);
+        }
+
+        // the center on a point finds it at zero radius
+        assert_eq!(t.vertices_within(points[7], 0.0), vec![7.into()]);
+    }
+
+    #[test]
+    fn nearest_point() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(13);
+
+        for &count in &[3, 10, 100, 1000] {
+            let points = random_points(count, count as u64);
+
+            let t = Delaunay::new(&points).unwrap();
+
+            for _ in 0..200 {
+                let q = Point::new(rng.gen_range(-50.0, 150.0), rng.gen_range(-50.0, 150.0));
+
+                let nearest = t.nearest_point(q).unwrap();
+                let expected = points
+                    .iter()
+                    .map(|p| p.distance_sq(q))
+                    .fold(f32::INFINITY, f32::min);
+
+                assert_eq!(points[nearest].distance_sq(q), expected);
+            }
+
+            assert_eq!(t.nearest_point(Point::new(f32::NAN, 50.0)), None);
+            assert!(t.k_nearest(Point::new(50.0, f32::NAN), 3).is_empty());
+        }
+    }
+
+    #[test]
+    fn any_triangulation() {
+        let points = random_points(300, 15);
+        let mut rng = rand::rngs::StdRng::seed_from_u64(16);
+
+        let points64 = points.iter().map(|p| p.cast()).collect::<Vec<Point<f64>>>();
+
+        let t = Delaunay::new(&points).unwrap();
+        let t64 = crate::Delaunay64::new(&points64).unwrap();
+        assert_eq!(t64.hull(), t.hull());
+
+        for _ in 0..50 {
+            let q = Point::new(rng.gen_range(-20.0, 120.0), rng.gen_range(-20.0, 120.0));
+
+            assert_eq!(
+                super::k_nearest(&t64, q.cast(), 5, &points64),
+                t.k_nearest(q, 5)
+            );
+            assert_eq!(
+                super::triangles_within(&t64, q.cast(), 10.0, &points64).len(),
+                t.triangles_within(q, 10.0).len()
+            );
+        }
+
+        let area = f64::from(t.hull_area());
+        assert!((super::hull_area(&t64, &points64) - area).abs() < 1e-6 * area);
+    }
+
+    #[test]
+    fn k_nearest() {
+        let points = random_points(500, 14);
+        let mut rng = rand::rngs::StdRng::seed_from_u64(15);
+
+        let t = Delaunay::new(&points).unwrap();
+
+        for _ in 0..100 {
+            let q = Point::new(rng.gen_range(-50.0, 150.0), rng.gen_range(-50.0, 150.0));
+            let k = rng.gen_range(0, 40);
+
+            let nearest = t.k_nearest(q, k);
+            let found = nearest
+                .iter()
+                .map(|&p| points[p].distance_sq(q))
+                .collect::<Vec<_>>();
+
+            let mut expected = points.iter().map(|p| p.distance_sq(q)).collect::<Vec<_>>();
+            expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            expected.truncate(k);
+            assert_eq!(found, expected);
+
+            let mut unique = nearest.iter().map(|p| p.as_usize()).collect::<Vec<_>>();
+            unique.sort();
+            unique.dedup();
+            assert_eq!(unique.len(), k);
+        }
+
+        assert_eq!(t.k_nearest(Point::new(50.0, 50.0), 1000).len(), 500);
+    }
+
+    /// Checks that the walk from `a` to `b` covers the whole segment with
+    /// adjacent triangles, each one once
+    fn assert_along_segment(t: &Delaunay, a: Point, b: Point, points: &[Point]) {
+        let crossed = super::triangles_along_segment(t, a, b, points).collect::<Vec<_>>();
+
+        // sampled points are rounded off the segment, so allow some slack
+        let contains = |e: EdgeIndex, p: Point| {
+            let tri = t.dcel.triangle(e, points);
+            Triangle(tri.0, tri.1, p).orientation() > -1e-3
+                && Triangle(tri.1, tri.2, p).orientation() > -1e-3
+                && Triangle(tri.2, tri.0, p).orientation() > -1e-3
+        };
+
+        assert!(contains(crossed[0], a));
+        assert!(contains(*crossed.last().unwrap(), b), "{:?} {:?}", a, b);
+
+        let mut unique = crossed.iter().map(|e| e.as_usize()).collect::<Vec<_>>();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(unique.len(), crossed.len());
+
+        for pair in crossed.windows(2) {
+            let corners = t.dcel.triangle_points(pair[0]);
+            assert!(t
+                .dcel
+                .triangle_points(pair[1])
+                .iter()
+                .any(|p| corners.contains(p)));
+        }
+
+        for i in 0..=100 {
+            let p = a.lerp(b, i as f32 / 100.0);
+            assert!(crossed.iter().any(|&e| contains(e, p)), "{:?} uncovered", p);
+        }
+    }
+
+    #[test]
+    fn triangles_along_segment() {
+        let points = random_points(500, 15);
+        let mut rng = rand::rngs::StdRng::seed_from_u64(16);
+
+        let t = Delaunay::new(&points).unwrap();
+
+        for _ in 0..100 {
+            let a = Point::new(rng.gen_range(20.0, 80.0), rng.gen_range(20.0, 80.0));
+            let b = Point::new(rng.gen_range(20.0, 80.0), rng.gen_range(20.0, 80.0));
+            assert_along_segment(&t, a, b, &points);
+        }
+
+        // between input points
+        assert_along_segment(&t, points[3], points[7], &points);
+
+        let outside = Point::new(-10.0, 50.0);
+        assert_eq!(t.triangles_along_segment(outside, points[0]).count(), 0);
+    }
+
+    #[test]
+    fn triangles_along_segment_grid() {
+        let size = 10;
+        let points = (0..size * size)
+            .map(|i| Point::new((i % size) as f32 * 10.0, (i / size) as f32 * 10.0))
+            .collect::<Vec<_>>();
+
+        let t = Delaunay::new(&points).unwrap();
+
+        for i in 0..size {
+            let c = i as f32 * 10.0;
+            let far = (size - 1) as f32 * 10.0;
+
+            // along the grid lines, through every vertex on them
+            assert_along_segment(&t, Point::new(0.0, c), Point::new(far, c), &points);
+            assert_along_segment(&t, Point::new(c, far), Point::new(c, 0.0), &points);
+
+            // between the grid lines, and along the diagonals
+            let mid = c.min(far - 5.0) + 5.0;
+            assert_along_segment(&t, Point::new(far, mid), Point::new(0.0, mid), &points);
+            assert_along_segment(&t, Point::new(0.0, 0.0), Point::new(far, c), &points);
+            assert_along_segment(&t, Point::new(far, far), Point::new(c, 0.0), &points);
+        }
+
+        // segments leaving the hull stop at its boundary
+        let crossed = t
+            .triangles_along_segment(Point::new(5.0, 5.0), Point::new(150.0, 5.0))
+            .count();
+        assert!(crossed > 0 && crossed <= 2 * (size - 1));
+    }
+
+    #[test]
+    fn hull_area_and_perimeter() {
+        let points = random_points(500, 12)
+            .into_iter()
+            .map(|p| p - Point::new(50.0, 50.0))
+            .collect::<Vec<_>>();
+
+        let t = Delaunay::new(&points).unwrap();
+
+        let triangles: f64 = t.dcel.triangles(&points).map(|t| f64::from(t.area())).sum();
+        let area = f64::from(t.hull_area());
+        assert!(
+            (area - triangles).abs() < 1e-3 * triangles,
+            "{} != {}",
+            area,
+            triangles
+        );
+
+        let boundary: f32 = (0..t.dcel.vertices.len())
+            .map(EdgeIndex::from)
+            .filter(|&e| t.dcel.twin(e).is_none())
+            .map(|e| {
+                let a = points[t.dcel.vertices[e].as_usize()];
+                a.distance_sq(points[t.dcel.edge_endpoint(e).as_usize()])
+                    .sqrt()
+            })
+            .sum();
+        let perimeter = t.hull_perimeter();
+        assert!((perimeter - boundary).abs() < 1e-3 * boundary);
+
+        // the hull of points in a 100 x 100 square
+        assert!(area < 100.0 * 100.0 && perimeter < 400.0);
+    }
+
+    #[test]
+    fn max_inscribed_circle() {
+        // thin triangles along the bottom, the two top points span a single
+        // big triangle
+        let mut points = (0..=20)
+            .map(|i| Point::new(i as f32 * 10.0, 0.0))
+            .chain((0..=20).map(|i| Point::new(i as f32 * 10.0 + 5.0, 5.0)))
+            .collect::<Vec<_>>();
+        points.push(Point::new(0.0, 205.0));
+        points.push(Point::new(205.0, 205.0));
+
+        let t = Delaunay::new(&points).unwrap();
+        let (best, radius) = t.max_inscribed_circle_triangle().unwrap();
+
+        let corners = t.dcel.triangle_points(best);
+        assert!(corners.contains(&PointIndex::from(42)));
+        assert!(corners.contains(&PointIndex::from(43)));
+        assert!(radius > 20.0);
+
+        // collinear points are only triangulated together with the frame
+        let points = (0..5)
+            .map(|i| Point::new(i as f32, 0.0))
+            .collect::<Vec<_>>();
+        let mut t = DelaunayBuilder::new()
+            .bounding_frame(1.0)
+            .build(&points)
+            .unwrap();
+        t.strip_frame();
+        assert_eq!(t.max_inscribed_circle_triangle(), None);
+    }
+
+    #[test]
+    fn vertex_areas_sum() {
+        let points = random_points(1000, 3);
+
+        let t = Delaunay::new(&points).unwrap();
+        let total: f64 = t
+            .dcel
+            .triangles(&points)
+            .map(|t| f64::from(t.orientation().abs()) / 2.0)
+            .sum();
+
+        let areas = t.vertex_areas();
+        assert!(areas.iter().all(|&a| a > 0.0));
+
+        let sum: f64 = areas.iter().map(|&a| f64::from(a)).sum();
+        assert!((sum - total).abs() < 1e-3 * total);
+    }
+
+    #[test]
+    fn vertex_areas_grid() {
+        let size = 8;
+        let h = 2.5;
+        let points = (0..size * size)
+            .map(|i| Point::new((i % size) as f32 * h, (i / size) as f32 * h))
+            .collect::<Vec<_>>();
+
+        let t = Delaunay::new(&points).unwrap();
+        let areas = t.vertex_areas();
+
+        for (i, &area) in areas.iter().enumerate() {
+            let on_border = |c: usize| c == 0 || c == size - 1;
+            let expected = match (on_border(i % size), on_border(i / size)) {
+                (true, true) => h * h / 4.0,
+                (true, false) | (false, true) => h * h / 2.0,
+                (false, false) => h * h,
+            };
+
+            assert!(
+                (area - expected).abs() < 1e-4,
+                "point {}: {} != {}",
+                i,
+                area,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn interior_edges() {
+        let size = 6;
+        let points = (0..size * size)
+            .map(|i| {
+                Point::new(
+                    (i % size) as f32 * 10.0 + (i / size) as f32,
+                    (i / size) as f32 * 10.0,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let mut t = Delaunay::new(&points).unwrap();
+
+        // punch a hole by removing the triangles touching a central point
+        let center = PointIndex::from(14);
+        while let Some(e) = t.dcel.vertices.iter().position(|&v| v == center) {
+            t.dcel.swap_remove_triangle(e.into());
+        }
+
+        let boundary = (0..t.dcel.vertices.len())
+            .map(EdgeIndex::from)
+            .filter(|&e| t.dcel.twin(e).is_none())
+            .map(|e| (t.dcel.vertices[e], t.dcel.edge_endpoint(e)))
+            .collect::<Vec<_>>();
+
+        let interior = t.interior_edges().collect::<Vec<_>>();
+        let shared = t.dcel.halfedges.iter().filter(|e| e.is_some()).count();
+        assert_eq!(interior.len(), shared / 2);
+
+        for (e, twin) in interior {
+            assert_eq!(t.dcel.twin(e), Some(twin));
+
+            let (a, b) = (t.dcel.vertices[e], t.dcel.edge_endpoint(e));
+            assert!(!boundary.contains(&(a, b)) && !boundary.contains(&(b, a)));
+        }
+
+        // the hole is surrounded by the neighbors of the removed point
+        assert!(boundary.len() > 4 * (size - 1));
+    }
+
+    #[test]
+    fn hull_edge_normals() {
+        let points = (0..36)
+            .map(|i| Point::new((i % 6) as f32 * 10.0, (i / 6) as f32 * 10.0))
+            .collect::<Vec<_>>();
+
+        let t = Delaunay::new(&points).unwrap();
+        let mut sides = [0; 4];
+
+        for e in (0..t.dcel.vertices.len()).map(EdgeIndex::from) {
+            let normal = match t.hull_edge_normal(e) {
+                Some(normal) => normal,
+                None => {
+                    assert!(t.dcel.twin(e).is_some());
+                    continue;
+                }
+            };
+
+            let a = points[t.dcel.vertices[e].as_usize()];
+            let b = points[t.dcel.edge_endpoint(e).as_usize()];
+            let side = match (normal.x as i32, normal.y as i32) {
+                (-1, 0) => (a.x == 0.0 && b.x == 0.0, 0),
+                (1, 0) => (a.x == 50.0 && b.x == 50.0, 1),
+                (0, -1) => (a.y == 0.0 && b.y == 0.0, 2),
+                (0, 1) => (a.y == 50.0 && b.y == 50.0, 3),
+                _ => panic!("unexpected normal {:?}", normal),
+            };
+
+            assert!(side.0, "normal {:?} points inwards", normal);
+            sides[side.1] += 1;
+        }
+
+        assert_eq!(sides, [5; 4]);
+    }
+
+    #[test]
+    fn gradients() {
+        let points = random_points(300, 13)
+            .into_iter()
+            .map(|p| p * 0.1)
+            .collect::<Vec<_>>();
+
+        let values = points
+            .iter()
+            .map(|p| 2.0 * p.x - 3.0 * p.y + 1.0)
+            .collect::<Vec<_>>();
+
+        let t = Delaunay::new(&points).unwrap();
+        let gradients = t.gradients(&values);
+        assert_eq!(gradients.len(), t.dcel.num_triangles());
+
+        for (gx, gy) in gradients {
+            assert!(
+                (gx - 2.0).abs() < 1e-2 && (gy + 3.0).abs() < 1e-2,
+                "({}, {})",
+                gx,
+                gy
+            );
+        }
+    }
+}
@@ -0,0 +1,348 @@
+//! Removal of points from the triangulation
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::dcel::{EdgeIndex, PointIndex, TrianglesAroundPoint};
+use crate::geom::{Point, Scalar, Triangle};
+use crate::Delaunay;
+
+/// Reason why a point can't be removed from the triangulation
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RemoveError {
+    /// The point is not a vertex of the triangulation, like a skipped
+    /// duplicate or an already removed point
+    NotInTriangulation,
+    /// All remaining points would lie on a single line
+    AllCollinear,
+}
+
+impl fmt::Display for RemoveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RemoveError::NotInTriangulation => write!(f, "the point is not triangulated"),
+            RemoveError::AllCollinear => write!(f, "the remaining points are collinear"),
+        }
+    }
+}
+
+impl std::error::Error for RemoveError {}
+
+impl<T: Scalar> Delaunay<T> {
+    /// Removes a point from the triangulation, the opposite of
+    /// [`insert`](Delaunay::insert).
+    ///
+    /// The triangles around the point are replaced with a triangulation of
+    /// the hole they leave, which is made Delaunay again by flipping edges.
+    /// Removing a hull point shrinks the hull to the remaining points. The
+    /// point stays in [`points`](Delaunay::points), so no indices change.
+    ///
+    /// Nothing is changed if an error is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Delaunay, Point, RemoveError};
+    /// let mut t = Delaunay::new(&[
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(100.0, 0.0),
+    ///     Point::new(0.0, 100.0),
+    ///     Point::new(20.0, 20.0),
+    /// ]).unwrap();
+    ///
+    /// t.remove(3.into()).unwrap();
+    /// assert_eq!(t.dcel.num_triangles(), 1);
+    /// assert_eq!(t.remove(3.into()), Err(RemoveError::NotInTriangulation));
+    /// assert_eq!(t.remove(0.into()), Err(RemoveError::AllCollinear));
+    /// ```
+    pub fn remove(&mut self, p: PointIndex) -> Result<(), RemoveError> {
+        let start = self
+            .dcel
+            .vertices
+            .iter()
+            .position(|&v| v == p)
+            .ok_or(RemoveError::NotInTriangulation)?;
+
+        let fan = TrianglesAroundPoint::new(&self.dcel, start.into()).collect::<Vec<_>>();
+
+        self.with_points(|delaunay, points| delaunay.remove_fan(&fan, points))
+    }
+
+    /// Replaces the triangles of the fan around a point, given by their
+    /// edges going out of the point, with a triangulation of their outline
+    fn remove_fan(&mut self, fan: &[EdgeIndex], points: &[Point<T>]) -> Result<(), RemoveError> {
+        let on_hull = self.dcel.twin(fan[0]).is_none();
+        let mut outline = fan
+            .iter()
+            .map(|&e| self.dcel.edge_endpoint(e))
+            .collect::<Vec<_>>();
+
+        let triangles = if on_hull {
+            let last = fan[fan.len() - 1];
+            outline.push(self.dcel.vertices[self.dcel.prev_edge(last)]);
+            fill_pockets(&outline, points)
+        } else {
+            clip_ears(&outline, points)
+        };
+
+        if triangles.is_empty() && fan.len() == self.dcel.num_triangles() {
+            return Err(RemoveError::AllCollinear);
+        }
+
+        // edges of the outline seen from the outside, keyed by their points
+        let mut outer = HashMap::new();
+        let mut slots = Vec::with_capacity(fan.len());
+
+        for &e in fan {
+            if let Some(twin) = self.dcel.twin(self.dcel.next_edge(e)) {
+                outer.insert(
+                    (self.dcel.vertices[twin], self.dcel.edge_endpoint(twin)),
+                    twin,
+                );
+                self.dcel.unlink(twin);
+            }
+
+            let t = self.dcel.triangle_first_edge(e);
+            for &x in &self.dcel.triangle_edges(t) {
+                self.dcel.unlink(x);
+            }
+            slots.push(t);
+        }
+
+        // the new triangles take the lowest slots, so removing the rest never
+        // moves them
+        slots.sort_unstable_by_key(|t| t.as_usize());
+
+        let mut inner = HashMap::new();
+        for (&t, vertices) in slots.iter().zip(&triangles) {
+            for (i, &v) in vertices.iter().enumerate() {
+                self.dcel.vertices[t + i] = v;
+                inner.insert((v, vertices[(i + 1) % 3]), t + i);
+            }
+        }
+
+        let mut diagonals = Vec::new();
+        for (&(a, b), &e) in &inner {
+            if let Some(&twin) = inner.get(&(b, a)) {
+                self.dcel.link(e, twin);
+                diagonals.push(e);
+            } else if let Some(&twin) = outer.get(&(b, a)) {
+                self.dcel.link(e, twin);
+            }
+        }
+
+        for &t in slots[triangles.len()..].iter().rev() {
+            self.dcel.swap_remove_triangle(t);
+        }
+
+        self.legalize_all(diagonals, points);
+        self.resync_hull();
+
+        if on_hull {
+            for &q in &outline {
+                if self.hull_snapshot().next(q).is_some() {
+                    self.hull.add_hash(q, points[q]);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Triangulates a simple polygon, like the hole left by an interior point, by
+/// cutting off its ears. The polygon has the inside on the left of its edges.
+pub(crate) fn clip_ears<T: Scalar>(
+    polygon: &[PointIndex],
+    points: &[Point<T>],
+) -> Vec<[PointIndex; 3]> {
+    let mut polygon = polygon.to_vec();
+    let mut triangles = Vec::with_capacity(polygon.len() - 2);
+
+    while polygon.len() > 3 {
+        let n = polygon.len();
+        let corner = |i: usize| [polygon[(i + n - 1) % n], polygon[i], polygon[(i + 1) % n]];
+
+        // the polygon is simple, so there is an ear, and with exact
+        // orientations it is found
+        let ear = (0..n)
+            .find(|&i| {
+                let vertices = corner(i);
+                let [a, b, c] = vertices.map(|v| points[v]);
+
+                Triangle(a, b, c).robust_orientation() > 0.0
+                    && !polygon
+                        .iter()
+                        .filter(|v| !vertices.contains(v))
+                        .any(|&v| covers(Triangle(a, b, c), points[v]))
+            })
+            .expect("a simple polygon has an ear");
+
+        triangles.push(corner(ear));
+        polygon.remove(ear);
+    }
+
+    triangles.push([polygon[0], polygon[1], polygon[2]]);
+    triangles
+}
+
+/// Triangulates the pockets between the chain of points around a removed
+/// hull point and the new hull, which is found like in Graham's scan
+fn fill_pockets<T: Scalar>(chain: &[PointIndex], points: &[Point<T>]) -> Vec<[PointIndex; 3]> {
+    let mut hull: Vec<PointIndex> = Vec::with_capacity(chain.len());
+    let mut triangles = Vec::new();
+
+    for &c in chain {
+        while let [.., a, b] = hull[..] {
+            if Triangle(points[a], points[b], points[c]).robust_orientation() <= 0.0 {
+                break;
+            }
+
+            triangles.push([a, b, c]);
+            hull.pop();
+        }
+
+        hull.push(c);
+    }
+
+    triangles
+}
+
+/// Returns true if the point is inside of the right-handed triangle or on its
+/// boundary
+fn covers<T: Scalar>(triangle: Triangle<T>, point: Point<T>) -> bool {
+    let Triangle(a, b, c) = triangle;
+
+    Triangle(a, b, point).robust_orientation() >= 0.0
+        && Triangle(b, c, point).robust_orientation() >= 0.0
+        && Triangle(c, a, point).robust_orientation() >= 0.0
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::testing::{assert_delaunay, edge_set, random_points};
+    use crate::Triangulation;
+
+    /// Triangulates the points without the removed ones, numbered like in
+    /// the full set
+    fn rebuilt(points: &[Point], removed: &HashSet<usize>) -> Delaunay {
+        let indices = (0..points.len())
+            .filter(|i| !removed.contains(i))
+            .collect::<Vec<_>>();
+        let subset = indices.iter().map(|&i| points[i]).collect::<Vec<_>>();
+
+        let mut t = Delaunay::new(&subset).unwrap();
+        t.remap_points(&indices, points.len());
+        t
+    }
+
+    #[test]
+    fn interior() {
+        let points = random_points(200, 1);
+        let mut t = Delaunay::new(&points).unwrap();
+        let hull = t.hull().iter().cloned().collect::<HashSet<_>>();
+
+        let removed = (0..points.len())
+            .filter(|&i| !hull.contains(&i.into()))
+            .take(20)
+            .collect::<HashSet<_>>();
+
+        for &i in &removed {
+            t.remove(i.into()).unwrap();
+        }
+
+        assert_delaunay(&t, &points);
+        assert_eq!(edge_set(&t), edge_set(&rebuilt(&points, &removed)));
+    }
+
+    #[test]
+    fn hull_points() {
+        let mut points = random_points(200, 2);
+        points.extend_from_slice(&[
+            Point::new(-10.0, -10.0),
+            Point::new(110.0, -10.0),
+            Point::new(110.0, 110.0),
+            Point::new(-10.0, 110.0),
+        ]);
+
+        let mut t = Delaunay::new(&points).unwrap();
+        let mut removed = HashSet::new();
+
+        for i in 200..204 {
+            t.remove(i.into()).unwrap();
+            removed.insert(i);
+        }
+
+        // the hull points were inside of the corners before
+        for _ in 0..10 {
+            let p = t.hull()[0];
+            t.remove(p).unwrap();
+            removed.insert(p.as_usize());
+        }
+
+        assert_delaunay(&t, &points);
+        assert_eq!(edge_set(&t), edge_set(&rebuilt(&points, &removed)));
+        assert_eq!(t.hull().len(), rebuilt(&points, &removed).hull().len());
+
+        // points outside of the shrunk hull are inserted again
+        assert_eq!(t.insert(Point::new(-20.0, 50.0)), Ok(204.into()));
+        assert_delaunay(&t, t.points());
+    }
+
+    #[test]
+    fn not_triangulated() {
+        let points = &[
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(0.0, 10.0),
+            Point::new(5.0, 0.0),
+        ];
+
+        let mut t = Delaunay::new(points).unwrap();
+        let skipped = [1, 2]
+            .iter()
+            .find(|&&i| !t.dcel.vertices.contains(&i.into()))
+            .cloned()
+            .unwrap();
+
+        assert_eq!(
+            t.remove(skipped.into()),
+            Err(RemoveError::NotInTriangulation)
+        );
+        assert_eq!(t.remove(10.into()), Err(RemoveError::NotInTriangulation));
+
+        // the rest is collinear without the only point off the line
+        let triangles = t.dcel.vertices.clone();
+        assert_eq!(t.remove(3.into()), Err(RemoveError::AllCollinear));
+        assert_eq!(t.dcel.vertices, triangles);
+
+        t.remove(4.into()).unwrap();
+        assert_eq!(t.dcel.num_triangles(), 1);
+        assert_delaunay(&t, points);
+    }
+
+    #[test]
+    fn every_third_point() {
+        let points = random_points(10_000, 3);
+        let mut t = Delaunay::new(&points).unwrap();
+
+        let removed = (0..points.len()).step_by(3).collect::<HashSet<_>>();
+        for &i in &removed {
+            t.remove(i.into()).unwrap();
+        }
+
+        assert_delaunay(&t, &points);
+
+        let used = t
+            .dcel
+            .vertices
+            .iter()
+            .map(|v| v.as_usize())
+            .collect::<HashSet<_>>();
+        assert_eq!(used.len(), points.len() - removed.len());
+        assert!(used.is_disjoint(&removed));
+    }
+}
@@ -0,0 +1,340 @@
+//! Mesh cleanup operations
+
+use crate::dcel::{CollapseTarget, EdgeIndex, TrianglesAroundPoint};
+use crate::geom::{Point, Scalar};
+use crate::Delaunay;
+
+impl<T: Scalar> Delaunay<T> {
+    /// Collapses the edge, merging its end point into its start point.
+    ///
+    /// The one or two triangles sharing the edge are removed and the
+    /// remaining point is moved, changing its coordinates in
+    /// [`points`](Delaunay::points): to the middle of the edge if both ends
+    /// are inside, or to the end on the hull, so that the hull stays convex.
+    /// An edge between two hull points keeps the start point in place.
+    /// Afterwards the edges around the merged point are flipped to restore
+    /// the Delaunay condition.
+    ///
+    /// Returns false without changing anything if the collapse would break
+    /// the mesh topology or flip a triangle, see
+    /// [`TrianglesDCEL::collapse_edge`](crate::dcel::TrianglesDCEL::collapse_edge).
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Delaunay, Point};
+    /// let mut t = Delaunay::new(&[
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(10.0, 0.0),
+    ///     Point::new(10.0, 10.0),
+    ///     Point::new(0.0, 10.0),
+    ///     Point::new(4.0, 6.0),
+    ///     Point::new(5.0, 6.0),
+    /// ]).unwrap();
+    ///
+    /// let e = (0..t.dcel.vertices.len())
+    ///     .map(|e| e.into())
+    ///     .find(|&e| t.dcel.vertices[e] == 4.into() && t.dcel.edge_endpoint(e) == 5.into())
+    ///     .unwrap();
+    ///
+    /// assert!(t.collapse_edge(e));
+    /// assert_eq!(t.points()[4], Point::new(4.5, 6.0));
+    /// assert_eq!(t.dcel.num_triangles(), 4);
+    /// ```
+    pub fn collapse_edge(&mut self, edge: EdgeIndex) -> bool {
+        let a = self.dcel.vertices[edge].as_usize();
+        let b = self.dcel.edge_endpoint(edge).as_usize();
+
+        let on_hull = |p: usize| self.hull_points.contains(&p.into());
+        let (pa, pb) = (self.points[a], self.points[b]);
+        let target = match (on_hull(a), on_hull(b)) {
+            (false, false) => midpoint(pa, pb),
+            (false, true) => pb,
+            (true, _) => pa,
+        };
+        self.move_point(a, target);
+
+        if self.with_points(|delaunay, points| delaunay.collapse_moved_edge(edge, points)) {
+            return true;
+        }
+
+        self.move_point(a, pa);
+        false
+    }
+
+    /// Sets the owned point `p`, and its normalized copy if there is one
+    fn move_point(&mut self, p: usize, position: Point<T>) {
+        self.points[p] = position;
+
+        if let Some(normalization) = self.normalization {
+            self.normalized[p] = normalization.apply(position);
+        }
+    }
+
+    /// Collapses the edge whose start point was already moved to its place
+    /// in `points`
+    fn collapse_moved_edge(&mut self, edge: EdgeIndex, points: &[Point<T>]) -> bool {
+        let a = self.dcel.vertices[edge].as_usize();
+
+        if self
+            .dcel
+            .collapse_edge(edge, CollapseTarget::Origin, points)
+            .is_err()
+        {
+            return false;
+        }
+
+        if let Some(start) = self.dcel.vertices.iter().position(|&v| v.as_usize() == a) {
+            let opposite_edges = TrianglesAroundPoint::new(&self.dcel, start.into())
+                .map(|e| self.dcel.next_edge(e))
+                .collect::<Vec<_>>();
+
+            for e in opposite_edges {
+                self.legalize(e, points);
+            }
+        }
+
+        // the collapse may have removed a hull point and moved the boundary
+        // edges around
+        self.resync_hull();
+
+        true
+    }
+
+    /// Merges points closer than `tol` to each other by collapsing the edges
+    /// between them, removing needle triangles.
+    ///
+    /// Merged points stay in [`points`](Delaunay::points), but are not
+    /// referenced by the triangulation anymore. The remaining points may be
+    /// moved, see [`collapse_edge`](Delaunay::collapse_edge).
+    pub fn merge_close_vertices(&mut self, tol: T) {
+        let tol_sq = tol * tol;
+
+        // the closest points are always connected with an edge, so it is
+        // enough to look at the edges. A collapse only changes the triangles
+        // around the merged point and the ones moved into the removed slots,
+        // so the scan goes on from the same edge, until a whole round of the
+        // edges finds nothing to collapse.
+        let mut e = 0;
+        let mut unchanged = 0;
+
+        while unchanged < self.dcel.vertices.len() {
+            if e >= self.dcel.vertices.len() {
+                e = 0;
+            }
+
+            let edge = EdgeIndex::from(e);
+            let (a, b) = (
+                self.points[self.dcel.vertices[edge]],
+                self.points[self.dcel.edge_endpoint(edge)],
+            );
+
+            let collapsed = a.distance_sq(b) < tol_sq && self.collapse_edge(edge);
+
+            // the slot holds another triangle after a collapse
+            if collapsed {
+                unchanged = 0;
+            } else {
+                unchanged += 1;
+                e += 1;
+            }
+        }
+    }
+}
+
+/// Returns the point halfway between `a` and `b`
+fn midpoint<T: Scalar>(a: Point<T>, b: Point<T>) -> Point<T> {
+    let two = T::from_f64(2.0);
+    Point::new((a.x + b.x) / two, (a.y + b.y) / two)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::testing::{assert_valid, random_points};
+    use crate::{Delaunay64, DelaunayBuilder, Triangle, Triangulation};
+
+    #[test]
+    fn needle() {
+        let mut points = (0..36)
+            .map(|i| {
+                Point::new(
+                    (i % 6) as f32 * 10.0 + (i / 6) as f32,
+                    (i / 6) as f32 * 10.0,
+                )
+            })
+            .collect::<Vec<_>>();
+        points.push(Point::new(points[14].x + 0.01, points[14].y + 0.005));
+
+        let mut t = Delaunay::new(&points).unwrap();
+        let before = t.dcel.num_triangles();
+        let hull = t.hull().len();
+
+        t.merge_close_vertices(0.1);
+        let points = t.points().to_vec();
+
+        assert_eq!(t.dcel.num_triangles(), before - 2);
+        assert_eq!(t.hull().len(), hull);
+        assert_valid(&t, &points);
+
+        let used = t
+            .dcel
+            .vertices
+            .iter()
+            .map(|v| v.as_usize())
+            .collect::<HashSet<_>>();
+        assert_eq!(used.len(), 36);
+
+        for (a, b, _) in t.dcel.wireframe_edges() {
+            assert!(points[a.as_usize()].distance_sq(points[b.as_usize()]) >= 0.01);
+        }
+    }
+
+    #[test]
+    fn needle_f64() {
+        // the pair is closer than the resolution of `f32` at these coordinates
+        let mut points = (0..36)
+            .map(|i| {
+                Point::new(
+                    (i % 6) as f64 * 10.0 + (i / 6) as f64,
+                    (i / 6) as f64 * 10.0,
+                )
+            })
+            .collect::<Vec<_>>();
+        points.push(Point::new(points[14].x + 1e-7, points[14].y + 5e-8));
+
+        let mut t = Delaunay64::new(&points).unwrap();
+        let before = t.dcel.num_triangles();
+
+        t.merge_close_vertices(1e-6);
+        let points = t.points().to_vec();
+
+        assert_eq!(t.dcel.num_triangles(), before - 2);
+        assert!(t
+            .dcel
+            .triangles(&points)
+            .all(|t| t.robust_orientation() > 0.0));
+        assert!(t.is_connected());
+    }
+
+    #[test]
+    fn normalized() {
+        let offset = Point::new(1.0e6, 2.0e6);
+        let mut points = random_points(400, 8);
+        let close = (0..40)
+            .map(|i| Point::new(points[i].x + 0.5, points[i].y))
+            .collect::<Vec<_>>();
+        points.extend(close);
+        let points = points.iter().map(|&p| p + offset).collect::<Vec<_>>();
+
+        let mut t = DelaunayBuilder::new()
+            .normalize(true)
+            .build(&points)
+            .unwrap();
+        t.merge_close_vertices(1.0);
+
+        // the owned points and the normalized ones the predicates run on
+        // moved together
+        let normalization = t.normalization().unwrap();
+        for (&p, &n) in t.points.iter().zip(&t.normalized) {
+            assert_eq!(normalization.apply(p), n);
+        }
+
+        let points = t.points().to_vec();
+        assert_valid(&t, &points);
+        for (a, b, _) in t.dcel.wireframe_edges() {
+            assert!(points[a.as_usize()].distance_sq(points[b.as_usize()]) >= 1.0);
+        }
+    }
+
+    /// Checks that the hull points kept the positions of the hull of
+    /// `points` and that every hull point is a convex or straight corner
+    fn assert_same_hull(t: &Delaunay, points: &[Point]) {
+        let expected = Delaunay::new(points)
+            .unwrap()
+            .hull()
+            .iter()
+            .map(|&p| points[p])
+            .collect::<Vec<_>>();
+
+        let hull = t.hull();
+        assert_eq!(hull.len(), expected.len());
+
+        for (i, &p) in hull.iter().enumerate() {
+            assert!(expected.contains(&t.points()[p]));
+
+            let corner = Triangle(
+                t.points()[p],
+                t.points()[hull[(i + 1) % hull.len()]],
+                t.points()[hull[(i + 2) % hull.len()]],
+            );
+            assert!(corner.robust_orientation() >= 0.0);
+        }
+    }
+
+    #[test]
+    fn hull_stays_convex() {
+        let points = vec![
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(10.0, 10.0),
+            Point::new(0.0, 10.0),
+            Point::new(5.0, -0.01),
+            Point::new(5.0, 0.05),
+            Point::new(4.0, 6.0),
+        ];
+
+        let mut t = Delaunay::new(&points).unwrap();
+        t.merge_close_vertices(0.1);
+        assert_eq!(t.dcel.num_triangles(), 5);
+        assert_same_hull(&t, &points);
+
+        // close pairs around the whole hull
+        let mut points = random_points(300, 9);
+        let center = Point::new(50.0, 50.0);
+        for p in Delaunay::new(&points).unwrap().hull().to_vec() {
+            let p = points[p];
+            points.push(p + (center - p).normalize() * 0.05);
+        }
+
+        let mut t = Delaunay::new(&points).unwrap();
+        t.merge_close_vertices(0.1);
+        assert_same_hull(&t, &points);
+        assert_valid(&t, t.points());
+    }
+
+    #[test]
+    fn hull_needle() {
+        let points = vec![
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(10.0, 10.0),
+            Point::new(0.0, 10.0),
+            Point::new(4.0, 6.0),
+            Point::new(10.0, 0.001),
+        ];
+
+        let mut t = Delaunay::new(&points).unwrap();
+        t.merge_close_vertices(0.1);
+        let points = t.points().to_vec();
+
+        assert_eq!(t.dcel.num_triangles(), 4);
+        assert_valid(&t, &points);
+
+        let mut hull = t.hull().to_vec();
+        assert_eq!(hull.len(), 4);
+
+        // the chain matches the boundary of the DCEL
+        let boundary = crate::boundary_edges(&t.dcel);
+        let first = hull
+            .iter()
+            .position(|&p| p == t.dcel.vertices[boundary[0]])
+            .unwrap();
+        hull.rotate_left(first);
+        for (p, e) in hull.into_iter().zip(boundary) {
+            assert_eq!(p, t.dcel.vertices[e]);
+            assert_eq!(t.hull_snapshot().boundary_triangle(p), Some(e));
+        }
+    }
+}
@@ -0,0 +1,602 @@
+//! Delaunay triangulation on the unit sphere.
+//!
+//! Points are stereographically projected from a pole far away from the data
+//! onto a plane, triangulated there with [`Delaunay64`], and the cap around
+//! the pole left uncovered by the planar triangulation is filled by
+//! triangulating the planar hull.
+//!
+//! Coordinates are taken and returned as `f32` only. The projection and the
+//! planar triangulation run in `f64` regardless, since points near the pole
+//! end up far away from the origin.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::dcel::{EdgeIndex, PointIndex, TrianglesAroundPoint, TrianglesDCEL};
+use crate::{Delaunay64, OptionIndex, Point64};
+
+/// Unit vector on the sphere
+pub type Vector3 = [f32; 3];
+
+/// Number of pole candidates tried by [`triangulate`]
+const POLE_CANDIDATES: usize = 64;
+
+/// Largest distance from the plane through the points for which they are
+/// considered to lie on a single circle
+const COPLANAR_TOLERANCE: f64 = 1e-12;
+
+/// Part of the sphere covered by a [`SphericalDelaunay`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Coverage {
+    /// Triangulate the whole sphere, including the cap around the projection pole
+    Sphere,
+
+    /// Triangulate only the region around the data, leaving the cap around
+    /// the projection pole uncovered
+    DataRegion,
+}
+
+/// Delaunay triangulation of points on the unit sphere.
+///
+/// Triangles are in counter-clockwise order when viewed from outside of
+/// the sphere.
+#[derive(Debug, Clone)]
+pub struct SphericalDelaunay {
+    /// Input points as unit vectors
+    pub points: Vec<Vector3>,
+
+    /// Triangles of the triangulation
+    pub dcel: TrianglesDCEL,
+
+    /// Pole used for the stereographic projection
+    pub pole: Vector3,
+
+    /// Maps point id to an edge going out of it
+    point_edges: Vec<OptionIndex<EdgeIndex>>,
+}
+
+/// Voronoi cell of a point on the sphere
+#[derive(Debug, Clone, PartialEq)]
+pub struct SphericalCell {
+    /// Cell vertices (circumcenters of the surrounding triangles) as unit vectors
+    pub vertices: Vec<Vector3>,
+
+    /// Great-circle length of the edge from `vertices[i]` to `vertices[i + 1]`.
+    ///
+    /// Closed cells also contain the length of the edge from the last vertex
+    /// back to the first one.
+    pub edge_lengths: Vec<f32>,
+}
+
+/// Triangulates the whole sphere.
+///
+/// Points are given as `(longitude, latitude)` in degrees.
+///
+/// # Examples
+/// ```
+/// # use triangulation::sphere;
+/// let octahedron = [
+///     (0.0, 0.0), (90.0, 0.0), (180.0, 0.0), (270.0, 0.0),
+///     (0.0, 90.0), (0.0, -90.0),
+/// ];
+/// let t = sphere::triangulate(&octahedron);
+/// assert_eq!(t.dcel.num_triangles(), 8);
+/// ```
+pub fn triangulate(lonlat_deg: &[(f32, f32)]) -> SphericalDelaunay {
+    triangulate_with(lonlat_deg, Coverage::Sphere)
+}
+
+/// Triangulates the given part of the sphere.
+///
+/// Points are given as `(longitude, latitude)` in degrees. The triangulation
+/// is empty if there are less than three distinct points, all of them lie on
+/// a single circle, or a coordinate is NaN or infinite.
+pub fn triangulate_with(lonlat_deg: &[(f32, f32)], coverage: Coverage) -> SphericalDelaunay {
+    let vectors = lonlat_deg
+        .iter()
+        .map(|&(lon, lat)| lonlat_to_vector(lon, lat))
+        .collect::<Vec<_>>();
+
+    let finite = lonlat_deg
+        .iter()
+        .all(|&(lon, lat)| lon.is_finite() && lat.is_finite());
+
+    let pole = choose_pole(&vectors);
+    let projected = vectors
+        .iter()
+        .map(|&v| stereographic(v, pole))
+        .collect::<Vec<_>>();
+
+    let planar = if finite && !on_one_circle(&vectors) {
+        Delaunay64::new(&projected).ok()
+    } else {
+        None
+    };
+
+    let mut triangles = Vec::new();
+
+    if let Some(planar) = planar {
+        triangles.extend(
+            planar
+                .dcel
+                .vertices
+                .chunks(3)
+                .map(|t| [t[0].as_usize(), t[1].as_usize(), t[2].as_usize()]),
+        );
+
+        if coverage == Coverage::Sphere {
+            fill_cap(&vectors, &mut triangles);
+        }
+    }
+
+    let dcel = build_dcel(&triangles);
+    let mut point_edges = vec![OptionIndex::none(); vectors.len()];
+
+    for (e, &p) in dcel.vertices.iter().enumerate() {
+        point_edges[p.as_usize()] = OptionIndex::some(e.into());
+    }
+
+    SphericalDelaunay {
+        points: vectors.iter().map(|&v| to_f32(v)).collect(),
+        dcel,
+        pole: to_f32(pole),
+        point_edges,
+    }
+}
+
+impl SphericalDelaunay {
+    /// Returns the circumcenter of the triangle as a unit vector
+    pub fn circumcenter(&self, t: EdgeIndex) -> Vector3 {
+        let [a, b, c] = self.dcel.triangle_points(t);
+        let a = to_f64(self.points[a.as_usize()]);
+        let b = to_f64(self.points[b.as_usize()]);
+        let c = to_f64(self.points[c.as_usize()]);
+
+        to_f32(normalize(cross(sub(b, a), sub(c, a))))
+    }
+
+    /// Returns the Voronoi cell of every input point.
+    ///
+    /// With [`Coverage::DataRegion`] the cells of the points on the border of
+    /// the region are open. Points which were skipped during triangulation
+    /// (duplicates) have empty cells.
+    pub fn voronoi_cells(&self) -> Vec<SphericalCell> {
+        self.point_edges
+            .iter()
+            .map(|e| {
+                let around = e
+                    .get()
+                    .map(|e| TrianglesAroundPoint::new(&self.dcel, e).collect::<Vec<_>>())
+                    .unwrap_or_default();
+
+                let closed = around
+                    .first()
+                    .map_or(false, |&e| self.dcel.twin(e).is_some());
+
+                let vertices = around
+                    .iter()
+                    .map(|&e| self.circumcenter(e))
+                    .collect::<Vec<_>>();
+
+                let mut edge_lengths = vertices
+                    .windows(2)
+                    .map(|w| arc_length(w[0], w[1]))
+                    .collect::<Vec<_>>();
+
+                if closed && vertices.len() > 1 {
+                    edge_lengths.push(arc_length(vertices[vertices.len() - 1], vertices[0]));
+                }
+
+                SphericalCell {
+                    vertices,
+                    edge_lengths,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Returns the great-circle distance between two unit vectors in radians
+pub fn arc_length(a: Vector3, b: Vector3) -> f32 {
+    let (a, b) = (to_f64(a), to_f64(b));
+
+    // atan2 stays accurate for both tiny and nearly antipodal arcs
+    let sin = length(cross(a, b));
+    let cos = dot(a, b);
+    sin.atan2(cos) as f32
+}
+
+/// Converts longitude and latitude in degrees to a unit vector
+fn lonlat_to_vector(lon: f32, lat: f32) -> [f64; 3] {
+    let (lon, lat) = (f64::from(lon).to_radians(), f64::from(lat).to_radians());
+    [lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin()]
+}
+
+/// Picks the projection pole with the largest angular distance to the
+/// nearest point.
+///
+/// Candidates are the direction opposite to the mean of the points and
+/// a Fibonacci lattice covering the sphere evenly.
+fn choose_pole(points: &[[f64; 3]]) -> [f64; 3] {
+    let mean = points.iter().fold([0.0; 3], |acc, &p| add(acc, p));
+    let golden_angle = std::f64::consts::PI * (3.0 - 5.0f64.sqrt());
+
+    let lattice = (0..POLE_CANDIDATES).map(|i| {
+        let z = 1.0 - (2 * i + 1) as f64 / POLE_CANDIDATES as f64;
+        let r = (1.0 - z * z).sqrt();
+        let (sin, cos) = (golden_angle * i as f64).sin_cos();
+        [r * cos, r * sin, z]
+    });
+
+    let opposite = if length(mean) > 1e-6 * points.len() as f64 {
+        Some(normalize([-mean[0], -mean[1], -mean[2]]))
+    } else {
+        None
+    };
+
+    // the largest distance means the smallest maximal dot product
+    let closeness = |c: [f64; 3]| {
+        points
+            .iter()
+            .map(|&p| dot(p, c))
+            .fold(f64::NEG_INFINITY, f64::max)
+    };
+
+    opposite
+        .into_iter()
+        .chain(lattice)
+        .map(|c| (closeness(c), c))
+        .fold((f64::INFINITY, [0.0, 0.0, 1.0]), |best, cur| {
+            if cur.0 < best.0 {
+                cur
+            } else {
+                best
+            }
+        })
+        .1
+}
+
+/// Returns true if the points lie on a single plane, which cuts the sphere in
+/// a circle, or there are less than three distinct points
+fn on_one_circle(points: &[[f64; 3]]) -> bool {
+    let a = match points.first() {
+        Some(&a) => a,
+        None => return true,
+    };
+
+    let largest = |best: [f64; 3], v: [f64; 3]| if length(v) > length(best) { v } else { best };
+    let ab = points.iter().map(|&b| sub(b, a)).fold([0.0; 3], largest);
+    let normal = points
+        .iter()
+        .map(|&c| cross(ab, sub(c, a)))
+        .fold([0.0; 3], largest);
+
+    if length(normal) == 0.0 {
+        return true;
+    }
+
+    let normal = normalize(normal);
+    points
+        .iter()
+        .all(|&p| dot(normal, sub(p, a)).abs() <= COPLANAR_TOLERANCE)
+}
+
+/// Projects the point stereographically from the pole onto the plane through
+/// the origin perpendicular to the pole.
+///
+/// The plane basis is chosen so that right-handed planar triangles are
+/// counter-clockwise when viewed from outside of the sphere.
+fn stereographic(v: [f64; 3], pole: [f64; 3]) -> Point64 {
+    let (u, w) = plane_basis(pole);
+    let d = 1.0 - dot(v, pole);
+
+    Point64::new(dot(v, u) / d, dot(v, w) / d)
+}
+
+/// Returns two unit vectors `u` and `w` orthogonal to the pole with
+/// `u × w = pole`
+fn plane_basis(pole: [f64; 3]) -> ([f64; 3], [f64; 3]) {
+    let helper = if pole[0].abs() < 0.9 {
+        [1.0, 0.0, 0.0]
+    } else {
+        [0.0, 1.0, 0.0]
+    };
+
+    let u = normalize(cross(helper, pole));
+    let w = cross(pole, u);
+    (u, w)
+}
+
+/// Triangulates the cap around the pole bounded by the hull of the planar
+/// triangulation.
+fn fill_cap(points: &[[f64; 3]], triangles: &mut Vec<[usize; 3]>) {
+    let edges = triangles
+        .iter()
+        .flat_map(|&[a, b, c]| vec![(a, b), (b, c), (c, a)])
+        .collect::<Vec<_>>();
+
+    let inner = edges.iter().cloned().collect::<HashSet<_>>();
+
+    // boundary edges reversed, so the cap is on their left
+    let next = edges
+        .iter()
+        .filter(|&&(a, b)| !inner.contains(&(b, a)))
+        .map(|&(a, b)| (b, a))
+        .collect::<HashMap<_, _>>();
+
+    let start = match next.keys().next() {
+        Some(&start) => start,
+        None => return,
+    };
+
+    let mut cycle = vec![start];
+    let mut current = next[&start];
+
+    while current != start && cycle.len() <= next.len() {
+        cycle.push(current);
+        current = next[&current];
+    }
+
+    if cycle.len() >= 3 {
+        triangulate_polygon(points, &cycle, 0, cycle.len() - 1, triangles);
+    }
+}
+
+/// Delaunay-triangulates the part of the polygon between `cycle[i]` and
+/// `cycle[j]` closed by the edge from `cycle[j]` to `cycle[i]`.
+fn triangulate_polygon(
+    points: &[[f64; 3]],
+    cycle: &[usize],
+    i: usize,
+    j: usize,
+    triangles: &mut Vec<[usize; 3]>,
+) {
+    if j < i + 2 {
+        return;
+    }
+
+    let a = points[cycle[j]];
+    let b = points[cycle[i]];
+
+    // candidate circles through the edge are totally ordered, so the one
+    // containing no other candidate is found in a single pass
+    let mut best = i + 1;
+
+    for k in i + 2..j {
+        if in_circumcap(a, b, points[cycle[best]], points[cycle[k]]) {
+            best = k;
+        }
+    }
+
+    triangles.push([cycle[j], cycle[i], cycle[best]]);
+    triangulate_polygon(points, cycle, i, best, triangles);
+    triangulate_polygon(points, cycle, best, j, triangles);
+}
+
+/// Returns true if `d` lies in the circumcap of counter-clockwise triangle
+/// `a`, `b`, `c`
+fn in_circumcap(a: [f64; 3], b: [f64; 3], c: [f64; 3], d: [f64; 3]) -> bool {
+    dot(sub(d, a), cross(sub(b, a), sub(c, a))) > 0.0
+}
+
+/// Builds a DCEL from counter-clockwise triangles, linking shared edges
+fn build_dcel(triangles: &[[usize; 3]]) -> TrianglesDCEL {
+    let mut dcel = TrianglesDCEL::with_capacity(triangles.len());
+    let mut edges = HashMap::with_capacity(3 * triangles.len());
+
+    for &[a, b, c] in triangles {
+        let t = dcel.add_triangle([a.into(), b.into(), c.into()]);
+
+        for e in dcel.triangle_edges(t).iter().cloned() {
+            let from: PointIndex = dcel.vertices[e];
+            let to = dcel.edge_endpoint(e);
+
+            if let Some(twin) = edges.remove(&(to, from)) {
+                dcel.link(e, twin);
+            } else {
+                edges.insert((from, to), e);
+            }
+        }
+    }
+
+    dcel
+}
+
+#[inline]
+fn to_f64(v: Vector3) -> [f64; 3] {
+    [f64::from(v[0]), f64::from(v[1]), f64::from(v[2])]
+}
+
+#[inline]
+fn to_f32(v: [f64; 3]) -> Vector3 {
+    [v[0] as f32, v[1] as f32, v[2] as f32]
+}
+
+#[inline]
+fn add(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+#[inline]
+fn sub(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+#[inline]
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+#[inline]
+fn cross(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+#[inline]
+fn length(a: [f64; 3]) -> f64 {
+    dot(a, a).sqrt()
+}
+
+#[inline]
+fn normalize(a: [f64; 3]) -> [f64; 3] {
+    let len = length(a);
+    [a[0] / len, a[1] / len, a[2] / len]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn icosahedron() -> Vec<(f32, f32)> {
+        let lat = 0.5f32.atan().to_degrees();
+        let mut points = vec![(0.0, 90.0), (0.0, -90.0)];
+
+        for i in 0..5 {
+            points.push((i as f32 * 72.0, lat));
+            points.push((i as f32 * 72.0 + 36.0, -lat));
+        }
+
+        points
+    }
+
+    fn edge_set(t: &SphericalDelaunay) -> HashSet<(usize, usize)> {
+        t.dcel
+            .wireframe_edges()
+            .map(|(a, b, _)| {
+                (
+                    a.as_usize().min(b.as_usize()),
+                    a.as_usize().max(b.as_usize()),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn icosahedron_edges() {
+        let t = triangulate(&icosahedron());
+        assert_eq!(t.dcel.num_triangles(), 20);
+        assert!(t.dcel.halfedges.iter().all(|e| e.is_some()));
+
+        // icosahedron edges connect vertices at cos(angle) = 1 / sqrt(5)
+        let mut expected = HashSet::new();
+        for a in 0..t.points.len() {
+            for b in a + 1..t.points.len() {
+                let cos = dot(to_f64(t.points[a]), to_f64(t.points[b]));
+                if (cos - 1.0 / 5.0f64.sqrt()).abs() < 1e-4 {
+                    expected.insert((a, b));
+                }
+            }
+        }
+
+        assert_eq!(expected.len(), 30);
+        assert_eq!(edge_set(&t), expected);
+    }
+
+    #[test]
+    fn icosahedron_voronoi() {
+        let t = triangulate(&icosahedron());
+        let cells = t.voronoi_cells();
+        assert_eq!(cells.len(), 12);
+
+        // the dual of an icosahedron is a dodecahedron
+        let side = arc_length(
+            t.circumcenter(0.into()),
+            t.circumcenter(t.dcel.twin(0.into()).unwrap()),
+        );
+
+        for cell in &cells {
+            assert_eq!(cell.vertices.len(), 5);
+            assert_eq!(cell.edge_lengths.len(), 5);
+            for &l in &cell.edge_lengths {
+                assert!((l - side).abs() < 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn counter_clockwise() {
+        let t = triangulate(&icosahedron());
+
+        for e in (0..t.dcel.vertices.len()).step_by(3) {
+            let [a, b, c] = t.dcel.triangle_points(e.into());
+            let (a, b, c) = (
+                to_f64(t.points[a.as_usize()]),
+                to_f64(t.points[b.as_usize()]),
+                to_f64(t.points[c.as_usize()]),
+            );
+            assert!(dot(a, cross(b, c)) > 0.0);
+        }
+    }
+
+    #[test]
+    fn empty_circumcaps() {
+        let mut seed = 7u32;
+        let mut random = || {
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+            seed as f32 / u32::MAX as f32
+        };
+
+        let points = (0..200)
+            .map(|_| {
+                (
+                    random() * 360.0 - 180.0,
+                    (random() * 2.0 - 1.0).asin().to_degrees(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let t = triangulate(&points);
+        assert_eq!(t.dcel.num_triangles(), 2 * points.len() - 4);
+
+        for e in (0..t.dcel.vertices.len()).step_by(3) {
+            let tri = t.dcel.triangle_points(e.into());
+            let [a, b, c] = [
+                to_f64(t.points[tri[0].as_usize()]),
+                to_f64(t.points[tri[1].as_usize()]),
+                to_f64(t.points[tri[2].as_usize()]),
+            ];
+
+            for (i, &p) in t.points.iter().enumerate() {
+                if tri.iter().all(|v| v.as_usize() != i) {
+                    assert!(!in_circumcap(a, b, c, to_f64(p)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn data_region() {
+        let points = icosahedron();
+        let full = triangulate_with(&points, Coverage::Sphere);
+        let region = triangulate_with(&points, Coverage::DataRegion);
+
+        assert!(region.dcel.num_triangles() < full.dcel.num_triangles());
+        assert!(edge_set(&region).is_subset(&edge_set(&full)));
+    }
+
+    #[test]
+    fn degenerate() {
+        let equator = [(0.0, 0.0), (90.0, 0.0), (180.0, 0.0), (270.0, 0.0)];
+        let parallel = (0..6).map(|i| (i as f32 * 60.0, 30.0)).collect::<Vec<_>>();
+        let mut nan = icosahedron();
+        nan[3].0 = f32::NAN;
+
+        for points in &[&equator[..], &parallel, &nan] {
+            for &coverage in &[Coverage::Sphere, Coverage::DataRegion] {
+                let t = triangulate_with(points, coverage);
+                assert_eq!(t.points.len(), points.len());
+                assert_eq!(t.dcel.num_triangles(), 0);
+            }
+        }
+
+        // one point off the equator is enough
+        let mut points = equator.to_vec();
+        points.push((45.0, 10.0));
+        assert_eq!(triangulate(&points).dcel.num_triangles(), 6);
+    }
+}
@@ -0,0 +1,77 @@
+//! Helpers shared by the unit tests
+
+use std::collections::HashSet;
+
+use rand::{Rng, SeedableRng};
+
+use crate::{boundary_edges, Delaunay, Point, PointIndex, Scalar, Triangulation};
+
+/// Returns `count` points spread uniformly over the square from 0 to 100
+pub fn random_points(count: usize, seed: u64) -> Vec<Point> {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+
+    (0..count)
+        .map(|_| Point::new(rng.gen_range(0.0, 100.0), rng.gen_range(0.0, 100.0)))
+        .collect()
+}
+
+/// Returns the center of a circle followed by `count` points evenly spaced
+/// on it
+pub fn circular(count: usize) -> Vec<Point> {
+    let mut points = Vec::with_capacity(count + 1);
+    points.push(Point::new(100.0, 100.0));
+
+    for i in 0..count {
+        let angle = i as f32 / count as f32 * 2.0 * std::f32::consts::PI;
+        let (sin, cos) = angle.sin_cos();
+        points.push(Point::new(cos * 100.0 + 100.0, sin * 100.0 + 100.0));
+    }
+
+    points
+}
+
+/// Returns the edges of the triangulation, each once with the lower point
+/// first
+pub fn edge_set<T: Scalar>(t: &Delaunay<T>) -> HashSet<(PointIndex, PointIndex)> {
+    t.dcel
+        .wireframe_edges()
+        .map(|(a, b, _)| if a < b { (a, b) } else { (b, a) })
+        .collect()
+}
+
+/// Checks that the twins are consistent, the triangles are connected and
+/// counter-clockwise, and the hull chain and points follow the boundary
+pub fn assert_valid<T: Scalar>(t: &Delaunay<T>, points: &[Point<T>]) {
+    assert_eq!(t.dcel.validate(), Ok(()));
+    assert!(t.is_connected());
+    assert!(t
+        .dcel
+        .triangles(points)
+        .all(|t| t.robust_orientation() > 0.0));
+
+    let boundary = boundary_edges(&t.dcel);
+    let snapshot = t.hull_snapshot();
+    assert_eq!(snapshot.chain().len(), boundary.len());
+
+    for e in boundary {
+        let p = t.dcel.vertices[e];
+        assert_eq!(snapshot.next(p), Some(t.dcel.edge_endpoint(e)));
+        assert_eq!(snapshot.boundary_triangle(p), Some(e));
+    }
+
+    let mut hull = t.hull().to_vec();
+    let offset = hull.iter().position(|&p| p == snapshot.start()).unwrap();
+    hull.rotate_left(offset);
+    assert_eq!(snapshot.chain(), hull);
+}
+
+/// Checks [`assert_valid`] and that no point lies inside the circumcircle of
+/// a neighboring triangle
+pub fn assert_delaunay<T: Scalar>(t: &Delaunay<T>, points: &[Point<T>]) {
+    assert_valid(t, points);
+
+    for (e, twin) in t.dcel.interior_edges() {
+        let opposite = points[t.dcel.vertices[t.dcel.prev_edge(twin)]];
+        assert!(!t.dcel.triangle(e, points).in_circumcircle(opposite));
+    }
+}
@@ -0,0 +1,364 @@
+//! Construction in tiles, which are triangulated on their own and then
+//! stitched together
+
+use std::ops::Range;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::divconq::{Half, Side};
+use crate::geom::{Point, Rect, Scalar, Triangle};
+use crate::{Delaunay, DelaunayBuilder, DelaunayError};
+
+impl<T: Scalar> Delaunay<T> {
+    /// Triangulates a set of given points like [`new`](Delaunay::new), but
+    /// in a grid of about `tile_count` tiles over their bounding box.
+    ///
+    /// Every tile is triangulated on its own, in parallel with the `parallel`
+    /// feature. The tiles are stitched with the merge of the
+    /// [divide-and-conquer](Delaunay::new_divconq) construction, first the
+    /// tiles of every column from the bottom up and then the columns from
+    /// left to right. On a single thread this is only somewhat faster than
+    /// [`new`](Delaunay::new) on large uniform input, about 1.3 times on a
+    /// million points, and the stitching keeps a second copy of the
+    /// triangulation in memory while the result is built.
+    ///
+    /// Cells which can't be triangulated on their own, because they have too
+    /// few points or only collinear ones, are merged with the next cell. If a
+    /// tile can't be triangulated anyway, all points are triangulated in a
+    /// single pass instead.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Delaunay, Point};
+    /// let points = (0..100)
+    ///     .map(|i| Point::new((i % 10) as f32 + 0.01 * i as f32, (i / 10) as f32))
+    ///     .collect::<Vec<_>>();
+    ///
+    /// let tiled = Delaunay::new_tiled(&points, 4).unwrap();
+    /// let single = Delaunay::new(&points).unwrap();
+    /// assert_eq!(tiled.dcel.num_triangles(), single.dcel.num_triangles());
+    /// ```
+    pub fn new_tiled(points: &[Point<T>], tile_count: usize) -> Result<Delaunay<T>, DelaunayError> {
+        DelaunayBuilder::new().tiles(tile_count).build(points)
+    }
+
+    pub(crate) fn triangulate_tiled(
+        points: &[Point<T>],
+        tile_count: usize,
+    ) -> Result<Delaunay<T>, DelaunayError> {
+        let columns = tiles(points, tile_count);
+
+        if columns.iter().map(Vec::len).sum::<usize>() <= 1 {
+            return Delaunay::triangulate(points);
+        }
+
+        // the tiles are numbered column by column from the bottom up, so the
+        // points of every group of adjacent tiles are a range of `order`
+        let order = columns
+            .iter()
+            .flatten()
+            .flatten()
+            .copied()
+            .collect::<Vec<_>>();
+        let sorted = order.iter().map(|&i| points[i]).collect::<Vec<_>>();
+
+        let mut offset = 0;
+        let columns = columns
+            .iter()
+            .map(|column| {
+                column
+                    .iter()
+                    .map(|tile| {
+                        offset += tile.len();
+                        offset - tile.len()..offset
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        match stitch_tiles(columns, &sorted) {
+            Some(part) => {
+                let mut delaunay = Delaunay::from_dcel(part.half.into_dcel(&order), points);
+                delaunay.flips = part.flips;
+                Ok(delaunay)
+            }
+            None => Delaunay::triangulate(points),
+        }
+    }
+}
+
+/// Buckets the points into a grid of about `tile_count` cells over their
+/// bounding box.
+///
+/// Returns the tiles of every column from the bottom up, after merging the
+/// columns and cells which can't be triangulated on their own into the next
+/// ones.
+fn tiles<T: Scalar>(points: &[Point<T>], tile_count: usize) -> Vec<Vec<Vec<usize>>> {
+    let rect = match Rect::from_points(points) {
+        Some(rect) => rect,
+        None => return Vec::new(),
+    };
+
+    let columns = (tile_count.max(1) as f32).sqrt().ceil() as usize;
+    let rows = (tile_count.max(1) + columns - 1) / columns;
+
+    // the cell never decreases with the coordinate, so neighboring cells are
+    // separated by a line
+    let cell = |v: T, min: T, size: T, count: usize| {
+        if size > T::ZERO {
+            (((v - min).to_f64() / size.to_f64() * count as f64) as usize).min(count - 1)
+        } else {
+            0
+        }
+    };
+
+    let mut grid = vec![vec![Vec::new(); rows]; columns];
+    for (i, p) in points.iter().enumerate() {
+        let column = cell(p.x, rect.min.x, rect.width(), columns);
+        let row = cell(p.y, rect.min.y, rect.height(), rows);
+        grid[column][row].push(i);
+    }
+
+    let grid = coalesce(
+        grid,
+        |column| triangulable(column.iter().flatten(), points),
+        |column, next| {
+            for (cell, next) in column.iter_mut().zip(next) {
+                cell.extend(next);
+            }
+        },
+    );
+
+    grid.into_iter()
+        .map(|column| {
+            coalesce(
+                column,
+                |cell| triangulable(cell.iter(), points),
+                |cell, next| cell.extend(next),
+            )
+        })
+        .collect()
+}
+
+/// Merges every item for which `ok` is false into the next one, and the
+/// remaining ones at the end into the last group
+fn coalesce<T>(items: Vec<T>, ok: impl Fn(&T) -> bool, merge: impl Fn(&mut T, T)) -> Vec<T> {
+    let mut groups: Vec<T> = Vec::new();
+    let mut pending: Option<T> = None;
+
+    for item in items {
+        let item = match pending.take() {
+            Some(mut group) => {
+                merge(&mut group, item);
+                group
+            }
+            None => item,
+        };
+
+        if ok(&item) {
+            groups.push(item);
+        } else {
+            pending = Some(item);
+        }
+    }
+
+    if let Some(rest) = pending {
+        match groups.last_mut() {
+            Some(last) => merge(last, rest),
+            None => groups.push(rest),
+        }
+    }
+
+    groups
+}
+
+/// Returns true if the points aren't all collinear
+fn triangulable<'a, T: Scalar>(
+    mut indices: impl Iterator<Item = &'a usize>,
+    points: &[Point<T>],
+) -> bool {
+    let a = match indices.next() {
+        Some(&i) => points[i],
+        None => return false,
+    };
+
+    let mut b = None;
+    indices.any(|&i| match b {
+        Some(b) => Triangle(a, b, points[i]).robust_orientation() != 0.0,
+        None => {
+            if points[i] != a {
+                b = Some(points[i]);
+            }
+            false
+        }
+    })
+}
+
+/// Triangulates the tiles, given by their ranges of the sorted points, and
+/// stitches them, every column from the bottom up and then the columns from
+/// left to right
+fn stitch_tiles<T: Scalar>(columns: Vec<Vec<Range<usize>>>, points: &[Point<T>]) -> Option<Part> {
+    // turned a quarter clockwise, the tiles of a column lie side by side from
+    // left to right like the columns, which the merge needs
+    let turned = points
+        .iter()
+        .map(|p| Point::new(p.y, -p.x))
+        .collect::<Vec<_>>();
+
+    let stitch_column = |column: Vec<Range<usize>>| {
+        #[cfg(feature = "parallel")]
+        let iter = column.into_par_iter();
+
+        #[cfg(not(feature = "parallel"))]
+        let iter = column.into_iter();
+
+        let parts = iter
+            .map(|tile| Part::new(tile, points))
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(merge_all(parts, Side::Left, &turned))
+    };
+
+    #[cfg(feature = "parallel")]
+    let iter = columns.into_par_iter();
+
+    #[cfg(not(feature = "parallel"))]
+    let iter = columns.into_iter();
+
+    let columns = iter.map(stitch_column).collect::<Option<Vec<_>>>()?;
+    Some(merge_all(columns, Side::Left, points))
+}
+
+/// Merges the parts, each of them lying on the right of the previous ones,
+/// in halves like the divide-and-conquer construction.
+///
+/// `side` is the side the merged part takes in the next merge.
+fn merge_all<T: Scalar>(mut parts: Vec<Part>, side: Side, points: &[Point<T>]) -> Part {
+    if parts.len() == 1 {
+        let part = parts.pop().unwrap();
+
+        return Part {
+            half: part.half.with_side(side, points),
+            flips: part.flips,
+        };
+    }
+
+    let right = parts.split_off(parts.len() / 2);
+
+    #[cfg(feature = "parallel")]
+    let (left, right) = rayon::join(
+        || merge_all(parts, Side::Left, points),
+        || merge_all(right, Side::Right, points),
+    );
+
+    #[cfg(not(feature = "parallel"))]
+    let (left, right) = (
+        merge_all(parts, Side::Left, points),
+        merge_all(right, Side::Right, points),
+    );
+
+    Part {
+        half: left.half.merge(right.half, side, points),
+        flips: left.flips + right.flips,
+    }
+}
+
+/// Triangulation of a tile, or of adjacent tiles merged together
+struct Part {
+    half: Half,
+    flips: usize,
+}
+
+impl Part {
+    /// Triangulates the points of a tile
+    fn new<T: Scalar>(tile: Range<usize>, points: &[Point<T>]) -> Option<Part> {
+        let delaunay = Delaunay::triangulate(&points[tile.clone()]).ok()?;
+
+        Some(Part {
+            half: Half::from_dcel(&delaunay.dcel, tile.start),
+            flips: delaunay.flips,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{assert_delaunay, edge_set, random_points};
+    use crate::Triangulation;
+
+    #[test]
+    fn uniform_matches_single_pass() {
+        for &(count, tiles) in &[(100, 4), (2000, 9), (2000, 64), (20000, 64)] {
+            let points = random_points(count, count as u64);
+
+            let single = Delaunay::new(&points).unwrap();
+            let tiled = Delaunay::new_tiled(&points, tiles).unwrap();
+
+            assert_delaunay(&tiled, &points);
+            assert_eq!(edge_set(&tiled), edge_set(&single), "{} {}", count, tiles);
+            assert_eq!(tiled.hull().len(), single.hull().len());
+            assert!(tiled.flip_count() > 0);
+        }
+    }
+
+    #[test]
+    fn uniform_f64() {
+        let points = random_points(5000, 3)
+            .iter()
+            .map(|p| Point::new(f64::from(p.x) + 1e6, f64::from(p.y) + 1e6))
+            .collect::<Vec<_>>();
+
+        let single = Delaunay::new(&points).unwrap();
+        let tiled = Delaunay::new_tiled(&points, 16).unwrap();
+
+        assert_eq!(edge_set(&tiled), edge_set(&single));
+    }
+
+    #[test]
+    fn grid() {
+        let points = (0..100 * 100)
+            .map(|i| Point::new((i % 100) as f32, (i / 100) as f32))
+            .collect::<Vec<_>>();
+
+        for &tiles in &[4, 16, 64] {
+            let t = Delaunay::new_tiled(&points, tiles).unwrap();
+
+            assert_delaunay(&t, &points);
+            assert_eq!(t.dcel.num_triangles(), 2 * 99 * 99);
+            assert_eq!(t.hull().len(), 4 * 99);
+        }
+    }
+
+    #[test]
+    fn degenerate_cells() {
+        // two clusters leave empty cells in between, and the points on the
+        // right edge are the only ones in their column
+        let mut points = random_points(500, 1);
+        points.extend(
+            random_points(500, 2)
+                .iter()
+                .map(|p| Point::new(p.x + 500.0, 10.0 * p.y)),
+        );
+        points.extend((0..5).map(|i| Point::new(2000.0, 100.0 + 200.0 * i as f32)));
+
+        let single = Delaunay::new(&points).unwrap();
+        let tiled = Delaunay::new_tiled(&points, 16).unwrap();
+
+        assert_delaunay(&tiled, &points);
+        assert_eq!(edge_set(&tiled), edge_set(&single));
+    }
+
+    #[test]
+    fn collinear() {
+        let points = (0..10)
+            .map(|i| Point::new(i as f32, 2.0 * i as f32))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            Delaunay::new_tiled(&points, 4).err(),
+            Some(DelaunayError::AllCollinear)
+        );
+    }
+}
@@ -0,0 +1,744 @@
+//! Voronoi diagram, the dual of the Delaunay triangulation
+
+use crate::dcel::{EdgeIndex, TrianglesAroundPoint, TrianglesDCEL};
+use crate::geom::{Point, Rect, Scalar};
+use crate::{Delaunay, DelaunayError, OptionIndex, PointIndex, Triangulation};
+
+/// Voronoi diagram view over a triangulation.
+///
+/// Every input point is a site, every triangle is a Voronoi vertex located at
+/// its circumcenter.
+#[derive(Debug, Clone)]
+pub struct Voronoi<'a, T = f32> {
+    dcel: &'a TrianglesDCEL,
+    points: &'a [Point<T>],
+
+    /// Maps site index to an edge going out of it
+    site_edges: Vec<OptionIndex<EdgeIndex>>,
+
+    /// Index of the first frame point, or the number of points
+    frame_start: usize,
+
+    /// Whether the frame points are left out of the sites
+    exclude_frame: bool,
+}
+
+/// Voronoi cell of a single site
+#[derive(Debug, Clone, PartialEq)]
+pub struct VoronoiCell<T = f32> {
+    /// Voronoi vertices in counter-clockwise order.
+    ///
+    /// For unbounded cells this is an open polyline between the two rays.
+    pub vertices: Vec<Point<T>>,
+
+    /// Whether the cell extends to infinity, which is the case for hull points
+    pub unbounded: bool,
+
+    /// Directions of the rays going to infinity from the first and from the
+    /// last vertex of an unbounded cell
+    pub rays: Option<[Point<T>; 2]>,
+}
+
+impl<'a, T: Scalar> Voronoi<'a, T> {
+    /// Creates the Voronoi view of the triangulation of `points`.
+    ///
+    /// The triangulation has to be Delaunay in `points`, otherwise the cells
+    /// overlap.
+    ///
+    /// If the triangulation has a bounding frame, `points` must contain its
+    /// positions too, like [`Delaunay::points`] does.
+    pub fn new<D: Triangulation<Scalar = T> + ?Sized>(
+        triangulation: &'a D,
+        points: &'a [Point<T>],
+    ) -> Voronoi<'a, T> {
+        let dcel = triangulation.dcel();
+        let mut site_edges = vec![OptionIndex::none(); points.len()];
+
+        for (e, &p) in dcel.vertices.iter().enumerate() {
+            site_edges[p.as_usize()] = OptionIndex::some(e.into());
+        }
+
+        let frame_start = triangulation
+            .frame_points()
+            .iter()
+            .map(|p| p.as_usize())
+            .min()
+            .unwrap_or(points.len());
+
+        Voronoi {
+            dcel,
+            points,
+            site_edges,
+            frame_start,
+            exclude_frame: true,
+        }
+    }
+
+    /// Excludes the frame points added by
+    /// [`DelaunayBuilder::bounding_frame`](crate::DelaunayBuilder::bounding_frame)
+    /// from the sites, which is the default. Without a frame this does
+    /// nothing.
+    pub fn exclude_frame(mut self, exclude: bool) -> Voronoi<'a, T> {
+        self.exclude_frame = exclude;
+        self
+    }
+
+    /// Returns the number of sites
+    pub fn num_sites(&self) -> usize {
+        if self.exclude_frame {
+            self.frame_start.min(self.points.len())
+        } else {
+            self.points.len()
+        }
+    }
+
+    /// Returns true if the cell of the site is bounded, i.e. the site is not
+    /// on the hull and is part of the triangulation.
+    pub fn is_bounded(&self, site: usize) -> bool {
+        self.site_triangles(site)
+            .next()
+            .map_or(false, |e| self.dcel.twin(e).is_some())
+    }
+
+    /// Returns the triangles around the site in counter-clockwise order,
+    /// empty if the site is not part of the triangulation.
+    fn site_triangles(&self, site: usize) -> impl Iterator<Item = EdgeIndex> + 'a {
+        let dcel = self.dcel;
+        self.site_edges[site]
+            .get()
+            .into_iter()
+            .flat_map(move |e| TrianglesAroundPoint::new(dcel, e))
+    }
+
+    /// Returns the cell of every site.
+    ///
+    /// Cells of points which are not part of the triangulation have no
+    /// vertices.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{voronoi::Voronoi, Delaunay, Point};
+    /// let points = &[
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(100.0, 0.0),
+    ///     Point::new(100.0, 100.0),
+    ///     Point::new(0.0, 100.0),
+    ///     Point::new(50.0, 50.0),
+    ///     Point::new(50.0, 50.0),
+    /// ];
+    /// let t = Delaunay::new(points).unwrap();
+    /// let cells = Voronoi::new(&t, points).cells();
+    ///
+    /// assert!(!cells[4].unbounded);
+    /// assert_eq!(cells[4].vertices.len(), 4);
+    /// assert!(cells[0].unbounded);
+    /// assert!(cells[5].vertices.is_empty());
+    /// ```
+    pub fn cells(&self) -> Vec<VoronoiCell<T>> {
+        let dcel = self.dcel;
+        let point = |p: crate::PointIndex| self.points[p.as_usize()];
+
+        // outward normal of a boundary edge, the triangles are right-handed
+        let normal = |a: Point<T>, b: Point<T>| {
+            let (dx, dy) = (b.x - a.x, b.y - a.y);
+            let len = (dx * dx + dy * dy).sqrt();
+            Point::new(-dy / len, dx / len)
+        };
+
+        (0..self.num_sites())
+            .map(|site| {
+                let around = self.site_triangles(site).collect::<Vec<_>>();
+
+                let vertices = around
+                    .iter()
+                    .map(|&e| dcel.triangle(e, self.points).circumcenter())
+                    .collect::<Vec<_>>();
+
+                let rays = match (around.first(), around.last()) {
+                    (Some(&first), Some(&last)) if dcel.twin(first).is_none() => {
+                        let p = self.points[site];
+                        let prev = point(dcel.vertices[dcel.prev_edge(last)]);
+                        Some([normal(p, point(dcel.edge_endpoint(first))), normal(prev, p)])
+                    }
+                    _ => None,
+                };
+
+                VoronoiCell {
+                    vertices,
+                    unbounded: rays.is_some(),
+                    rays,
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the cell of the site clipped to `bounds`, as a polygon in
+    /// counter-clockwise order.
+    ///
+    /// The cell is the intersection of the rectangle with the half-planes
+    /// closer to the site than to each of its neighbors, so cells of hull
+    /// points are finite as well. Sites which are not part of the
+    /// triangulation have empty cells.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Delaunay, Point, Rect};
+    /// let points = &[
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(10.0, 0.0),
+    ///     Point::new(0.0, 10.0),
+    /// ];
+    /// let t = Delaunay::new(points).unwrap();
+    /// let bounds = Rect::new(Point::new(0.0, 0.0), Point::new(10.0, 10.0));
+    /// let cell = t.voronoi().clipped_cell(0, bounds);
+    ///
+    /// assert_eq!(cell.len(), 4);
+    /// assert!(cell.contains(&Point::new(5.0, 5.0)));
+    /// ```
+    pub fn clipped_cell(&self, site: usize, bounds: Rect<T>) -> Vec<Point<T>> {
+        let dcel = self.dcel;
+        let p = self.points[site];
+
+        let mut neighbors = self.site_triangles(site).peekable();
+        if neighbors.peek().is_none() {
+            return Vec::new();
+        }
+
+        let mut cell = vec![
+            bounds.min,
+            Point::new(bounds.max.x, bounds.min.y),
+            bounds.max,
+            Point::new(bounds.min.x, bounds.max.y),
+        ];
+
+        // both other vertices of every triangle, so the last neighbor of a
+        // hull point isn't missed
+        for e in neighbors {
+            for &q in &[dcel.edge_endpoint(e), dcel.vertices[dcel.prev_edge(e)]] {
+                cell = clip_bisector(&cell, p, self.points[q.as_usize()]);
+            }
+        }
+
+        cell
+    }
+
+    /// Returns the number of Voronoi vertices of every cell.
+    ///
+    /// Cells of hull points are unbounded, only their finite vertices are
+    /// counted. Points which were skipped during triangulation (duplicates)
+    /// have empty cells.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{voronoi::Voronoi, Delaunay, Point};
+    /// let points = &[
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(100.0, 0.0),
+    ///     Point::new(100.0, 100.0),
+    ///     Point::new(0.0, 100.0),
+    ///     Point::new(40.0, 60.0),
+    /// ];
+    /// let t = Delaunay::new(points).unwrap();
+    /// let sizes = Voronoi::new(&t, points).cell_sizes();
+    /// assert_eq!(sizes[4], 4);
+    /// ```
+    pub fn cell_sizes(&self) -> Vec<usize> {
+        (0..self.num_sites())
+            .map(|site| self.site_triangles(site).count())
+            .collect()
+    }
+}
+
+/// Clips the polygon to the half-plane of points closer to `p` than to `q`
+/// (one step of Sutherland-Hodgman).
+fn clip_bisector<T: Scalar>(polygon: &[Point<T>], p: Point<T>, q: Point<T>) -> Vec<Point<T>> {
+    let two = T::from_f64(2.0);
+    let mid = Point::new((p.x + q.x) / two, (p.y + q.y) / two);
+    let (nx, ny) = (q.x - p.x, q.y - p.y);
+
+    // positive on the side of `q`
+    let side = |a: Point<T>| (a.x - mid.x) * nx + (a.y - mid.y) * ny;
+
+    let mut clipped = Vec::with_capacity(polygon.len() + 1);
+
+    for (i, &a) in polygon.iter().enumerate() {
+        let b = polygon[(i + 1) % polygon.len()];
+        let (sa, sb) = (side(a), side(b));
+
+        if sa <= T::ZERO {
+            clipped.push(a);
+        }
+
+        if (sa < T::ZERO && sb > T::ZERO) || (sa > T::ZERO && sb < T::ZERO) {
+            let t = sa / (sa - sb);
+            clipped.push(Point::new(a.x + (b.x - a.x) * t, a.y + (b.y - a.y) * t));
+        }
+    }
+
+    clipped
+}
+
+/// Returns the area-weighted centroid of the polygon, or `None` if it is
+/// degenerate.
+fn polygon_centroid<T: Scalar>(polygon: &[Point<T>]) -> Option<Point<T>> {
+    let (mut area, mut cx, mut cy) = (T::ZERO, T::ZERO, T::ZERO);
+
+    for (i, &a) in polygon.iter().enumerate() {
+        let b = polygon[(i + 1) % polygon.len()];
+        let cross = a.x * b.y - b.x * a.y;
+
+        area = area + cross;
+        cx = cx + (a.x + b.x) * cross;
+        cy = cy + (a.y + b.y) * cross;
+    }
+
+    if area.abs() <= T::EPSILON {
+        return None;
+    }
+
+    let three = T::from_f64(3.0);
+    Some(Point::new(cx / (three * area), cy / (three * area)))
+}
+
+/// Returns the area of the polygon by the shoelace formula
+fn polygon_area<T: Scalar>(polygon: &[Point<T>]) -> T {
+    let twice: f64 = polygon
+        .iter()
+        .zip(polygon.iter().cycle().skip(1))
+        .map(|(a, b)| a.x.to_f64() * b.y.to_f64() - b.x.to_f64() * a.y.to_f64())
+        .sum();
+
+    T::from_f64((twice / 2.0).abs())
+}
+
+/// Runs `iterations` steps of Lloyd's algorithm, moving every point to the
+/// centroid of its Voronoi cell clipped to `bounds`.
+///
+/// Returns the triangulation of the relaxed points. Points which are not
+/// part of the triangulation (duplicates) or outside of `bounds` stay where
+/// they are.
+///
+/// # Examples
+/// ```
+/// # use triangulation::{voronoi::lloyd_relax, Point, Rect};
+/// let mut points = vec![
+///     Point::new(1.0, 1.0),
+///     Point::new(2.0, 1.0),
+///     Point::new(1.0, 2.0),
+///     Point::new(2.0, 2.0),
+/// ];
+/// let bounds = Rect::new(Point::new(0.0, 0.0), Point::new(10.0, 10.0));
+/// let t = lloyd_relax(&mut points, bounds, 50).unwrap();
+///
+/// assert_eq!(t.dcel.num_triangles(), 2);
+/// assert!(points[0].x < 5.0 && points[3].x > 5.0);
+/// ```
+pub fn lloyd_relax<T: Scalar>(
+    points: &mut [Point<T>],
+    bounds: Rect<T>,
+    iterations: usize,
+) -> Result<Delaunay<T>, DelaunayError> {
+    for _ in 0..iterations {
+        let t = Delaunay::new(points)?;
+        let voronoi = t.voronoi();
+
+        let centroids = (0..points.len())
+            .map(|site| polygon_centroid(&voronoi.clipped_cell(site, bounds)))
+            .collect::<Vec<_>>();
+
+        for (p, centroid) in points.iter_mut().zip(centroids) {
+            if let Some(c) = centroid {
+                *p = c;
+            }
+        }
+    }
+
+    Delaunay::new(points)
+}
+
+impl<T: Scalar> Delaunay<T> {
+    /// Returns the Voronoi diagram of the triangulation, see
+    /// [`Voronoi::new`].
+    pub fn voronoi(&self) -> Voronoi<'_, T> {
+        Voronoi::new(self, &self.points)
+    }
+
+    /// Returns the Voronoi cell of a single point as its vertices in
+    /// counter-clockwise order.
+    ///
+    /// Returns `None` for hull points, whose cells are unbounded, and for
+    /// points which are not part of the triangulation.
+    ///
+    /// Finds an edge going out of the point by scanning the triangles, so it
+    /// takes O(n) time but doesn't need the point-to-triangle map.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Delaunay, Point};
+    /// let mut points = vec![Point::new(100.0, 100.0)];
+    ///
+    /// for i in 0..8 {
+    ///     let angle = i as f32 / 8.0 * 2.0 * std::f32::consts::PI;
+    ///     let (sin, cos) = angle.sin_cos();
+    ///     points.push(Point::new(cos * 100.0 + 100.0, sin * 100.0 + 100.0));
+    /// }
+    ///
+    /// let t = Delaunay::new(&points).unwrap();
+    /// let cell = t.voronoi_cell(0.into()).unwrap();
+    ///
+    /// assert_eq!(cell.len(), 8);
+    /// assert!(t.voronoi_cell(1.into()).is_none());
+    /// ```
+    pub fn voronoi_cell(&self, p: PointIndex) -> Option<Vec<Point<T>>> {
+        voronoi_cell(self, p, &self.points)
+    }
+
+    /// Returns the area of the Voronoi cell of every point.
+    ///
+    /// Without `bounds` the cells of hull points are unbounded and have
+    /// infinite area. With `bounds` every cell is clipped to the rectangle,
+    /// so the areas sum up to its area as long as all points lie inside it.
+    /// Points which are not part of the triangulation have zero area.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Delaunay, Point, Rect};
+    /// let points = &[
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(10.0, 0.0),
+    ///     Point::new(10.0, 10.0),
+    ///     Point::new(0.0, 10.0),
+    ///     Point::new(5.0, 5.0),
+    /// ];
+    /// let t = Delaunay::new(points).unwrap();
+    ///
+    /// let areas = t.cell_areas(None);
+    /// assert_eq!(areas[0], f32::INFINITY);
+    /// assert!((areas[4] - 50.0).abs() < 1e-3);
+    ///
+    /// let bounds = Rect::new(Point::new(0.0, 0.0), Point::new(10.0, 10.0));
+    /// let areas = t.cell_areas(Some(bounds));
+    /// assert!((areas[0] - 12.5).abs() < 1e-3);
+    /// ```
+    pub fn cell_areas(&self, bounds: Option<Rect<T>>) -> Vec<T> {
+        cell_areas(self, &self.points, bounds)
+    }
+
+    /// Returns the barycentric dual cell of every point.
+    ///
+    /// The cell of a point is made of the midpoints of its edges and the
+    /// centroids of its triangles, in counter-clockwise order. Unlike Voronoi
+    /// cells it always stays inside the triangles around the point. Cells of
+    /// hull points are closed along the hull edges through the point itself.
+    /// The cells tile the triangulation exactly.
+    ///
+    /// Cells are indexed by point, points which are not part of the
+    /// triangulation have empty cells.
+    ///
+    /// # Examples
+    /// ```
+    /// # use triangulation::{Delaunay, Point};
+    /// let points = &[
+    ///     Point::new(0.0, 0.0),
+    ///     Point::new(30.0, 0.0),
+    ///     Point::new(0.0, 30.0),
+    /// ];
+    /// let t = Delaunay::new(points).unwrap();
+    /// let cells = t.barycentric_dual_cells();
+    ///
+    /// assert_eq!(cells[0].len(), 4);
+    /// assert!(cells[0].contains(&Point::new(10.0, 10.0)));
+    /// ```
+    pub fn barycentric_dual_cells(&self) -> Vec<Vec<Point<T>>> {
+        barycentric_dual_cells(self, &self.points)
+    }
+}
+
+/// Like [`Delaunay::voronoi_cell`], for any [`Triangulation`] with the points
+/// passed in. The triangulation has to be Delaunay in them, like in
+/// [`Voronoi::new`].
+pub fn voronoi_cell<T: Triangulation + ?Sized>(
+    triangulation: &T,
+    p: PointIndex,
+    points: &[Point<T::Scalar>],
+) -> Option<Vec<Point<T::Scalar>>> {
+    let dcel = triangulation.dcel();
+    // the last edge, like in the point-to-triangle map, so the cell starts
+    // at the same vertex as in the Voronoi diagram
+    let start = dcel.vertices.iter().rposition(|&v| v == p)?;
+    let mut around = dcel.triangles_around_edge(start.into()).peekable();
+
+    // the fan starts at the boundary if it is interrupted by a missing twin
+    let closed = around.peek().map_or(false, |&e| dcel.twin(e).is_some());
+    if !closed {
+        return None;
+    }
+
+    Some(
+        around
+            .map(|e| dcel.triangle(e, points).circumcenter())
+            .collect(),
+    )
+}
+
+/// Like [`Delaunay::cell_areas`], for any [`Triangulation`] with the points
+/// passed in, see [`voronoi_cell`].
+pub fn cell_areas<T: Triangulation + ?Sized>(
+    triangulation: &T,
+    points: &[Point<T::Scalar>],
+    bounds: Option<Rect<T::Scalar>>,
+) -> Vec<T::Scalar> {
+    let voronoi = Voronoi::new(triangulation, points);
+
+    match bounds {
+        Some(bounds) => (0..voronoi.num_sites())
+            .map(|site| polygon_area(&voronoi.clipped_cell(site, bounds)))
+            .collect(),
+        None => voronoi
+            .cells()
+            .iter()
+            .map(|cell| {
+                if cell.unbounded {
+                    T::Scalar::INFINITY
+                } else {
+                    polygon_area(&cell.vertices)
+                }
+            })
+            .collect(),
+    }
+}
+
+/// Like [`Delaunay::barycentric_dual_cells`], for any [`Triangulation`] with
+/// the points passed in. They have to be indexed like the triangulation, with
+/// its triangles counter-clockwise.
+pub fn barycentric_dual_cells<T: Triangulation + ?Sized>(
+    triangulation: &T,
+    points: &[Point<T::Scalar>],
+) -> Vec<Vec<Point<T::Scalar>>> {
+    let dcel = triangulation.dcel();
+    let voronoi = Voronoi::new(triangulation, points);
+    let two = T::Scalar::from_f64(2.0);
+    let midpoint = |a: Point<T::Scalar>, b: Point<T::Scalar>| (a + b) / two;
+
+    (0..points.len())
+        .map(|site| {
+            let p = points[site];
+            let around = voronoi.site_triangles(site).collect::<Vec<_>>();
+
+            let last = match around.last() {
+                Some(&last) => last,
+                None => return Vec::new(),
+            };
+
+            let mut cell = Vec::with_capacity(2 * around.len() + 2);
+
+            if dcel.twin(around[0]).is_none() {
+                cell.push(p);
+            }
+
+            for &e in &around {
+                let tri = dcel.triangle(e, points);
+                cell.push(midpoint(p, tri.1));
+                cell.push(tri.centroid());
+            }
+
+            if dcel.twin(dcel.prev_edge(last)).is_none() {
+                cell.push(midpoint(p, points[dcel.vertices[dcel.prev_edge(last)]]));
+            }
+
+            cell
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{circular, random_points};
+    use crate::DelaunayBuilder;
+
+    #[test]
+    fn cell_sizes() {
+        let count = 12;
+        let points = circular(count);
+
+        let t = Delaunay::new(&points).unwrap();
+        let sizes = Voronoi::new(&t, &points).cell_sizes();
+
+        assert_eq!(sizes[0], count);
+        assert!(sizes[1..].iter().all(|&s| s > 0 && s < count));
+    }
+
+    #[test]
+    fn bounding_frame() {
+        let points = random_points(200, 4);
+
+        let plain = Delaunay::new(&points).unwrap();
+        let voronoi = Voronoi::new(&plain, &points);
+        assert!(!(0..points.len()).all(|site| voronoi.is_bounded(site)));
+
+        let t = DelaunayBuilder::new()
+            .bounding_frame(1.0)
+            .build(&points)
+            .unwrap();
+        assert_eq!(&t.points()[200..], t.frame_positions());
+
+        let voronoi = Voronoi::new(&t, t.points());
+        assert_eq!(voronoi.num_sites(), 200);
+        assert_eq!(voronoi.cell_sizes().len(), 200);
+        assert!((0..200).all(|site| voronoi.is_bounded(site)));
+
+        let voronoi = voronoi.exclude_frame(false);
+        assert_eq!(voronoi.num_sites(), 204);
+        assert!((200..204).all(|site| !voronoi.is_bounded(site)));
+    }
+
+    #[test]
+    fn cells() {
+        let points = random_points(300, 6);
+
+        let t = Delaunay::new(&points).unwrap();
+        let hull = t.hull();
+        let cells = t.voronoi().cells();
+        assert_eq!(cells.len(), points.len());
+
+        for (site, cell) in cells.iter().enumerate() {
+            let on_hull = hull.contains(&site.into());
+            assert_eq!(cell.unbounded, on_hull);
+            assert_eq!(cell.rays.is_some(), on_hull);
+            assert!(!cell.vertices.is_empty());
+
+            // every vertex is at least as close to the site as to any other
+            for v in &cell.vertices {
+                let d = v.distance_sq(points[site]).sqrt();
+                assert!(points.iter().all(|p| p.distance_sq(*v).sqrt() >= d - 1e-2));
+            }
+
+            if let Some([first, last]) = cell.rays {
+                // moving along the rays keeps the site closest
+                let far =
+                    |v: Point, dir: Point| Point::new(v.x + dir.x * 1000.0, v.y + dir.y * 1000.0);
+                for &p in &[
+                    far(cell.vertices[0], first),
+                    far(*cell.vertices.last().unwrap(), last),
+                ] {
+                    let d = p.distance_sq(points[site]).sqrt();
+                    assert!(points.iter().all(|q| q.distance_sq(p).sqrt() >= d - 1e-1));
+                }
+            } else {
+                assert!(signed_area(&cell.vertices).abs() > 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn single_cell() {
+        let points = random_points(200, 7);
+
+        let t = Delaunay::new(&points).unwrap();
+        let cells = t.voronoi().cells();
+
+        for (site, cell) in cells.into_iter().enumerate() {
+            match t.voronoi_cell(site.into()) {
+                Some(vertices) => {
+                    assert!(!cell.unbounded);
+                    assert_eq!(vertices, cell.vertices);
+                }
+                None => assert!(cell.unbounded),
+            }
+        }
+    }
+
+    #[test]
+    fn lloyd() {
+        let mut points = random_points(200, 8);
+
+        let bounds = Rect::new(Point::new(0.0, 0.0), Point::new(100.0, 100.0));
+
+        let min_distance = |points: &[Point]| {
+            let t = Delaunay::new(points).unwrap();
+            t.dcel
+                .wireframe_edges()
+                .map(|(a, b, _)| {
+                    points[a.as_usize()]
+                        .distance_sq(points[b.as_usize()])
+                        .sqrt()
+                })
+                .fold(f32::INFINITY, f32::min)
+        };
+
+        let before = min_distance(&points);
+        let t = lloyd_relax(&mut points, bounds, 20).unwrap();
+        assert_eq!(t.points(), &points[..]);
+
+        for p in &points {
+            assert!(p.x >= 0.0 && p.x <= 100.0 && p.y >= 0.0 && p.y <= 100.0);
+        }
+
+        // relaxed points are spread almost evenly, roughly 7 units apart
+        assert!(min_distance(&points) > 3.0 * before.max(1.0));
+
+        // the clipped cells tile the bounds
+        let voronoi = t.voronoi();
+        let total: f64 = (0..points.len())
+            .map(|site| signed_area(&voronoi.clipped_cell(site, bounds)).abs())
+            .sum();
+        assert!((total - 100.0 * 100.0).abs() < 1.0, "{}", total);
+    }
+
+    #[test]
+    fn cell_areas() {
+        let points = random_points(500, 9);
+
+        let t = Delaunay::new(&points).unwrap();
+        let hull = t.hull();
+
+        let unbounded = t.cell_areas(None);
+        for (site, area) in unbounded.iter().enumerate() {
+            assert_eq!(area.is_infinite(), hull.contains(&site.into()));
+        }
+
+        let bounds = Rect::new(Point::new(0.0, 0.0), Point::new(100.0, 100.0));
+        let clipped = t.cell_areas(Some(bounds));
+
+        let total: f64 = clipped.iter().map(|&a| f64::from(a)).sum();
+        assert!((total - 100.0 * 100.0).abs() < 1.0, "{}", total);
+
+        // clipping only shrinks cells
+        for (a, b) in clipped.iter().zip(&unbounded) {
+            assert!(a <= &(b * (1.0 + 1e-4)));
+        }
+    }
+
+    fn signed_area(polygon: &[Point]) -> f64 {
+        let n = polygon.len();
+        (0..n)
+            .map(|i| {
+                let (a, b) = (polygon[i], polygon[(i + 1) % n]);
+                f64::from(a.x) * f64::from(b.y) - f64::from(b.x) * f64::from(a.y)
+            })
+            .sum::<f64>()
+            / 2.0
+    }
+
+    #[test]
+    fn barycentric_area() {
+        let points = random_points(500, 9);
+
+        let t = Delaunay::new(&points).unwrap();
+        let cells = t.barycentric_dual_cells();
+        assert_eq!(cells.len(), points.len());
+
+        let total: f64 = t
+            .dcel
+            .triangles(&points)
+            .map(|t| signed_area(&[t.0, t.1, t.2]))
+            .sum();
+
+        let areas = cells.iter().map(|c| signed_area(c)).collect::<Vec<_>>();
+
+        // all cells have the same winding as the triangles
+        assert!(areas.iter().all(|a| a.signum() == total.signum()));
+        assert!((areas.iter().sum::<f64>() - total).abs() < 1e-6 * total.abs());
+    }
+}
@@ -0,0 +1,232 @@
+//! Shared support for the integration tests
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use triangulation::{Delaunay, Point};
+
+/// Input points with the expected triangulation
+pub struct Fixture {
+    pub name: String,
+    pub path: PathBuf,
+    pub comments: Vec<String>,
+    pub points: Vec<Point>,
+    pub triangles: Vec<[usize; 3]>,
+
+    /// Expected triangles with the `robust` feature, if its exact predicates
+    /// break ties between cocircular points differently
+    pub robust_triangles: Option<Vec<[usize; 3]>>,
+}
+
+impl Fixture {
+    /// Parses a fixture file.
+    ///
+    /// The format is line based: a `points N` header followed by `N` lines of
+    /// `x y`, then a `triangles M` header followed by `M` lines of `a b c`,
+    /// and optionally a `triangles robust M` section in the same format.
+    /// Empty lines and lines starting with `#` are ignored.
+    pub fn load(path: &Path) -> Fixture {
+        let text = fs::read_to_string(path).unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+        let comments = text
+            .lines()
+            .filter(|l| l.starts_with('#'))
+            .map(str::to_owned)
+            .collect();
+        let mut lines = text
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && !l.starts_with('#'));
+
+        let mut section = |name: &str| {
+            let header = lines.next()?;
+            let count = header
+                .strip_prefix(name)
+                .and_then(|n| n.trim().parse::<usize>().ok())
+                .unwrap_or_else(|| panic!("{}: bad header {:?}", path.display(), header));
+
+            let fields = (0..count)
+                .map(|_| {
+                    lines
+                        .next()
+                        .unwrap_or_else(|| panic!("{}: truncated {}", path.display(), name))
+                })
+                .map(|l| l.split_whitespace().map(str::to_owned).collect::<Vec<_>>())
+                .collect::<Vec<_>>();
+
+            Some(fields)
+        };
+
+        let points = section("points")
+            .unwrap_or_else(|| panic!("{}: missing points", path.display()))
+            .iter()
+            .map(|f| Point::new(f[0].parse().unwrap(), f[1].parse().unwrap()))
+            .collect();
+
+        let parse_triangles = |section: Vec<Vec<String>>| -> Vec<[usize; 3]> {
+            section
+                .iter()
+                .map(|f| {
+                    [
+                        f[0].parse().unwrap(),
+                        f[1].parse().unwrap(),
+                        f[2].parse().unwrap(),
+                    ]
+                })
+                .collect()
+        };
+
+        let triangles = section("triangles")
+            .map(parse_triangles)
+            .unwrap_or_else(|| panic!("{}: missing triangles", path.display()));
+        let robust_triangles = section("triangles robust").map(parse_triangles);
+
+        Fixture {
+            name: path.file_stem().unwrap().to_string_lossy().into_owned(),
+            path: path.to_owned(),
+            comments,
+            points,
+            triangles,
+            robust_triangles,
+        }
+    }
+
+    /// Returns the expected triangles for the enabled features
+    pub fn expected(&self) -> &[[usize; 3]] {
+        match &self.robust_triangles {
+            Some(triangles) if cfg!(feature = "robust") => triangles,
+            _ => &self.triangles,
+        }
+    }
+
+    /// Writes the fixture back with the given triangles as the expectation
+    /// for the enabled features.
+    ///
+    /// The `robust` expectation is only kept where it differs from the
+    /// default one.
+    pub fn save(&self, triangles: &[[usize; 3]]) {
+        let (plain, robust) = if cfg!(feature = "robust") {
+            (&self.triangles[..], Some(triangles))
+        } else {
+            (triangles, self.robust_triangles.as_deref())
+        };
+        let robust = robust.filter(|&robust| robust != plain);
+
+        let mut out = String::new();
+        for comment in &self.comments {
+            writeln!(out, "{}", comment).unwrap();
+        }
+
+        writeln!(out, "points {}", self.points.len()).unwrap();
+        for p in &self.points {
+            writeln!(out, "{} {}", p.x, p.y).unwrap();
+        }
+
+        writeln!(out, "triangles {}", plain.len()).unwrap();
+        for t in plain {
+            writeln!(out, "{} {} {}", t[0], t[1], t[2]).unwrap();
+        }
+
+        if let Some(robust) = robust {
+            writeln!(out, "triangles robust {}", robust.len()).unwrap();
+            for t in robust {
+                writeln!(out, "{} {} {}", t[0], t[1], t[2]).unwrap();
+            }
+        }
+
+        fs::write(&self.path, out).unwrap();
+    }
+}
+
+/// Returns all fixtures in `tests/fixtures`, sorted by name
+pub fn fixtures() -> Vec<Fixture> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    let mut paths = fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|p| p.extension().map_or(false, |e| e == "txt"))
+        .collect::<Vec<_>>();
+
+    paths.sort();
+    paths.iter().map(|p| Fixture::load(p)).collect()
+}
+
+/// Returns the triangles of the triangulation in a canonical order: every
+/// triangle starts at its smallest point, keeping the winding, and the
+/// triangles are sorted.
+pub fn canonical_triangles(t: &Delaunay) -> Vec<[usize; 3]> {
+    let mut triangles = (0..t.dcel.num_triangles())
+        .map(|i| {
+            let [a, b, c] = t.dcel.triangle_points((3 * i).into());
+            let [a, b, c] = [a.as_usize(), b.as_usize(), c.as_usize()];
+
+            if a < b && a < c {
+                [a, b, c]
+            } else if b < c {
+                [b, c, a]
+            } else {
+                [c, a, b]
+            }
+        })
+        .collect::<Vec<_>>();
+
+    triangles.sort();
+    triangles
+}
+
+/// Draws the expected and actual triangles into an SVG file, highlighting
+/// the triangles present in only one of them.
+pub fn write_svg_diff(
+    path: &Path,
+    points: &[Point],
+    expected: &[[usize; 3]],
+    actual: &[[usize; 3]],
+) {
+    let (mut min, mut max) = (points[0], points[0]);
+    for p in points {
+        min = Point::new(min.x.min(p.x), min.y.min(p.y));
+        max = Point::new(max.x.max(p.x), max.y.max(p.y));
+    }
+
+    let size = (max.x - min.x).max(max.y - min.y).max(1e-6);
+    let stroke = size / 500.0;
+
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{} {} {} {}">"#,
+        min.x - size * 0.05,
+        min.y - size * 0.05,
+        size * 1.1,
+        size * 1.1
+    )
+    .unwrap();
+
+    let mut polygon = |t: &[usize; 3], style: &str| {
+        let (a, b, c) = (points[t[0]], points[t[1]], points[t[2]]);
+        writeln!(
+            svg,
+            r#"<polygon points="{},{} {},{} {},{}" stroke-width="{}" {}/>"#,
+            a.x, a.y, b.x, b.y, c.x, c.y, stroke, style
+        )
+        .unwrap();
+    };
+
+    for t in actual.iter().filter(|t| expected.binary_search(t).is_ok()) {
+        polygon(t, r#"fill="none" stroke="gray""#);
+    }
+
+    // missing triangles in green, unexpected ones in red
+    for t in expected.iter().filter(|t| actual.binary_search(t).is_err()) {
+        polygon(t, r#"fill="green" fill-opacity="0.3" stroke="green""#);
+    }
+
+    for t in actual.iter().filter(|t| expected.binary_search(t).is_err()) {
+        polygon(t, r#"fill="red" fill-opacity="0.3" stroke="red""#);
+    }
+
+    svg.push_str("</svg>\n");
+
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, svg).unwrap();
+}
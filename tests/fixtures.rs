@@ -0,0 +1,50 @@
+//! Golden-output regression tests.
+//!
+//! Every file in `tests/fixtures` holds input points and the expected
+//! triangles. The exact predicates of the `robust` feature may break ties
+//! between cocircular points differently, so a fixture can record separate
+//! triangles for it. Run with `UPDATE_FIXTURES=1`, with and without
+//! `--features robust`, to regenerate the expectations after an intended
+//! change of the output.
+
+mod common;
+
+use std::env;
+use std::path::Path;
+
+use triangulation::Delaunay;
+
+use common::{canonical_triangles, fixtures, write_svg_diff};
+
+#[test]
+fn golden_fixtures() {
+    let update = env::var_os("UPDATE_FIXTURES").map_or(false, |v| v == "1");
+    let fixtures = fixtures();
+    assert!(!fixtures.is_empty());
+
+    let mut failed = Vec::new();
+
+    for fixture in &fixtures {
+        let t = Delaunay::new(&fixture.points).expect("fixture can't be triangulated");
+        let actual = canonical_triangles(&t);
+
+        if update {
+            fixture.save(&actual);
+            continue;
+        }
+
+        let mut expected = fixture.expected().to_vec();
+        expected.sort();
+
+        if actual != expected {
+            let svg = Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("target/fixture-diffs")
+                .join(format!("{}.svg", fixture.name));
+
+            write_svg_diff(&svg, &fixture.points, &expected, &actual);
+            failed.push(format!("{} (diff in {})", fixture.name, svg.display()));
+        }
+    }
+
+    assert!(failed.is_empty(), "fixtures differ: {}", failed.join(", "));
+}
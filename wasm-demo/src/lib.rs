@@ -8,11 +8,10 @@ extern {
 
 #[wasm_bindgen]
 pub fn triangulate(p: &[f32]) -> Vec<u32> {
-    let mut points = Vec::with_capacity(p.len() / 2);
-
-    for i in (0..p.len()).step_by(2) {
-        points.push(Point::new(p[i], p[i + 1]));
-    }
+    let points = p
+        .chunks_exact(2)
+        .map(|c| Point::from([c[0], c[1]]))
+        .collect::<Vec<_>>();
 
     let t = Delaunay::new(&points).unwrap();
     t.dcel.vertices.iter().map(|&v| v.as_usize() as u32).collect()